@@ -428,7 +428,12 @@ impl LlmClient {
             LlmProvider::Ollama => Arc::new(OllamaClient::new(model)?),
         };
         
-        Ok(Self {
+        Ok(Self::with_provider(provider))
+    }
+
+    /// Client for any provider (e.g. a stub in tests), without storage
+    pub fn with_provider(provider: Arc<dyn LlmProviderTrait>) -> Self {
+        Self {
             provider,
             storage: None,
             conversations: Arc::new(RwLock::new(LruCache::new(
@@ -439,7 +444,7 @@ impl LlmClient {
             ))),
             conversation_ttl: Duration::from_secs(1800), // Reduced from 1 hour to 30 minutes
             cache_ttl: Duration::from_secs(1800), // Reduced from 1 hour to 30 minutes
-        })
+        }
     }
 
     pub async fn with_storage(provider_type: LlmProvider, model: &str, collection: Option<String>) -> Result<Self> {
@@ -0,0 +1,61 @@
+// Bearer token check for the admin routes. The runtime lexicon edits (`POST`/`DELETE /lexicon`)
+// change how every client's text is spoken and invalidate cached audio, so they cannot be open
+// to anyone who reaches the server; `POST /voices/reload` uses the same check.
+
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Middleware for the admin routes: requires `Authorization: Bearer <ADMIN_TOKEN>`.
+/// Without a configured token the routes are refused.
+pub async fn require_admin(State(state): State<AppState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    check_admin_token(request.headers(), state.config.admin_token.as_deref())?;
+    Ok(next.run(request).await)
+}
+
+fn check_admin_token(headers: &HeaderMap, token: Option<&str>) -> Result<(), ApiError> {
+    let Some(token) = token else {
+        return Err(ApiError::Unauthorized("Admin API disabled: ADMIN_TOKEN is not set".to_string()));
+    };
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    match given {
+        Some(given) if constant_time_eq(given.as_bytes(), token.as_bytes()) => Ok(()),
+        Some(_) => Err(ApiError::Unauthorized("Invalid admin token".to_string())),
+        None => Err(ApiError::Unauthorized("Missing admin token (Authorization: Bearer ...)".to_string())),
+    }
+}
+
+/// Compare without returning early, so the time taken does not reveal the matching prefix
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_token() {
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(AUTHORIZATION, value.parse().unwrap());
+            headers
+        };
+        assert!(check_admin_token(&headers("Bearer secret"), Some("secret")).is_ok());
+        assert!(check_admin_token(&headers("Bearer secreT"), Some("secret")).is_err());
+        assert!(check_admin_token(&headers("secret"), Some("secret")).is_err());
+        assert!(check_admin_token(&HeaderMap::new(), Some("secret")).is_err());
+        // No token configured: always refused
+        assert!(check_admin_token(&headers("Bearer "), None).is_err());
+    }
+}
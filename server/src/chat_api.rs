@@ -0,0 +1,589 @@
+// The /chat and /voice-chat endpoints and the /ws/chat/stream WebSocket: LLM replies, spoken
+// with the TTS manager
+
+use std::sync::atomic::Ordering;
+
+use axum::{
+    extract::{State, WebSocketUpgrade},
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::tts_api::{clean_text_for_tts, ProsodyParams};
+use crate::validation::{parse_audio_format, validate_chat_request, validate_conversation_id, validate_loudness, validate_sample_rate, validate_silence, validate_speaker_id};
+
+#[derive(Deserialize)]
+pub struct ChatRequest {
+    message: String,
+    conversation_id: Option<String>,
+    language: Option<String>, // For TTS language selection
+}
+
+#[derive(Serialize)]
+pub struct ChatResponse {
+    reply: String,
+    conversation_id: String,
+    audio_base64: Option<String>, // Audio for bot response
+    sample_rate: Option<u32>,
+    duration_ms: Option<u64>,
+}
+
+pub async fn chat_endpoint(
+    State(state): State<AppState>,
+    Json(req): Json<ChatRequest>,
+) -> Result<Json<ChatResponse>, ApiError> {
+    state.request_count.fetch_add(1, Ordering::Relaxed);
+    let start_time = std::time::Instant::now();
+    
+    validate_chat_request(&req.message)?;
+    if let Some(ref id) = req.conversation_id {
+        validate_conversation_id(id)?;
+    }
+
+    let message = req.message.clone();
+    let conv_id = req.conversation_id.clone();
+    let llm = state.llm.clone();
+    let language = req.language.clone();
+
+    info!("Chat request received: message length={}, conv_id={:?}", message.len(), conv_id);
+
+    // Run LLM async with timeout (no blocking needed - fully async now)
+    let conv_id = conv_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let result = tokio::time::timeout(
+        state.config.llm_timeout(),
+        llm.chat_with_history(Some(conv_id.clone()), &message)
+    )
+    .await;
+
+    let reply = match result {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(e)) => {
+            state.metrics.chat.record_error();
+            state.metrics.llm_specific.record_error();
+            error!("LLM error: {}", e);
+            return Err(ApiError::LlmError(format!("LLM error: {e}")));
+        }
+        Err(_) => {
+            let timeout_secs = state.config.llm_timeout().as_secs();
+            state.metrics.chat.record_error();
+            state.metrics.llm_specific.record_timeout();
+            error!("LLM request timed out after {} seconds", timeout_secs);
+            return Err(ApiError::LlmError(format!(
+                "Request timed out after {} seconds. Please try again with a shorter message.",
+                timeout_secs
+            )));
+        }
+    };
+
+    let total_latency_ms = start_time.elapsed().as_millis() as u64;
+    
+    // Record metrics
+    state.metrics.chat.record_request(total_latency_ms);
+    state.metrics.llm_specific.record_request(total_latency_ms, reply.len());
+    
+    info!("LLM response received in {:.2}s, reply length={}", total_latency_ms as f64 / 1000.0, reply.len());
+
+    // Return text immediately - TTS generation moved to background for speed
+    // This ensures response time is only limited by LLM, not TTS
+    let response = ChatResponse {
+        reply: reply.clone(),
+        conversation_id: conv_id.clone(),
+        audio_base64: None,
+        sample_rate: None,
+        duration_ms: None,
+    };
+
+    // Generate TTS in background (completely non-blocking)
+    if let Some(lang) = language {
+        let tts_state = state.tts.clone();
+        // Clean text for natural TTS speech with pauses and prosody
+        let reply_for_tts = clean_text_for_tts(&reply, &lang);
+        tokio::spawn(async move {
+            let _ = tokio::task::spawn_blocking(move || {
+                if let Ok((samples, sr)) = tts_state.synthesize_with_sample_rate(&reply_for_tts, Some(&lang), None, None) {
+                    let _ = tts_core::TtsManager::encode_wav_base64(&samples, sr);
+                    // Audio generated in background - frontend can request via /tts if needed
+                }
+            }).await;
+        });
+    }
+
+    Ok(Json(response))
+}
+
+#[derive(Deserialize)]
+pub struct VoiceChatRequest {
+    message: String,
+    conversation_id: Option<String>,
+    language: Option<String>,
+    voice: Option<String>, // voice ID (e.g., "norman", "thorsten")
+    speaker: Option<i64>, // speaker ID for multi-speaker voices
+    #[serde(flatten)]
+    prosody: ProsodyParams,
+    #[serde(default)]
+    expressive: bool, // apply per-sentence emotion hints (rate + pitch)
+    format: Option<String>, // output format, as for /tts
+    sample_rate: Option<u32>, // resample output (Hz), as for /tts
+    #[serde(flatten)]
+    loudness: tts_core::LoudnessTarget, // target_lufs / true_peak_db, as for /tts
+    #[serde(default)]
+    alignment: bool, // return word timings, as for /tts
+    #[serde(flatten)]
+    silence: tts_core::SilenceOptions, // trim_silence / max_silence_ms, as for /tts
+}
+
+#[derive(Serialize)]
+pub struct VoiceChatResponse {
+    audio_base64: String,
+    sample_rate: u32,
+    mime_type: &'static str,
+    duration_ms: u64,
+    loudness: tts_core::LoudnessReport,
+    conversation_id: String,
+    reply: String, // Original reply for display
+    cleaned_text: String, // Cleaned text that was actually spoken
+    #[serde(skip_serializing_if = "Option::is_none")]
+    words: Option<Vec<tts_core::WordTiming>>, // char offsets into `reply`
+}
+
+pub async fn voice_chat_endpoint(
+    State(state): State<AppState>,
+    Json(req): Json<VoiceChatRequest>,
+) -> Result<Json<VoiceChatResponse>, ApiError> {
+    state.request_count.fetch_add(1, Ordering::Relaxed);
+    let start_time = std::time::Instant::now();
+    validate_chat_request(&req.message)?;
+    if let Some(ref id) = req.conversation_id {
+        validate_conversation_id(id)?;
+    }
+    validate_speaker_id(req.speaker)?;
+    req.prosody.validate()?;
+    let format = parse_audio_format(req.format.as_deref())?;
+    validate_sample_rate(req.sample_rate)?;
+    validate_loudness(&req.loudness)?;
+    validate_silence(&req.silence)?;
+
+    let message = req.message.clone();
+    let conv_id = req.conversation_id.clone();
+    let llm = state.llm.clone();
+    // Default to en_US if available, otherwise de_DE
+    let default_lang = if state.tts.list_languages().contains(&"en_US".to_string()) {
+        "en_US"
+    } else {
+        "de_DE"
+    };
+    let language = req.language.clone().unwrap_or_else(|| default_lang.to_string());
+
+    // Get LLM response with timeout (fully async now)
+    let conv_id = conv_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let result = tokio::time::timeout(
+        state.config.llm_timeout(),
+        llm.chat_with_history(Some(conv_id.clone()), &message)
+    )
+    .await;
+
+    let reply = match result {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(e)) => {
+            state.metrics.voice_chat.record_error();
+            state.metrics.llm_specific.record_error();
+            error!("LLM error: {}", e);
+            return Err(ApiError::LlmError(format!("LLM error: {e}")));
+        }
+        Err(_) => {
+            let timeout_secs = state.config.llm_timeout().as_secs();
+            state.metrics.voice_chat.record_error();
+            state.metrics.llm_specific.record_timeout();
+            error!("LLM request timed out after {} seconds", timeout_secs);
+            return Err(ApiError::LlmError(format!(
+                "Request timed out after {} seconds. Please try again with a shorter message.",
+                timeout_secs
+            )));
+        }
+    };
+
+    // Clean text for natural TTS speech
+    let cleaned_reply = clean_text_for_tts(&reply, &language);
+    
+    // Generate TTS audio (required for voice chat) - use caching
+    let voice_id = req.voice.as_deref();
+    let tts = state.tts.clone();
+    
+    let tts_start = std::time::Instant::now();
+    let audio = tts
+        .synthesize_with_cache(
+            &cleaned_reply,
+            Some(&language),
+            voice_id,
+            &tts_core::SynthesisOptions {
+                speaker: req.speaker,
+                prosody: req.prosody.to_prosody(),
+                expressive: req.expressive,
+                format,
+                sample_rate: req.sample_rate,
+                loudness: req.loudness,
+                alignment: req.alignment,
+                silence: req.silence,
+                parallelism: state.config.voice_chat_parallelism,
+            },
+        )
+        .await
+        .map_err(|e| {
+            state.metrics.voice_chat.record_error();
+            ApiError::TtsError(e)
+        })?;
+
+    let tts_time_ms = tts_start.elapsed().as_millis() as u64;
+    let total_latency_ms = start_time.elapsed().as_millis() as u64;
+    
+    // Record metrics with cache hit tracking
+    state.metrics.voice_chat.record_request(total_latency_ms);
+    state.metrics.llm_specific.record_request(total_latency_ms, reply.len());
+    state.metrics.tts_specific.record_synthesis(tts_time_ms, 0, audio.cache_hit); // samples not needed for cached responses
+
+    Ok(Json(VoiceChatResponse {
+        audio_base64: audio.audio_base64(),
+        sample_rate: audio.sample_rate,
+        mime_type: audio.mime_type,
+        duration_ms: audio.duration_ms,
+        loudness: audio.loudness,
+        conversation_id: conv_id,
+        words: audio.alignment.map(|alignment| tts_core::map_word_offsets(alignment.words, &cleaned_reply, &reply)),
+        reply: reply.clone(),
+        cleaned_text: cleaned_reply,
+    }))
+}
+
+/// WebSocket endpoint for streaming chat (LLM + TTS)
+/// Accepts query parameters: message, conversation_id (optional), language (optional),
+/// voice (optional), speaker (optional), expressive (optional, "true" to enable emotion hints),
+/// sample_rate (optional, Hz; keeps the chunk rate constant across voices)
+pub async fn chat_stream_ws(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> impl IntoResponse {
+    let message = params.get("message").cloned().unwrap_or_default();
+    let conversation_id = params.get("conversation_id").cloned();
+    let language = params.get("language").cloned();
+    let voice = params.get("voice").cloned();
+    let speaker = match params.get("speaker").map(|s| s.parse::<i64>()) {
+        Some(Ok(sid)) => Some(sid),
+        Some(Err(_)) => {
+            return ws.on_upgrade(move |mut socket| async move {
+                use axum::extract::ws::Message;
+                let error_msg = serde_json::json!({ "error": "Invalid input: speaker must be an integer", "code": 400 });
+                let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+            });
+        }
+        None => None,
+    };
+    let sample_rate = match params.get("sample_rate").map(|s| s.parse::<u32>()) {
+        Some(Ok(rate)) => Some(rate),
+        Some(Err(_)) => {
+            return ws.on_upgrade(move |mut socket| async move {
+                use axum::extract::ws::Message;
+                let error_msg = serde_json::json!({ "error": "Invalid input: sample_rate must be an integer", "code": 400 });
+                let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+            });
+        }
+        None => None,
+    };
+    let expressive = params.get("expressive").is_some_and(|v| v == "true" || v == "1");
+    let max_silence_ms = match params.get("max_silence_ms").map(|s| s.parse::<u32>()) {
+        Some(Ok(ms)) => Some(ms),
+        Some(Err(_)) => {
+            return ws.on_upgrade(move |mut socket| async move {
+                use axum::extract::ws::Message;
+                let error_msg = serde_json::json!({ "error": "Invalid input: max_silence_ms must be an integer", "code": 400 });
+                let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+            });
+        }
+        None => None,
+    };
+    let silence = tts_core::SilenceOptions {
+        trim_silence: params.get("trim_silence").is_some_and(|v| v == "true" || v == "1"),
+        max_silence_ms,
+    };
+    if let Err(e) = validate_silence(&silence) {
+        return ws.on_upgrade(move |mut socket| async move {
+            use axum::extract::ws::Message;
+            let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
+            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+        });
+    }
+    let tts_options = tts_core::SynthesisOptions {
+        speaker,
+        expressive,
+        sample_rate,
+        silence,
+        parallelism: state.config.stream_parallelism,
+        ..Default::default()
+    };
+    
+    if message.is_empty() {
+        return ws.on_upgrade(move |mut socket| async move {
+            use axum::extract::ws::Message;
+            let error_msg = serde_json::json!({ "error": "Message parameter is required", "code": 400 });
+            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+        });
+    }
+    
+    if let Err(e) = validate_chat_request(&message) {
+        return ws.on_upgrade(move |mut socket| async move {
+            use axum::extract::ws::Message;
+            let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
+            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+        });
+    }
+    
+    if let Some(ref id) = conversation_id {
+        if let Err(e) = validate_conversation_id(id) {
+            return ws.on_upgrade(move |mut socket| async move {
+                use axum::extract::ws::Message;
+                let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
+                let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+            });
+        }
+    }
+
+    if let Err(e) = validate_speaker_id(speaker).and_then(|_| validate_sample_rate(sample_rate)) {
+        return ws.on_upgrade(move |mut socket| async move {
+            use axum::extract::ws::Message;
+            let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
+            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
+        });
+    }
+
+    ws.on_upgrade(move |socket| async move {
+        use axum::extract::ws::Message;
+        use futures_util::{SinkExt as _, StreamExt as _};
+        
+        // Split socket into sender and receiver
+        let (mut sender, _receiver) = socket.split();
+        
+        // Send initial status
+        let _ = sender.send(Message::Text(
+            serde_json::json!({ 
+                "type": "status", 
+                "status": "streaming", 
+                "message": "Starting LLM stream..." 
+            }).to_string().into()
+        )).await;
+        
+        // Get LLM client and create stream
+        let llm = state.llm.clone();
+        let message_clone = message.clone();
+        let conv_id_clone = conversation_id.clone();
+        
+        // Create channel for LLM tokens
+        // The task holds the only sender, so the channel closes when the LLM stream ends
+        let (token_stream_tx, mut token_stream_rx) = mpsc::channel::<Result<String, String>>(100);
+        
+        // Spawn task to handle LLM streaming (fully async, no lock needed)
+        tokio::spawn(async move {
+            // Create stream directly (no mutex needed - client is async and thread-safe)
+            let mut stream = llm.chat_with_history_stream(conv_id_clone, &message_clone);
+            
+            // Consume stream and forward tokens
+            use futures_util::StreamExt as _;
+            while let Some(result) = stream.next().await {
+                if token_stream_tx.send(result.map_err(|e| e.to_string())).await.is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        });
+        
+        // Create channel for TTS audio chunks
+        let (tts_tx, mut tts_rx) = mpsc::channel::<Result<(String, u32), String>>(10);
+        let tts_tx_clone = tts_tx.clone();
+        
+        // Stream tokens and optionally generate TTS
+        let mut full_text = String::new();
+        let mut accumulated_text = String::new();
+        let tts_state = state.tts.clone();
+        let lang = language.clone().unwrap_or_else(|| "en_US".to_string());
+        
+        // Buffer for TTS generation (generate TTS for chunks of text)
+        const TTS_CHUNK_SIZE: usize = 50; // Generate TTS every ~50 characters
+        
+        // Track if LLM stream is complete
+        let mut llm_complete = false;
+        let mut pending_tts_tasks = 0u32;
+        
+        // Use select to handle both LLM tokens and TTS chunks
+        loop {
+            tokio::select! {
+                // Handle LLM tokens
+                token_result = token_stream_rx.recv(), if !llm_complete => {
+                    match token_result {
+                        Some(Ok(token)) => {
+                            full_text.push_str(&token);
+                            accumulated_text.push_str(&token);
+                            
+                            // Send token to client
+                            if let Err(e) = sender.send(Message::Text(
+                                serde_json::json!({
+                                    "type": "token",
+                                    "token": token.clone(),
+                                    "text": full_text.clone()
+                                }).to_string().into()
+                            )).await {
+                                warn!("Failed to send token: {}", e);
+                                break;
+                            }
+                            
+                            // Generate TTS chunk if we have enough text
+                            if accumulated_text.len() >= TTS_CHUNK_SIZE {
+                                let text_for_tts = accumulated_text.clone();
+                                accumulated_text.clear();
+                                
+                                // Generate TTS in background
+                                let tts_state_clone = tts_state.clone();
+                                let lang_clone = lang.clone();
+                                let voice_clone = voice.clone();
+                                let options_clone = tts_options.clone();
+                                let tts_tx_for_task = tts_tx_clone.clone();
+                                // Clean text for natural TTS speech with pauses and prosody
+                                let text_for_tts_cleaned = clean_text_for_tts(&text_for_tts, &lang);
+                                
+                                pending_tts_tasks += 1;
+                                tokio::spawn(async move {
+                                    let (samples, sample_rate) = match tokio::task::spawn_blocking(move || {
+                                        tts_state_clone.synthesize_with_options(&text_for_tts_cleaned, Some(&lang_clone), voice_clone.as_deref(), &options_clone)
+                                    }).await {
+                                        Ok(Ok(result)) => result,
+                                        Ok(Err(e)) => {
+                                            let _ = tts_tx_for_task.send(Err(format!("TTS error: {}", e))).await;
+                                            return;
+                                        }
+                                        Err(e) => {
+                                            let _ = tts_tx_for_task.send(Err(format!("Task error: {}", e))).await;
+                                            return;
+                                        }
+                                    };
+                                    
+                                    // Convert to base64 WAV
+                                    match tts_core::TtsManager::encode_wav_base64(&samples, sample_rate) {
+                                        Ok(audio_base64) => {
+                                            let _ = tts_tx_for_task.send(Ok((audio_base64, sample_rate))).await;
+                                        }
+                                        Err(e) => {
+                                            let _ = tts_tx_for_task.send(Err(format!("WAV encoding error: {}", e))).await;
+                                        }
+                                    }
+                                });
+                            }
+                        }
+                        Some(Err(e)) => {
+                            let err_msg = serde_json::json!({ "error": format!("Stream error: {e}"), "code": 500 });
+                            let _ = sender.send(Message::Text(err_msg.to_string().into())).await;
+                            break;
+                        }
+                        None => {
+                            // LLM stream complete - generate TTS for remaining text
+                            llm_complete = true;
+                            if !accumulated_text.is_empty() {
+                                let text_for_tts = accumulated_text.clone();
+                                accumulated_text.clear();
+                                // Clean text for natural TTS speech with pauses and prosody
+                                let text_for_tts_cleaned = clean_text_for_tts(&text_for_tts, &lang);
+                                pending_tts_tasks += 1;
+                                
+                                let tts_state_final = tts_state.clone();
+                                let lang_final = lang.clone();
+                                let voice_final = voice.clone();
+                                let options_final = tts_options.clone();
+                                let tts_tx_final = tts_tx_clone.clone();
+                                
+                                tokio::spawn(async move {
+                                    match tokio::task::spawn_blocking(move || {
+                                        tts_state_final.synthesize_with_options(&text_for_tts_cleaned, Some(&lang_final), voice_final.as_deref(), &options_final)
+                                    }).await {
+                                        Ok(Ok((samples, sample_rate))) => {
+                                            match tts_core::TtsManager::encode_wav_base64(&samples, sample_rate) {
+                                                Ok(audio_base64) => {
+                                                    let _ = tts_tx_final.send(Ok((audio_base64, sample_rate))).await;
+                                                }
+                                                Err(e) => {
+                                                    let _ = tts_tx_final.send(Err(format!("WAV encoding error: {}", e))).await;
+                                                }
+                                            }
+                                        }
+                                        Ok(Err(e)) => {
+                                            let _ = tts_tx_final.send(Err(format!("TTS error: {}", e))).await;
+                                        }
+                                        Err(e) => {
+                                            let _ = tts_tx_final.send(Err(format!("Task error: {}", e))).await;
+                                        }
+                                    }
+                                });
+                            }
+                            
+                            // If no pending TTS tasks, we can break
+                            if pending_tts_tasks == 0 {
+                                break;
+                            }
+                        }
+                    }
+                }
+                
+                // Handle TTS chunks
+                tts_result = tts_rx.recv() => {
+                    match tts_result {
+                        Some(Ok((audio_base64, sample_rate))) => {
+                            pending_tts_tasks = pending_tts_tasks.saturating_sub(1);
+                            
+                            if let Err(e) = sender.send(Message::Text(
+                                serde_json::json!({
+                                    "type": "audio_chunk",
+                                    "audio": audio_base64,
+                                    "sample_rate": sample_rate
+                                }).to_string().into()
+                            )).await {
+                                warn!("Failed to send audio chunk: {}", e);
+                                break;
+                            }
+                            
+                            // If LLM is complete and no more pending tasks, break
+                            if llm_complete && pending_tts_tasks == 0 {
+                                break;
+                            }
+                        }
+                        Some(Err(e)) => {
+                            pending_tts_tasks = pending_tts_tasks.saturating_sub(1);
+                            warn!("TTS error: {}", e);
+                            
+                            // If LLM is complete and no more pending tasks, break
+                            if llm_complete && pending_tts_tasks == 0 {
+                                break;
+                            }
+                        }
+                        None => {
+                            // TTS channel closed - if LLM is also complete, we're done
+                            if llm_complete {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Send completion status
+        let _ = sender.send(Message::Text(
+            serde_json::json!({ 
+                "type": "status", 
+                "status": "complete",
+                "text": full_text
+            }).to_string().into()
+        )).await;
+        
+        let _ = sender.close().await;
+    })
+}
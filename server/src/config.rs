@@ -9,6 +9,36 @@ pub struct ServerConfig {
     pub llm_timeout_secs: u64,
     pub request_timeout_secs: u64,
     pub cors_allowed_origins: Option<Vec<String>>,
+    // Bearer token of the admin routes (lexicon editing, voice reload); refused without one
+    pub admin_token: Option<String>,
+    // Loaded TTS models: memory budget and idle time before unloading (0 = never)
+    pub model_cache_mb: u64,
+    pub model_idle_secs: u64,
+    // Memory budget of the TTS response cache
+    pub response_cache_mb: u64,
+    // Persistent TTS response cache (disabled without a directory)
+    pub disk_cache_dir: Option<String>,
+    pub disk_cache_max_mb: u64,
+    pub disk_cache_ttl_secs: u64,
+    // Voice configuration, reloaded on change and via POST /voices/reload
+    pub map_file: String,
+    // How often to check models/map.json for changes (0 = no watch, reload via the admin endpoint)
+    pub map_watch_secs: u64,
+    // Build the voice catalog from the voice folders' metadata.json (map.json entries override)
+    pub discover_voices: bool,
+    // Refuse to start when a voice is broken (missing files, LFS pointers, checksum mismatch)
+    pub strict_voices: bool,
+    // Engine of voices whose map.json entry names none ("mock" runs without model files)
+    pub tts_engine: tts_core::EngineKind,
+    // Per-voice synthesizer pool: instances, concurrent syntheses, queued requests and queue timeout
+    pub pool_instances: usize,
+    pub pool_max_concurrent: usize,
+    pub pool_max_queue: usize,
+    pub pool_queue_timeout_ms: u64,
+    // Text chunks synthesized in parallel per request class: /tts, /voice-chat and the chat stream
+    pub tts_parallelism: usize,
+    pub voice_chat_parallelism: usize,
+    pub stream_parallelism: usize,
 }
 
 impl Default for ServerConfig {
@@ -19,6 +49,25 @@ impl Default for ServerConfig {
             llm_timeout_secs: 120,
             request_timeout_secs: 60,
             cors_allowed_origins: None,
+            admin_token: None,
+            model_cache_mb: 2048,
+            model_idle_secs: 1800,
+            response_cache_mb: 128,
+            disk_cache_dir: None,
+            disk_cache_max_mb: 1024,
+            disk_cache_ttl_secs: 30 * 24 * 3600,
+            map_file: "models/map.json".to_string(),
+            map_watch_secs: 5,
+            discover_voices: false,
+            strict_voices: false,
+            tts_engine: tts_core::EngineKind::Piper,
+            pool_instances: 1,
+            pool_max_concurrent: 2,
+            pool_max_queue: 32,
+            pool_queue_timeout_ms: 30_000,
+            tts_parallelism: 1,
+            voice_chat_parallelism: 1,
+            stream_parallelism: 1,
         }
     }
 }
@@ -54,12 +103,113 @@ impl ServerConfig {
                     .collect()
             });
         
+        let admin_token = std::env::var("ADMIN_TOKEN")
+            .ok()
+            .filter(|token| !token.trim().is_empty());
+        
+        let model_cache_mb = std::env::var("TTS_MODEL_CACHE_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2048);
+        
+        let model_idle_secs = std::env::var("TTS_MODEL_IDLE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1800);
+        
+        let response_cache_mb = std::env::var("TTS_RESPONSE_CACHE_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(128);
+        
+        let disk_cache_dir = std::env::var("TTS_DISK_CACHE_DIR")
+            .ok()
+            .filter(|dir| !dir.trim().is_empty());
+        
+        let disk_cache_max_mb = std::env::var("TTS_DISK_CACHE_MAX_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1024);
+        
+        let disk_cache_ttl_secs = std::env::var("TTS_DISK_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30 * 24 * 3600);
+        
+        let map_watch_secs = std::env::var("TTS_MAP_WATCH_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        
+        let discover_voices = std::env::var("TTS_DISCOVER_VOICES")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        
+        let strict_voices = std::env::var("TTS_STRICT_VOICES")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        
+        let tts_engine = std::env::var("TTS_ENGINE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default();
+        
+        let pool_instances = std::env::var("TTS_POOL_INSTANCES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1);
+        
+        let pool_max_concurrent = std::env::var("TTS_POOL_MAX_CONCURRENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
+        
+        let pool_max_queue = std::env::var("TTS_POOL_MAX_QUEUE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(32);
+        
+        let pool_queue_timeout_ms = std::env::var("TTS_POOL_QUEUE_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30_000);
+        
+        let parallelism = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .map(|v| v.max(1))
+                .unwrap_or(1)
+        };
+        let tts_parallelism = parallelism("TTS_PARALLEL_CHUNKS");
+        let voice_chat_parallelism = parallelism("TTS_VOICE_CHAT_PARALLEL_CHUNKS");
+        let stream_parallelism = parallelism("TTS_STREAM_PARALLEL_CHUNKS");
+        
         Self {
             port,
             rate_limit_per_minute,
             llm_timeout_secs,
             request_timeout_secs,
             cors_allowed_origins,
+            admin_token,
+            model_cache_mb,
+            model_idle_secs,
+            response_cache_mb,
+            disk_cache_dir,
+            disk_cache_max_mb,
+            disk_cache_ttl_secs,
+            map_file: "models/map.json".to_string(),
+            map_watch_secs,
+            discover_voices,
+            strict_voices,
+            tts_engine,
+            pool_instances,
+            pool_max_concurrent,
+            pool_max_queue,
+            pool_queue_timeout_ms,
+            tts_parallelism,
+            voice_chat_parallelism,
+            stream_parallelism,
         }
     }
     
@@ -70,5 +220,30 @@ impl ServerConfig {
     pub fn llm_timeout(&self) -> Duration {
         Duration::from_secs(self.llm_timeout_secs)
     }
+    
+    pub fn model_cache(&self) -> tts_core::ModelCacheConfig {
+        tts_core::ModelCacheConfig {
+            max_bytes: self.model_cache_mb * 1024 * 1024,
+            idle_ttl: (self.model_idle_secs > 0).then(|| Duration::from_secs(self.model_idle_secs)),
+            ..Default::default()
+        }
+    }
+    
+    pub fn pool(&self) -> tts_core::PoolConfig {
+        tts_core::PoolConfig {
+            instances: self.pool_instances.max(1),
+            max_concurrent: self.pool_max_concurrent.max(1),
+            max_queue: self.pool_max_queue,
+            queue_timeout: Duration::from_millis(self.pool_queue_timeout_ms),
+        }
+    }
+    
+    pub fn disk_cache(&self) -> Option<tts_core::DiskCacheConfig> {
+        self.disk_cache_dir.as_ref().map(|dir| tts_core::DiskCacheConfig {
+            dir: dir.into(),
+            max_bytes: self.disk_cache_max_mb * 1024 * 1024,
+            ttl: Duration::from_secs(self.disk_cache_ttl_secs),
+        })
+    }
 }
 
@@ -26,6 +26,13 @@ pub enum ApiError {
 
     #[error("Not found: {0}")]
     NotFound(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// Well-formed input that failed validation, with each problem found
+    #[error("Unprocessable: {0}")]
+    Unprocessable(String, Vec<String>),
 }
 
 /// Error response structure
@@ -33,12 +40,20 @@ pub enum ApiError {
 struct ErrorResponse {
     error: String,
     code: u16,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<String>,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let mut details = Vec::new();
         let (status, error_message) = match self {
             ApiError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
+            // A voice with a full synthesizer queue: the client may retry
+            ApiError::TtsError(e) if e.downcast_ref::<tts_core::VoiceBusy>().is_some() => {
+                tracing::warn!("TTS error: {}", e);
+                (StatusCode::SERVICE_UNAVAILABLE, format!("TTS error: {}", e))
+            }
             ApiError::TtsError(e) => {
                 tracing::error!("TTS error: {}", e);
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("TTS error: {}", e))
@@ -55,11 +70,17 @@ impl IntoResponse for ApiError {
                 (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded".to_string())
             }
             ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            ApiError::Unprocessable(msg, errors) => {
+                details = errors;
+                (StatusCode::UNPROCESSABLE_ENTITY, msg)
+            }
         };
 
         let body = Json(ErrorResponse {
             error: error_message.clone(),
             code: status.as_u16(),
+            details,
         });
 
         (status, body).into_response()
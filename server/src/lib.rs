@@ -1,10 +1,11 @@
-//! Server library for testing
+//! Server library: shared by the binary and the integration tests
 
+pub mod auth;
+pub mod chat_api;
+pub mod config;
 pub mod error;
 pub mod validation;
 pub mod metrics;
-
-// Note: Handlers are in main.rs since this is a binary crate
-// For testing, we'll need to make the handlers accessible differently
-// or move them to a shared module
-
+pub mod state;
+pub mod tts_api;
+pub mod voice_api;
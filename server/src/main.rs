@@ -1,57 +1,33 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use axum::{
-    extract::{Request, State, WebSocketUpgrade},
+    extract::{Request, State},
     middleware::Next,
-    response::{IntoResponse, Response},
+    response::Response,
     routing::{get, post},
     Json, Router,
 };
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use tokio::net::TcpListener;
-use tokio::sync::mpsc;
 use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, timeout::TimeoutLayer, trace::TraceLayer};
 use tower_governor::{governor::GovernorConfigBuilder, key_extractor::GlobalKeyExtractor, GovernorLayer};
-use tracing::{error, info, warn};
+use tracing::{info, warn};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use llm_core::{LlmClient, LlmProvider};
 
-mod error;
-mod validation;
-mod config;
-mod metrics;
+use server::metrics;
+use server::config::ServerConfig;
+use server::metrics::AppMetrics;
+use server::auth::require_admin;
+use server::chat_api::{chat_endpoint, chat_stream_ws, voice_chat_endpoint};
+use server::state::AppState;
+use server::tts_api::tts_endpoint;
+use server::voice_api::{add_lexicon_entry, delete_lexicon_entry, list_lexicons, phonemize_endpoint, reload_voices, voices_status};
 
-use crate::error::ApiError;
-use crate::validation::{validate_chat_request, validate_conversation_id, validate_tts_request};
-use crate::config::ServerConfig;
-use crate::metrics::AppMetrics;
-
-#[derive(Clone)]
-pub struct AppState {
-    pub tts: Arc<tts_core::TtsManager>,
-    pub llm: Arc<LlmClient>, // No mutex needed - client is async and thread-safe
-    pub request_count: Arc<AtomicU64>,
-    pub config: ServerConfig,
-    pub metrics: AppMetrics,
-    pub llm_provider: LlmProvider, // Store the provider type for API queries
-}
-
-#[derive(Deserialize)]
-pub struct TtsRequest {
-    text: String,
-    language: Option<String>,
-    speaker: Option<i64>,
-    voice: Option<String>, // voice ID (e.g., "norman", "thorsten")
-}
-
-#[derive(Serialize)]
-pub struct TtsResponse {
-    audio_base64: String,
-    duration_ms: u64,
-    sample_rate: u32,
-}
+/// Scanned for `<language>/<voice>/metadata.json` with `TTS_DISCOVER_VOICES`
+const MODELS_DIR: &str = "models";
 
 #[derive(Serialize)]
 pub struct VoiceInfo {
@@ -64,22 +40,10 @@ pub struct VoiceInfo {
     gender: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     quality: Option<String>,
-}
-
-#[derive(Deserialize)]
-pub struct ChatRequest {
-    message: String,
-    conversation_id: Option<String>,
-    language: Option<String>, // For TTS language selection
-}
-
-#[derive(Serialize)]
-pub struct ChatResponse {
-    reply: String,
-    conversation_id: String,
-    audio_base64: Option<String>, // Audio for bot response
-    sample_rate: Option<u32>,
-    duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    speakers: Vec<tts_core::SpeakerInfo>, // speakers of multi-speaker voices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<tts_core::VoiceMetadata>, // metadata.json of the voice folder
 }
 
 #[tokio::main]
@@ -119,16 +83,59 @@ async fn async_main() -> anyhow::Result<()> {
     // Start model keep-alive (if needed)
     llm.start_keep_alive();
 
+    // Load configuration from environment
+    let config = ServerConfig::from_env();
+
     info!("Loading TTS models...");
-    let tts = Arc::new(
-        tts_core::TtsManager::new_from_mapfile("models/map.json")
-            .unwrap_or_else(|e| {
-                warn!("Could not load models/map.json: {e}, using empty map.");
-                tts_core::TtsManager::new(std::collections::HashMap::new())
-            }),
-    );
+    let mut tts = tts_core::TtsManager::new_from_mapfile(&config.map_file)
+        .unwrap_or_else(|e| {
+            warn!("Could not load {}: {e}, using empty map.", config.map_file);
+            tts_core::TtsManager::new(std::collections::HashMap::new())
+        })
+        .with_response_cache_bytes(config.response_cache_mb as usize * 1024 * 1024)
+        .with_model_cache(config.model_cache())
+        .with_engine(config.tts_engine)
+        .with_pool(config.pool());
+    if config.tts_engine == tts_core::EngineKind::Mock {
+        info!("TTS engine: mock (deterministic tones, no model files needed)");
+    }
+    if config.discover_voices {
+        match tts.clone().with_voice_discovery(MODELS_DIR) {
+            Ok(with_discovery) => tts = with_discovery,
+            Err(e) => warn!("TTS voice discovery in {MODELS_DIR} failed: {e:#}"),
+        }
+    }
+    if let Some(disk_cache) = config.disk_cache() {
+        let dir = disk_cache.dir.display().to_string();
+        match tts.clone().with_disk_cache(disk_cache) {
+            Ok(with_disk_cache) => {
+                tts = with_disk_cache;
+                if let Some(usage) = tts.disk_cache_usage() {
+                    info!("TTS disk cache at {} ({} entries, {} MB)", dir, usage.entries, usage.bytes / (1024 * 1024));
+                }
+            }
+            Err(e) => warn!("TTS disk cache disabled: {e}"),
+        }
+    }
+    let tts = Arc::new(tts);
     info!("Loaded {} TTS voices", tts.list_languages().len());
     
+    // Check the voice files before anything loads them
+    let voice_status = tokio::task::spawn_blocking({
+        let tts = tts.clone();
+        move || tts.voice_status()
+    })
+    .await?;
+    let broken: Vec<&tts_core::VoiceStatus> = voice_status.iter().filter(|status| !status.is_ok()).collect();
+    for status in &broken {
+        let issues: Vec<String> = status.issues.iter().map(|issue| issue.to_string()).collect();
+        warn!("TTS voice {} is broken: {}", status.voice, issues.join("; "));
+    }
+    if config.strict_voices && !broken.is_empty() {
+        anyhow::bail!("{} of {} TTS voices are broken (TTS_STRICT_VOICES is set); see the warnings above", broken.len(), voice_status.len());
+    }
+    info!("Checked {} TTS voices: {} broken", voice_status.len(), broken.len());
+    
     // Preload frequently used models (en_US, de_DE)
     info!("Preloading frequently used TTS models...");
     if let Err(e) = tts.preload_models(&["en_US", "de_DE"]) {
@@ -137,12 +144,56 @@ async fn async_main() -> anyhow::Result<()> {
         info!("TTS models preloaded successfully");
     }
 
+    // Unload models that have been idle past the TTL
+    if let Some(idle_ttl) = config.model_cache().idle_ttl {
+        let tts = tts.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval((idle_ttl / 4).max(std::time::Duration::from_secs(10)));
+            loop {
+                interval.tick().await;
+                for config in tts.unload_idle_models() {
+                    info!("Unloaded idle TTS model {}", config);
+                }
+            }
+        });
+    }
+
+    // Reload map.json when it changes; a map that fails validation is rejected and the
+    // current one stays in place
+    if config.map_watch_secs > 0 {
+        let tts = tts.clone();
+        let map_file = config.map_file.clone();
+        let period = std::time::Duration::from_secs(config.map_watch_secs);
+        tokio::spawn(async move {
+            let modified = || std::fs::metadata(&map_file).and_then(|m| m.modified()).ok();
+            let mut last_modified = modified();
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let current = modified();
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+                match tts.reload_mapfile(&map_file).await {
+                    Ok(reload) => {
+                        info!(
+                            "Reloaded {}: {} voices added, {} removed, {} models unloaded",
+                            map_file, reload.added.len(), reload.removed.len(), reload.unloaded_models.len()
+                        );
+                        if !reload.broken.is_empty() {
+                            warn!("TTS voices still broken after the reload: {}", reload.broken.join(", "));
+                        }
+                    }
+                    Err(e) => warn!("Rejected changed {map_file}, keeping the current voices: {e:#}"),
+                }
+            }
+        });
+    }
+
     // Initialize start time for uptime calculation
-    let _ = START_TIME.get_or_init(|| std::time::Instant::now());
+    let _ = START_TIME.get_or_init(std::time::Instant::now);
 
-    // Load configuration from environment
-    let config = ServerConfig::from_env();
-    
     let state = AppState { 
         tts, 
         llm,
@@ -195,7 +246,7 @@ async fn async_main() -> anyhow::Result<()> {
     let governor_conf = Arc::new(
         GovernorConfigBuilder::default()
             .per_second((config.rate_limit_per_minute / 60) as u64) // Convert per-minute to per-second
-            .burst_size(config.rate_limit_per_minute as u32)
+            .burst_size(config.rate_limit_per_minute)
             .key_extractor(GlobalKeyExtractor)
             .finish()
             .unwrap(),
@@ -234,7 +285,9 @@ async fn async_main() -> anyhow::Result<()> {
         .route("/llm/provider", get(llm_provider_endpoint))
         .route("/voices", get(list_voices))
         .route("/voices/detail", get(list_voices_detail))
+        .route("/voices/status", get(voices_status))
         .route("/tts", post(tts_endpoint))
+        .route("/phonemize", post(phonemize_endpoint))
         .route("/chat", post(chat_endpoint))
         .route("/voice-chat", post(voice_chat_endpoint))
         .route("/ws/chat/stream", get(chat_stream_ws));
@@ -242,11 +295,32 @@ async fn async_main() -> anyhow::Result<()> {
     // Metrics endpoints - consider adding authentication in production
     let metrics_api = Router::new()
         .route("/metrics", get(metrics_endpoint))
-        .route("/metrics/detailed", get(detailed_metrics_endpoint));
-    
+        .route("/metrics/detailed", get(detailed_metrics_endpoint))
+        .route("/models", get(list_loaded_models));
+    
+    // Lexicon listing is public; editing is an admin route
+    let lexicon_api = Router::new()
+        .route("/lexicon", get(list_lexicons));
+
+    // Lexicon editing - requires the ADMIN_TOKEN bearer token
+    let lexicon_admin_api = Router::new()
+        .route("/lexicon", post(add_lexicon_entry).delete(delete_lexicon_entry))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), require_admin));
+
+    // Voice map reload - requires the ADMIN_TOKEN bearer token
+    let admin_api = Router::new()
+        .route("/voices/reload", post(reload_voices))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), require_admin));
+    if state.config.admin_token.is_none() {
+        warn!("ADMIN_TOKEN not set, lexicon editing and POST /voices/reload are disabled");
+    }
+
     let api = Router::new()
         .merge(public_api)
-        .merge(metrics_api);
+        .merge(metrics_api)
+        .merge(lexicon_api)
+        .merge(lexicon_admin_api)
+        .merge(admin_api);
 
     let app = Router::new()
         .merge(api.clone())   // root paths
@@ -437,6 +511,9 @@ pub async fn detailed_metrics_endpoint(State(state): State<AppState>) -> Json<cr
             cache_misses: state.metrics.tts_specific.cache_misses.load(Ordering::Relaxed),
             cache_hit_rate: state.metrics.tts_specific.cache_hit_rate(),
             total_samples: state.metrics.tts_specific.total_samples.load(Ordering::Relaxed),
+            response_cache: state.tts.response_cache_usage().await,
+            disk_cache: state.tts.disk_cache_usage(),
+            pools: state.tts.pool_stats(),
         },
         llm: LlmMetricsResponse {
             request_count: state.metrics.llm_specific.request_count.load(Ordering::Relaxed),
@@ -449,6 +526,28 @@ pub async fn detailed_metrics_endpoint(State(state): State<AppState>) -> Json<cr
     })
 }
 
+#[derive(Serialize)]
+pub struct LoadedModelsResponse {
+    models: Vec<tts_core::LoadedModel>,
+    total_bytes: u64,
+    max_bytes: u64,
+    max_models: usize,
+    idle_ttl_secs: Option<u64>,
+}
+
+/// Loaded TTS models with their approximate memory, most recently used first
+pub async fn list_loaded_models(State(state): State<AppState>) -> Json<LoadedModelsResponse> {
+    let models = state.tts.loaded_models();
+    let limits = state.tts.model_cache_config();
+    Json(LoadedModelsResponse {
+        total_bytes: models.iter().map(|model| model.memory_bytes).sum(),
+        models,
+        max_bytes: limits.max_bytes,
+        max_models: limits.max_models,
+        idle_ttl_secs: limits.idle_ttl.map(|ttl| ttl.as_secs()),
+    })
+}
+
 pub async fn list_voices(State(state): State<AppState>) -> Json<Vec<String>> {
     Json(state.tts.list_languages())
 }
@@ -467,6 +566,8 @@ pub async fn list_voices_detail(State(state): State<AppState>) -> Json<Vec<Voice
                 display_name: voice_entry.display_name.clone(),
                 gender: voice_entry.gender.clone(),
                 quality: voice_entry.quality.clone(),
+                speakers: tts_core::TtsManager::read_speakers(&voice_entry.config).unwrap_or_default(),
+                metadata: voice_entry.metadata.clone(),
             });
         }
     }
@@ -476,9 +577,11 @@ pub async fn list_voices_detail(State(state): State<AppState>) -> Json<Vec<Voice
         // Skip if already added from new format
         if !out.iter().any(|v| v.key.starts_with(&format!("{}:", k))) {
             out.push(VoiceInfo {
-                key: k.clone(),
-                config: cfg.clone(),
-                speaker: *spk,
+                speakers: tts_core::TtsManager::read_speakers(&cfg).unwrap_or_default(),
+                metadata: tts_core::VoiceMetadata::for_config(&cfg).ok().flatten(),
+                key: k,
+                config: cfg,
+                speaker: spk,
                 display_name: None,
                 gender: None,
                 quality: None,
@@ -488,740 +591,3 @@ pub async fn list_voices_detail(State(state): State<AppState>) -> Json<Vec<Voice
     
     Json(out)
 }
-
-pub async fn tts_endpoint(
-    State(state): State<AppState>,
-    Json(req): Json<TtsRequest>,
-) -> Result<Json<TtsResponse>, ApiError> {
-    state.request_count.fetch_add(1, Ordering::Relaxed);
-    let start_time = std::time::Instant::now();
-    validate_tts_request(&req.text, req.language.as_deref())?;
-
-    let tts = state.tts.clone();
-    // Clean text for natural TTS speech with pauses and prosody
-    let text = clean_text_for_tts(&req.text);
-    let language = req.language.clone();
-    let voice = req.voice.clone();
-    
-    // Use new async caching method
-    let tts_start = std::time::Instant::now();
-    let (audio_base64, sample_rate, duration_ms, cache_hit) = tts
-        .synthesize_with_cache(&text, language.as_deref(), voice.as_deref())
-        .await
-        .map_err(|e| {
-            state.metrics.tts.record_error();
-            ApiError::TtsError(e)
-        })?;
-
-    let tts_time_ms = tts_start.elapsed().as_millis() as u64;
-    let latency_ms = start_time.elapsed().as_millis() as u64;
-    
-    // Record metrics with cache hit tracking
-    state.metrics.tts.record_request(latency_ms);
-    state.metrics.tts_specific.record_synthesis(tts_time_ms, 0, cache_hit); // samples not needed for cached responses
-    
-    info!("TTS request completed in {}ms (synthesis: {}ms), duration: {}ms, cache_hit: {}", 
-          latency_ms, tts_time_ms, duration_ms, cache_hit);
-
-    Ok(Json(TtsResponse {
-        audio_base64,
-        duration_ms,
-        sample_rate,
-    }))
-}
-
-pub async fn chat_endpoint(
-    State(state): State<AppState>,
-    Json(req): Json<ChatRequest>,
-) -> Result<Json<ChatResponse>, ApiError> {
-    state.request_count.fetch_add(1, Ordering::Relaxed);
-    let start_time = std::time::Instant::now();
-    
-    validate_chat_request(&req.message)?;
-    if let Some(ref id) = req.conversation_id {
-        validate_conversation_id(id)?;
-    }
-
-    let message = req.message.clone();
-    let conv_id = req.conversation_id.clone();
-    let llm = state.llm.clone();
-    let language = req.language.clone();
-
-    info!("Chat request received: message length={}, conv_id={:?}", message.len(), conv_id);
-
-    // Run LLM async with timeout (no blocking needed - fully async now)
-    let conv_id = conv_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-    let result = tokio::time::timeout(
-        state.config.llm_timeout(),
-        llm.chat_with_history(Some(conv_id.clone()), &message)
-    )
-    .await;
-
-    let reply = match result {
-        Ok(Ok(reply)) => reply,
-        Ok(Err(e)) => {
-            state.metrics.chat.record_error();
-            state.metrics.llm_specific.record_error();
-            error!("LLM error: {}", e);
-            return Err(ApiError::LlmError(format!("LLM error: {e}")));
-        }
-        Err(_) => {
-            let timeout_secs = state.config.llm_timeout().as_secs();
-            state.metrics.chat.record_error();
-            state.metrics.llm_specific.record_timeout();
-            error!("LLM request timed out after {} seconds", timeout_secs);
-            return Err(ApiError::LlmError(format!(
-                "Request timed out after {} seconds. Please try again with a shorter message.",
-                timeout_secs
-            )));
-        }
-    };
-
-    let total_latency_ms = start_time.elapsed().as_millis() as u64;
-    
-    // Record metrics
-    state.metrics.chat.record_request(total_latency_ms);
-    state.metrics.llm_specific.record_request(total_latency_ms, reply.len());
-    
-    info!("LLM response received in {:.2}s, reply length={}", total_latency_ms as f64 / 1000.0, reply.len());
-
-    // Return text immediately - TTS generation moved to background for speed
-    // This ensures response time is only limited by LLM, not TTS
-    let response = ChatResponse {
-        reply: reply.clone(),
-        conversation_id: conv_id.clone(),
-        audio_base64: None,
-        sample_rate: None,
-        duration_ms: None,
-    };
-
-    // Generate TTS in background (completely non-blocking)
-    if let Some(lang) = language {
-        let tts_state = state.tts.clone();
-        // Clean text for natural TTS speech with pauses and prosody
-        let reply_for_tts = clean_text_for_tts(&reply);
-        tokio::spawn(async move {
-            let _ = tokio::task::spawn_blocking(move || {
-                if let Ok((samples, sr)) = tts_state.synthesize_with_sample_rate(&reply_for_tts, Some(&lang), None, None) {
-                    let _ = tts_core::TtsManager::encode_wav_base64(&samples, sr);
-                    // Audio generated in background - frontend can request via /tts if needed
-                }
-            }).await;
-        });
-    }
-
-    Ok(Json(response))
-}
-
-#[derive(Deserialize)]
-pub struct VoiceChatRequest {
-    message: String,
-    conversation_id: Option<String>,
-    language: Option<String>,
-    voice: Option<String>, // voice ID (e.g., "norman", "thorsten")
-}
-
-#[derive(Serialize)]
-pub struct VoiceChatResponse {
-    audio_base64: String,
-    sample_rate: u32,
-    duration_ms: u64,
-    conversation_id: String,
-    reply: String, // Original reply for display
-    cleaned_text: String, // Cleaned text that was actually spoken
-}
-
-pub async fn voice_chat_endpoint(
-    State(state): State<AppState>,
-    Json(req): Json<VoiceChatRequest>,
-) -> Result<Json<VoiceChatResponse>, ApiError> {
-    state.request_count.fetch_add(1, Ordering::Relaxed);
-    let start_time = std::time::Instant::now();
-    validate_chat_request(&req.message)?;
-    if let Some(ref id) = req.conversation_id {
-        validate_conversation_id(id)?;
-    }
-
-    let message = req.message.clone();
-    let conv_id = req.conversation_id.clone();
-    let llm = state.llm.clone();
-    // Default to en_US if available, otherwise de_DE
-    let default_lang = if state.tts.list_languages().contains(&"en_US".to_string()) {
-        "en_US"
-    } else {
-        "de_DE"
-    };
-    let language = req.language.clone().unwrap_or_else(|| default_lang.to_string());
-
-    // Get LLM response with timeout (fully async now)
-    let conv_id = conv_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-    let result = tokio::time::timeout(
-        state.config.llm_timeout(),
-        llm.chat_with_history(Some(conv_id.clone()), &message)
-    )
-    .await;
-
-    let reply = match result {
-        Ok(Ok(reply)) => reply,
-        Ok(Err(e)) => {
-            state.metrics.voice_chat.record_error();
-            state.metrics.llm_specific.record_error();
-            error!("LLM error: {}", e);
-            return Err(ApiError::LlmError(format!("LLM error: {e}")));
-        }
-        Err(_) => {
-            let timeout_secs = state.config.llm_timeout().as_secs();
-            state.metrics.voice_chat.record_error();
-            state.metrics.llm_specific.record_timeout();
-            error!("LLM request timed out after {} seconds", timeout_secs);
-            return Err(ApiError::LlmError(format!(
-                "Request timed out after {} seconds. Please try again with a shorter message.",
-                timeout_secs
-            )));
-        }
-    };
-
-    // Clean text for natural TTS speech
-    let cleaned_reply = clean_text_for_tts(&reply);
-    
-    // Generate TTS audio (required for voice chat) - use caching
-    let voice_id = req.voice.as_deref();
-    let tts = state.tts.clone();
-    
-    let tts_start = std::time::Instant::now();
-    let (audio_base64, sample_rate, duration_ms, cache_hit) = tts
-        .synthesize_with_cache(
-            &cleaned_reply,
-            Some(&language),
-            voice_id
-        )
-        .await
-        .map_err(|e| {
-            state.metrics.voice_chat.record_error();
-            ApiError::TtsError(e)
-        })?;
-
-    let tts_time_ms = tts_start.elapsed().as_millis() as u64;
-    let total_latency_ms = start_time.elapsed().as_millis() as u64;
-    
-    // Record metrics with cache hit tracking
-    state.metrics.voice_chat.record_request(total_latency_ms);
-    state.metrics.llm_specific.record_request(total_latency_ms, reply.len());
-    state.metrics.tts_specific.record_synthesis(tts_time_ms, 0, cache_hit); // samples not needed for cached responses
-
-    Ok(Json(VoiceChatResponse {
-        audio_base64,
-        sample_rate,
-        duration_ms,
-        conversation_id: conv_id,
-        reply: reply.clone(),
-        cleaned_text: cleaned_reply,
-    }))
-}
-
-/// WebSocket endpoint for streaming chat (LLM + TTS)
-/// Accepts query parameters: message, conversation_id (optional), language (optional)
-pub async fn chat_stream_ws(
-    ws: WebSocketUpgrade,
-    State(state): State<AppState>,
-    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
-) -> impl IntoResponse {
-    let message = params.get("message").cloned().unwrap_or_default();
-    let conversation_id = params.get("conversation_id").cloned();
-    let language = params.get("language").cloned();
-    
-    if message.is_empty() {
-        return ws.on_upgrade(move |mut socket| async move {
-            use axum::extract::ws::Message;
-            let error_msg = serde_json::json!({ "error": "Message parameter is required", "code": 400 });
-            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
-        });
-    }
-    
-    if let Err(e) = validate_chat_request(&message) {
-        return ws.on_upgrade(move |mut socket| async move {
-            use axum::extract::ws::Message;
-            let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
-            let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
-        });
-    }
-    
-    if let Some(ref id) = conversation_id {
-        if let Err(e) = validate_conversation_id(id) {
-            return ws.on_upgrade(move |mut socket| async move {
-                use axum::extract::ws::Message;
-                let error_msg = serde_json::json!({ "error": format!("{e}"), "code": 400 });
-                let _ = socket.send(Message::Text(error_msg.to_string().into())).await;
-            });
-        }
-    }
-
-    ws.on_upgrade(move |socket| async move {
-        use axum::extract::ws::Message;
-        use futures_util::{SinkExt as _, StreamExt as _};
-        
-        // Split socket into sender and receiver
-        let (mut sender, _receiver) = socket.split();
-        
-        // Send initial status
-        let _ = sender.send(Message::Text(
-            serde_json::json!({ 
-                "type": "status", 
-                "status": "streaming", 
-                "message": "Starting LLM stream..." 
-            }).to_string().into()
-        )).await;
-        
-        // Get LLM client and create stream
-        let llm = state.llm.clone();
-        let message_clone = message.clone();
-        let conv_id_clone = conversation_id.clone();
-        
-        // Create channel for LLM tokens
-        let (token_stream_tx, mut token_stream_rx) = mpsc::channel::<Result<String, String>>(100);
-        let token_stream_tx_clone = token_stream_tx.clone();
-        
-        // Spawn task to handle LLM streaming (fully async, no lock needed)
-        tokio::spawn(async move {
-            // Create stream directly (no mutex needed - client is async and thread-safe)
-            let mut stream = llm.chat_with_history_stream(conv_id_clone, &message_clone);
-            
-            // Consume stream and forward tokens
-            use futures_util::StreamExt as _;
-            while let Some(result) = stream.next().await {
-                if token_stream_tx_clone.send(result.map_err(|e| e.to_string())).await.is_err() {
-                    break; // Receiver dropped
-                }
-            }
-        });
-        
-        // Create channel for TTS audio chunks
-        let (tts_tx, mut tts_rx) = mpsc::channel::<Result<(String, u32), String>>(10);
-        let tts_tx_clone = tts_tx.clone();
-        
-        // Stream tokens and optionally generate TTS
-        let mut full_text = String::new();
-        let mut accumulated_text = String::new();
-        let tts_state = state.tts.clone();
-        let lang = language.clone().unwrap_or_else(|| "en_US".to_string());
-        
-        // Buffer for TTS generation (generate TTS for chunks of text)
-        const TTS_CHUNK_SIZE: usize = 50; // Generate TTS every ~50 characters
-        
-        // Track if LLM stream is complete
-        let mut llm_complete = false;
-        let mut pending_tts_tasks = 0u32;
-        
-        // Use select to handle both LLM tokens and TTS chunks
-        loop {
-            tokio::select! {
-                // Handle LLM tokens
-                token_result = token_stream_rx.recv(), if !llm_complete => {
-                    match token_result {
-                        Some(Ok(token)) => {
-                            full_text.push_str(&token);
-                            accumulated_text.push_str(&token);
-                            
-                            // Send token to client
-                            if let Err(e) = sender.send(Message::Text(
-                                serde_json::json!({
-                                    "type": "token",
-                                    "token": token.clone(),
-                                    "text": full_text.clone()
-                                }).to_string().into()
-                            )).await {
-                                warn!("Failed to send token: {}", e);
-                                break;
-                            }
-                            
-                            // Generate TTS chunk if we have enough text
-                            if accumulated_text.len() >= TTS_CHUNK_SIZE {
-                                let text_for_tts = accumulated_text.clone();
-                                accumulated_text.clear();
-                                
-                                // Generate TTS in background
-                                let tts_state_clone = tts_state.clone();
-                                let lang_clone = lang.clone();
-                                let tts_tx_for_task = tts_tx_clone.clone();
-                                // Clean text for natural TTS speech with pauses and prosody
-                                let text_for_tts_cleaned = clean_text_for_tts(&text_for_tts);
-                                
-                                pending_tts_tasks += 1;
-                                tokio::spawn(async move {
-                                    let (samples, sample_rate) = match tokio::task::spawn_blocking(move || {
-                                        tts_state_clone.synthesize_with_sample_rate(&text_for_tts_cleaned, Some(&lang_clone), None, None)
-                                    }).await {
-                                        Ok(Ok(result)) => result,
-                                        Ok(Err(e)) => {
-                                            let _ = tts_tx_for_task.send(Err(format!("TTS error: {}", e))).await;
-                                            return;
-                                        }
-                                        Err(e) => {
-                                            let _ = tts_tx_for_task.send(Err(format!("Task error: {}", e))).await;
-                                            return;
-                                        }
-                                    };
-                                    
-                                    // Convert to base64 WAV
-                                    match tts_core::TtsManager::encode_wav_base64(&samples, sample_rate) {
-                                        Ok(audio_base64) => {
-                                            let _ = tts_tx_for_task.send(Ok((audio_base64, sample_rate))).await;
-                                        }
-                                        Err(e) => {
-                                            let _ = tts_tx_for_task.send(Err(format!("WAV encoding error: {}", e))).await;
-                                        }
-                                    }
-                                });
-                            }
-                        }
-                        Some(Err(e)) => {
-                            let err_msg = serde_json::json!({ "error": format!("Stream error: {e}"), "code": 500 });
-                            let _ = sender.send(Message::Text(err_msg.to_string().into())).await;
-                            break;
-                        }
-                        None => {
-                            // LLM stream complete - generate TTS for remaining text
-                            llm_complete = true;
-                            if !accumulated_text.is_empty() {
-                                let text_for_tts = accumulated_text.clone();
-                                accumulated_text.clear();
-                                // Clean text for natural TTS speech with pauses and prosody
-                                let text_for_tts_cleaned = clean_text_for_tts(&text_for_tts);
-                                pending_tts_tasks += 1;
-                                
-                                let tts_state_final = tts_state.clone();
-                                let lang_final = lang.clone();
-                                let tts_tx_final = tts_tx_clone.clone();
-                                
-                                tokio::spawn(async move {
-                                    match tokio::task::spawn_blocking(move || {
-                                        tts_state_final.synthesize_with_sample_rate(&text_for_tts_cleaned, Some(&lang_final), None, None)
-                                    }).await {
-                                        Ok(Ok((samples, sample_rate))) => {
-                                            match tts_core::TtsManager::encode_wav_base64(&samples, sample_rate) {
-                                                Ok(audio_base64) => {
-                                                    let _ = tts_tx_final.send(Ok((audio_base64, sample_rate))).await;
-                                                }
-                                                Err(e) => {
-                                                    let _ = tts_tx_final.send(Err(format!("WAV encoding error: {}", e))).await;
-                                                }
-                                            }
-                                        }
-                                        Ok(Err(e)) => {
-                                            let _ = tts_tx_final.send(Err(format!("TTS error: {}", e))).await;
-                                        }
-                                        Err(e) => {
-                                            let _ = tts_tx_final.send(Err(format!("Task error: {}", e))).await;
-                                        }
-                                    }
-                                });
-                            }
-                            
-                            // If no pending TTS tasks, we can break
-                            if pending_tts_tasks == 0 {
-                                break;
-                            }
-                        }
-                    }
-                }
-                
-                // Handle TTS chunks
-                tts_result = tts_rx.recv() => {
-                    match tts_result {
-                        Some(Ok((audio_base64, sample_rate))) => {
-                            if pending_tts_tasks > 0 {
-                                pending_tts_tasks -= 1;
-                            }
-                            
-                            if let Err(e) = sender.send(Message::Text(
-                                serde_json::json!({
-                                    "type": "audio_chunk",
-                                    "audio": audio_base64,
-                                    "sample_rate": sample_rate
-                                }).to_string().into()
-                            )).await {
-                                warn!("Failed to send audio chunk: {}", e);
-                                break;
-                            }
-                            
-                            // If LLM is complete and no more pending tasks, break
-                            if llm_complete && pending_tts_tasks == 0 {
-                                break;
-                            }
-                        }
-                        Some(Err(e)) => {
-                            if pending_tts_tasks > 0 {
-                                pending_tts_tasks -= 1;
-                            }
-                            warn!("TTS error: {}", e);
-                            
-                            // If LLM is complete and no more pending tasks, break
-                            if llm_complete && pending_tts_tasks == 0 {
-                                break;
-                            }
-                        }
-                        None => {
-                            // TTS channel closed - if LLM is also complete, we're done
-                            if llm_complete {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Send completion status
-        let _ = sender.send(Message::Text(
-            serde_json::json!({ 
-                "type": "status", 
-                "status": "complete",
-                "text": full_text
-            }).to_string().into()
-        )).await;
-        
-        let _ = sender.close().await;
-    })
-}
-
-/// Detect emotional tone from text based on punctuation and keywords
-/// Returns a prosody hint (rate, pitch) for more expressive speech
-fn detect_emotion(text: &str) -> (f32, f32) {
-    let text_lower = text.to_lowercase();
-    
-    // Excitement indicators (exclamation marks, exciting words)
-    let has_excitement = text.contains('!') || 
-        text_lower.contains("amazing") || text_lower.contains("wonderful") || 
-        text_lower.contains("fantastic") || text_lower.contains("incredible") ||
-        text_lower.contains("excellent") || text_lower.contains("great");
-    
-    // Question indicators (questions typically have rising intonation)
-    let is_question = text.trim_end().ends_with('?');
-    
-    // Sadness/concern indicators
-    let has_concern = text_lower.contains("sorry") || text_lower.contains("unfortunately") ||
-        text_lower.contains("problem") || text_lower.contains("issue") ||
-        text_lower.contains("difficult") || text_lower.contains("challenge");
-    
-    // Adjust prosody based on emotion
-    if has_excitement {
-        // Slightly faster, higher pitch for excitement
-        (1.05, 1.1)
-    } else if is_question {
-        // Normal speed, slightly higher pitch for questions (rising intonation)
-        (1.0, 1.05)
-    } else if has_concern {
-        // Slightly slower, lower pitch for concern
-        (0.95, 0.95)
-    } else {
-        // Neutral prosody
-        (1.0, 1.0)
-    }
-}
-
-/// Clean text for natural TTS speech
-/// Removes markdown, special formatting, and converts text to be more natural for speech
-/// Enhanced with pause markers for commas and sentence endings for all languages
-fn clean_text_for_tts(text: &str) -> String {
-    let mut cleaned = text.to_string();
-    
-    // Remove markdown code blocks (multiline)
-    while let Some(start) = cleaned.find("```") {
-        if let Some(end) = cleaned[start + 3..].find("```") {
-            cleaned.replace_range(start..start + end + 6, "");
-        } else {
-            break;
-        }
-    }
-    
-    // Remove inline code blocks
-    while let Some(start) = cleaned.find('`') {
-        if let Some(end) = cleaned[start + 1..].find('`') {
-            let code_content = cleaned[start + 1..start + 1 + end].to_string();
-            cleaned.replace_range(start..start + end + 2, &code_content);
-        } else {
-            break;
-        }
-    }
-    
-    // Remove markdown links but keep the text [text](url) -> text
-    let mut pos = 0;
-    while let Some(start) = cleaned[pos..].find('[') {
-        let start = pos + start;
-        if let Some(mid) = cleaned[start + 1..].find(']') {
-            let mid = start + 1 + mid;
-            if let Some(end) = cleaned[mid + 1..].find(')') {
-                let end = mid + 1 + end;
-                let link_text = cleaned[start + 1..mid].to_string();
-                let link_len = link_text.len();
-                cleaned.replace_range(start..end + 1, &link_text);
-                pos = start + link_len;
-            } else {
-                break;
-            }
-        } else {
-            break;
-        }
-    }
-    
-    // Remove markdown bold/italic but keep the text
-    cleaned = cleaned.replace("**", "");
-    cleaned = cleaned.replace("*", "");
-    cleaned = cleaned.replace("__", "");
-    cleaned = cleaned.replace("_", "");
-    cleaned = cleaned.replace("~~", "");
-    cleaned = cleaned.replace("#", "");
-    
-    // Remove markdown headers (lines starting with #)
-    let lines: Vec<&str> = cleaned.lines().collect();
-    cleaned = lines
-        .iter()
-        .map(|line| {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with('#') {
-                trimmed.trim_start_matches('#').trim_start()
-            } else {
-                line
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    
-    // Remove markdown list markers
-    let lines: Vec<&str> = cleaned.lines().collect();
-    cleaned = lines
-        .iter()
-        .map(|line| {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
-                &trimmed[2..]
-            } else if let Some(num_end) = trimmed.find(". ") {
-                if trimmed[..num_end].chars().all(|c| c.is_ascii_digit()) {
-                    &trimmed[num_end + 2..]
-                } else {
-                    line
-                }
-            } else {
-                line
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    
-    // Remove "asterisk" word if it appears (TTS might read * as "asterisk")
-    cleaned = cleaned.replace(" asterisk ", " ");
-    cleaned = cleaned.replace(" asterisks ", " ");
-    cleaned = cleaned.replace("Asterisk ", "");
-    cleaned = cleaned.replace("Asterisks ", "");
-    
-    // Normalize whitespace - replace multiple spaces/newlines with single space
-    let mut result = String::with_capacity(cleaned.len());
-    let mut last_was_whitespace = false;
-    for ch in cleaned.chars() {
-        if ch.is_whitespace() {
-            if !last_was_whitespace {
-                result.push(' ');
-                last_was_whitespace = true;
-            }
-        } else {
-            result.push(ch);
-            last_was_whitespace = false;
-        }
-    }
-    cleaned = result;
-    
-    // Fix spacing around punctuation - remove space before punctuation
-    cleaned = cleaned.replace(" ,", ",");
-    cleaned = cleaned.replace(" .", ".");
-    cleaned = cleaned.replace(" !", "!");
-    cleaned = cleaned.replace(" ?", "?");
-    cleaned = cleaned.replace(" ;", ";");
-    cleaned = cleaned.replace(" :", ":");
-    
-    // Enhanced: Add natural pauses for commas and sentence endings
-    // This helps TTS systems naturally pause at appropriate points for all languages
-    let mut result = String::with_capacity(cleaned.len() * 2);
-    let chars: Vec<char> = cleaned.chars().collect();
-    for i in 0..chars.len() {
-        result.push(chars[i]);
-        
-        // Add pause markers after punctuation
-        if i + 1 < chars.len() {
-            let next_char = chars[i + 1];
-            
-            match chars[i] {
-                // Commas: short pause (add extra space for natural pause)
-                ',' if !next_char.is_whitespace() && !matches!(next_char, ',' | '.' | '!' | '?' | ';' | ':' | ')') => {
-                    result.push_str("  "); // Double space for short pause hint
-                }
-                // Semicolons: medium pause
-                ';' if !next_char.is_whitespace() && !matches!(next_char, ',' | '.' | '!' | '?' | ';' | ':' | ')') => {
-                    result.push_str("   "); // Triple space for medium pause
-                }
-                // Colons: medium pause
-                ':' if !next_char.is_whitespace() && !matches!(next_char, ',' | '.' | '!' | '?' | ';' | ':' | ')') => {
-                    result.push_str("   "); // Triple space for medium pause
-                }
-                // Sentence endings: longer pause (period, exclamation, question)
-                '.' | '!' | '?' if !next_char.is_whitespace() && !matches!(next_char, ',' | '.' | '!' | '?' | ';' | ':' | ')') => {
-                    // Check if this is an abbreviation (e.g., "Dr.", "Mr.", "etc.")
-                    let is_abbrev = if i >= 2 {
-                        let prev_chars = &chars[i.saturating_sub(3)..=i];
-                        let prev_str: String = prev_chars.iter().collect();
-                        prev_str.ends_with("Dr.") || prev_str.ends_with("Mr.") || 
-                        prev_str.ends_with("Mrs.") || prev_str.ends_with("Ms.") ||
-                        prev_str.ends_with("Prof.") || prev_str.ends_with("etc.") ||
-                        prev_str.ends_with("vs.") || prev_str.ends_with("e.g.") ||
-                        prev_str.ends_with("i.e.") || prev_str.ends_with("a.m.") ||
-                        prev_str.ends_with("p.m.")
-                    } else {
-                        false
-                    };
-                    
-                    if !is_abbrev {
-                        result.push_str("    "); // Quadruple space for longer sentence-ending pause
-                    } else {
-                        result.push(' '); // Just single space for abbreviations
-                    }
-                }
-                _ => {
-                    // Ensure space after punctuation if needed
-                    if matches!(chars[i], ',' | '.' | '!' | '?' | ';' | ':') && 
-                       !next_char.is_whitespace() && 
-                       !matches!(next_char, ',' | '.' | '!' | '?' | ';' | ':' | ')') {
-                        result.push(' ');
-                    }
-                }
-            }
-        }
-    }
-    cleaned = result;
-    
-    // Clean up excessive spaces (more than 4 consecutive spaces) but keep pause hints
-    // This normalizes while preserving intentional pauses
-    let mut result = String::with_capacity(cleaned.len());
-    let mut space_count = 0;
-    for ch in cleaned.chars() {
-        if ch == ' ' {
-            space_count += 1;
-            // Keep up to 4 spaces (for sentence endings), normalize beyond that
-            if space_count <= 4 {
-                result.push(ch);
-            }
-        } else {
-            space_count = 0;
-            result.push(ch);
-        }
-    }
-    cleaned = result;
-    
-    // Remove leading/trailing whitespace
-    cleaned = cleaned.trim().to_string();
-    
-    // If empty after cleaning, return original (fallback)
-    if cleaned.is_empty() {
-        text.to_string()
-    } else {
-        cleaned
-    }
-}
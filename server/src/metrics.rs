@@ -309,6 +309,10 @@ pub struct TtsMetricsResponse {
     pub cache_misses: u64,
     pub cache_hit_rate: f64,
     pub total_samples: u64,
+    pub response_cache: tts_core::CacheUsage, // memory held by cached audio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_cache: Option<tts_core::CacheUsage>,
+    pub pools: Vec<tts_core::PoolStats>, // synthesizer pools of the loaded voices
 }
 
 #[derive(Serialize)]
@@ -0,0 +1,17 @@
+// Shared state of the HTTP handlers
+
+use std::sync::{atomic::AtomicU64, Arc};
+
+use llm_core::{LlmClient, LlmProvider};
+
+use crate::{config::ServerConfig, metrics::AppMetrics};
+
+#[derive(Clone)]
+pub struct AppState {
+    pub tts: Arc<tts_core::TtsManager>,
+    pub llm: Arc<LlmClient>, // No mutex needed - client is async and thread-safe
+    pub request_count: Arc<AtomicU64>,
+    pub config: ServerConfig,
+    pub metrics: AppMetrics,
+    pub llm_provider: LlmProvider, // Store the provider type for API queries
+}
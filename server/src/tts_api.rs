@@ -0,0 +1,381 @@
+// The /tts endpoint, its request types and the text cleaning shared with the chat handlers
+
+use std::sync::atomic::Ordering;
+
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::validation::{parse_audio_format, parse_subtitle_options, validate_loudness, validate_phoneme_request, validate_prosody, validate_sample_rate, validate_silence, validate_speaker_id, validate_tts_request};
+
+#[derive(Deserialize)]
+pub struct TtsRequest {
+    #[serde(default)]
+    text: String,
+    phonemes: Option<String>, // eSpeak IPA phonemes, one sentence per line (instead of text)
+    language: Option<String>,
+    speaker: Option<i64>,
+    voice: Option<String>, // voice ID (e.g., "norman", "thorsten")
+    #[serde(flatten)]
+    prosody: ProsodyParams,
+    #[serde(default)]
+    ssml: bool, // treat text as SSML (<speak>...</speak>)
+    format: Option<String>, // output format (wav, wav24, wav_f32, pcm, flac, mulaw, alaw; opus and mp3 with the cargo features)
+    sample_rate: Option<u32>, // resample output (Hz); defaults to the voice's native rate
+    #[serde(flatten)]
+    loudness: tts_core::LoudnessTarget, // target_lufs / true_peak_db (defaults from map.json)
+    #[serde(default)]
+    alignment: bool, // return word timings (plain text input only)
+    #[serde(flatten)]
+    subtitles: SubtitleParams,
+    #[serde(flatten)]
+    silence: tts_core::SilenceOptions, // trim_silence / max_silence_ms
+}
+
+/// Optional SRT/WebVTT export for /tts (plain text input only)
+#[derive(Deserialize, Default)]
+pub struct SubtitleParams {
+    subtitles: Option<String>, // srt or vtt
+    subtitle_line_length: Option<usize>, // characters per line (default 42)
+    subtitle_max_lines: Option<usize>, // lines per cue (default 2)
+    subtitle_max_duration_ms: Option<u64>, // longest time a cue stays on screen (default 7000)
+}
+
+impl SubtitleParams {
+    fn parse(&self) -> Result<Option<(tts_core::SubtitleFormat, tts_core::SubtitleOptions)>, ApiError> {
+        parse_subtitle_options(
+            self.subtitles.as_deref(),
+            self.subtitle_line_length,
+            self.subtitle_max_lines,
+            self.subtitle_max_duration_ms,
+        )
+    }
+}
+
+/// Optional prosody controls shared by /tts and /voice-chat
+#[derive(Deserialize, Default)]
+pub struct ProsodyParams {
+    rate: Option<f32>, // speaking rate multiplier (alternative to length_scale)
+    length_scale: Option<f32>,
+    noise_scale: Option<f32>,
+    noise_w: Option<f32>,
+}
+
+impl ProsodyParams {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        validate_prosody(self.rate, self.length_scale, self.noise_scale, self.noise_w)
+    }
+
+    pub fn to_prosody(&self) -> tts_core::Prosody {
+        let base = self.rate.map(tts_core::Prosody::from_rate).unwrap_or_default();
+        tts_core::Prosody {
+            length_scale: self.length_scale.or(base.length_scale),
+            noise_scale: self.noise_scale,
+            noise_w: self.noise_w,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TtsResponse {
+    audio_base64: String,
+    duration_ms: u64,
+    sample_rate: u32,
+    mime_type: &'static str,
+    loudness: tts_core::LoudnessReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    words: Option<Vec<tts_core::WordTiming>>, // char offsets into `text`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtitles: Option<String>, // SRT or WebVTT file contents
+}
+
+/// Synthesize speech. Returns JSON with base64 audio, or the audio itself as the response body
+/// when the `Accept` header asks for audio (metadata then goes into `X-Audio-*` headers).
+pub async fn tts_endpoint(
+    State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    Json(req): Json<TtsRequest>,
+) -> Result<Response, ApiError> {
+    state.request_count.fetch_add(1, Ordering::Relaxed);
+    let start_time = std::time::Instant::now();
+    match &req.phonemes {
+        Some(phonemes) => validate_phoneme_request(phonemes, &req.text, req.ssml, req.language.as_deref())?,
+        None => validate_tts_request(&req.text, req.language.as_deref())?,
+    }
+    validate_speaker_id(req.speaker)?;
+    req.prosody.validate()?;
+    let format = parse_audio_format(req.format.as_deref())?;
+    validate_sample_rate(req.sample_rate)?;
+    validate_loudness(&req.loudness)?;
+    validate_silence(&req.silence)?;
+    let subtitles = req.subtitles.parse()?;
+    if subtitles.is_some() && (req.ssml || req.phonemes.is_some()) {
+        return Err(ApiError::InvalidInput("Subtitles require plain text input".to_string()));
+    }
+    let binary = accepts_audio(&headers);
+    if binary && (req.alignment || subtitles.is_some()) {
+        return Err(ApiError::InvalidInput("Word timings and subtitles require a JSON response".to_string()));
+    }
+
+    let tts = state.tts.clone();
+    let language = req.language.clone();
+    let voice = req.voice.clone();
+    let options = tts_core::SynthesisOptions {
+        speaker: req.speaker,
+        prosody: req.prosody.to_prosody(),
+        format,
+        sample_rate: req.sample_rate,
+        loudness: req.loudness,
+        alignment: req.alignment || subtitles.is_some(),
+        silence: req.silence,
+        parallelism: state.config.tts_parallelism,
+        ..Default::default()
+    };
+    
+    // Use new async caching method
+    let tts_start = std::time::Instant::now();
+    let mut spoken_text: Option<String> = None;
+    let result = if let Some(phonemes) = &req.phonemes {
+        // Phonemes are synthesized as given: no cleaning, normalization or lexicon
+        tts.synthesize_phonemes_with_cache(phonemes, language.as_deref(), voice.as_deref(), &options).await
+    } else if req.ssml {
+        // SSML markup controls pauses itself, so the text is not cleaned or split
+        tts.synthesize_ssml_with_cache(&req.text, language.as_deref(), voice.as_deref(), &options).await
+    } else {
+        // Clean text for natural TTS speech with pauses and prosody
+        let text = clean_text_for_tts(&req.text, language.as_deref().unwrap_or("de_DE"));
+        let result = tts.synthesize_with_cache(&text, language.as_deref(), voice.as_deref(), &options).await;
+        spoken_text = Some(text);
+        result
+    };
+    let audio = result.map_err(|e| {
+        if let Some(ssml_error) = e.downcast_ref::<tts_core::SsmlError>() {
+            return ApiError::InvalidInput(ssml_error.to_string());
+        }
+        state.metrics.tts.record_error();
+        ApiError::TtsError(e)
+    })?;
+
+    let tts_time_ms = tts_start.elapsed().as_millis() as u64;
+    let latency_ms = start_time.elapsed().as_millis() as u64;
+    
+    // Record metrics with cache hit tracking
+    state.metrics.tts.record_request(latency_ms);
+    state.metrics.tts_specific.record_synthesis(tts_time_ms, 0, audio.cache_hit); // samples not needed for cached responses
+    
+    info!("TTS request completed in {}ms (synthesis: {}ms), duration: {}ms, cache_hit: {}", 
+          latency_ms, tts_time_ms, audio.duration_ms, audio.cache_hit);
+
+    if binary {
+        let mut response = audio.audio.into_response();
+        let headers = response.headers_mut();
+        headers.insert(axum::http::header::CONTENT_TYPE, axum::http::HeaderValue::from_static(audio.mime_type));
+        headers.insert("x-audio-sample-rate", audio.sample_rate.into());
+        headers.insert("x-audio-duration-ms", audio.duration_ms.into());
+        headers.insert("x-cache-hit", axum::http::HeaderValue::from_static(if audio.cache_hit { "true" } else { "false" }));
+        return Ok(response);
+    }
+
+    Ok(Json(TtsResponse {
+        audio_base64: audio.audio_base64(),
+        duration_ms: audio.duration_ms,
+        sample_rate: audio.sample_rate,
+        mime_type: audio.mime_type,
+        loudness: audio.loudness,
+        // Subtitles show the cleaned text; word timings point into the request text
+        subtitles: subtitles.zip(audio.alignment.as_ref()).zip(spoken_text.as_ref()).map(|(((format, options), alignment), spoken)| {
+            format.render(&tts_core::build_cues(spoken, alignment, &options))
+        }),
+        words: audio
+            .alignment
+            .filter(|_| req.alignment)
+            .zip(spoken_text)
+            .map(|(alignment, spoken)| tts_core::map_word_offsets(alignment.words, &spoken, &req.text)),
+    })
+    .into_response())
+}
+
+/// True if the `Accept` header prefers a raw audio body over JSON
+fn accepts_audio(headers: &axum::http::HeaderMap) -> bool {
+    headers
+        .get(axum::http::header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .and_then(|accept| accept.split(',').next())
+        .is_some_and(|first| {
+            let media_type = first.split(';').next().unwrap_or("").trim();
+            media_type.starts_with("audio/") || media_type == "application/octet-stream"
+        })
+}
+
+/// Clean text for natural TTS speech
+/// Removes markdown, special formatting, and converts text to be more natural for speech
+/// Enhanced with pause markers for commas and sentence endings for all languages
+pub fn clean_text_for_tts(text: &str, language: &str) -> String {
+    let mut cleaned = text.to_string();
+    
+    // Remove markdown code blocks (multiline)
+    while let Some(start) = cleaned.find("```") {
+        if let Some(end) = cleaned[start + 3..].find("```") {
+            cleaned.replace_range(start..start + end + 6, "");
+        } else {
+            break;
+        }
+    }
+    
+    // Remove inline code blocks
+    while let Some(start) = cleaned.find('`') {
+        if let Some(end) = cleaned[start + 1..].find('`') {
+            let code_content = cleaned[start + 1..start + 1 + end].to_string();
+            cleaned.replace_range(start..start + end + 2, &code_content);
+        } else {
+            break;
+        }
+    }
+    
+    // Remove markdown links but keep the text [text](url) -> text
+    let mut pos = 0;
+    while let Some(start) = cleaned[pos..].find('[') {
+        let start = pos + start;
+        if let Some(mid) = cleaned[start + 1..].find(']') {
+            let mid = start + 1 + mid;
+            if let Some(end) = cleaned[mid + 1..].find(')') {
+                let end = mid + 1 + end;
+                let link_text = cleaned[start + 1..mid].to_string();
+                let link_len = link_text.len();
+                cleaned.replace_range(start..end + 1, &link_text);
+                pos = start + link_len;
+            } else {
+                break;
+            }
+        } else {
+            break;
+        }
+    }
+    
+    // Remove markdown bold/italic but keep the text
+    cleaned = cleaned.replace("**", "");
+    cleaned = cleaned.replace("*", "");
+    cleaned = cleaned.replace("__", "");
+    cleaned = cleaned.replace("_", "");
+    cleaned = cleaned.replace("~~", "");
+    cleaned = cleaned.replace("#", "");
+    
+    // Remove markdown headers (lines starting with #)
+    let lines: Vec<&str> = cleaned.lines().collect();
+    cleaned = lines
+        .iter()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('#') {
+                trimmed.trim_start_matches('#').trim_start()
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    
+    // Remove markdown list markers
+    let lines: Vec<&str> = cleaned.lines().collect();
+    cleaned = lines
+        .iter()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
+                &trimmed[2..]
+            } else if let Some(num_end) = trimmed.find(". ") {
+                if trimmed[..num_end].chars().all(|c| c.is_ascii_digit()) {
+                    &trimmed[num_end + 2..]
+                } else {
+                    line
+                }
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    
+    // Remove "asterisk" word if it appears (TTS might read * as "asterisk")
+    cleaned = cleaned.replace(" asterisk ", " ");
+    cleaned = cleaned.replace(" asterisks ", " ");
+    cleaned = cleaned.replace("Asterisk ", "");
+    cleaned = cleaned.replace("Asterisks ", "");
+    
+    // Normalize whitespace - replace multiple spaces/newlines with single space
+    let mut result = String::with_capacity(cleaned.len());
+    let mut last_was_whitespace = false;
+    for ch in cleaned.chars() {
+        if ch.is_whitespace() {
+            if !last_was_whitespace {
+                result.push(' ');
+                last_was_whitespace = true;
+            }
+        } else {
+            result.push(ch);
+            last_was_whitespace = false;
+        }
+    }
+    cleaned = result;
+    
+    // Fix spacing around punctuation - remove space before punctuation
+    cleaned = cleaned.replace(" ,", ",");
+    cleaned = cleaned.replace(" .", ".");
+    cleaned = cleaned.replace(" !", "!");
+    cleaned = cleaned.replace(" ?", "?");
+    cleaned = cleaned.replace(" ;", ";");
+    cleaned = cleaned.replace(" :", ":");
+    
+    // Enhanced: Add natural pauses for commas and sentence endings
+    // This helps TTS systems naturally pause at appropriate points for all languages.
+    // Segments come from the shared segmenter, so abbreviations like "z.B." or "M." get no pause.
+    let segments = tts_core::segment_text(&cleaned, language);
+    let mut result = String::with_capacity(cleaned.len() * 2);
+    for (i, segment) in segments.iter().enumerate() {
+        result.push_str(&segment.text);
+        let followed = i + 1 < segments.len();
+        if followed && !segment.text.ends_with(char::is_whitespace) {
+            result.push_str(match segment.boundary {
+                Some(tts_core::Boundary::Comma) => "  ", // Double space for short pause hint
+                Some(tts_core::Boundary::Clause) => "   ", // Triple space for medium pause
+                Some(tts_core::Boundary::Sentence) => "    ", // Quadruple space for longer sentence-ending pause
+                None => "",
+            });
+        }
+    }
+    cleaned = result;
+    
+    // Clean up excessive spaces (more than 4 consecutive spaces) but keep pause hints
+    // This normalizes while preserving intentional pauses
+    let mut result = String::with_capacity(cleaned.len());
+    let mut space_count = 0;
+    for ch in cleaned.chars() {
+        if ch == ' ' {
+            space_count += 1;
+            // Keep up to 4 spaces (for sentence endings), normalize beyond that
+            if space_count <= 4 {
+                result.push(ch);
+            }
+        } else {
+            space_count = 0;
+            result.push(ch);
+        }
+    }
+    cleaned = result;
+    
+    // Remove leading/trailing whitespace
+    cleaned = cleaned.trim().to_string();
+    
+    // If empty after cleaning, return original (fallback)
+    if cleaned.is_empty() {
+        text.to_string()
+    } else {
+        cleaned
+    }
+}
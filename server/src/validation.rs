@@ -6,6 +6,28 @@ const MAX_TEXT_LENGTH: usize = 5000;
 const MIN_TEXT_LENGTH: usize = 1;
 /// Maximum message length for chat requests
 const MAX_MESSAGE_LENGTH: usize = 10000;
+/// Allowed range for speaking rate and Piper length scale
+const RATE_RANGE: (f32, f32) = (0.25, 4.0);
+/// Allowed range for Piper noise scale and noise width
+const NOISE_RANGE: (f32, f32) = (0.0, 2.0);
+/// Allowed loudness targets (LUFS)
+const LOUDNESS_RANGE: (f32, f32) = (-40.0, -5.0);
+/// Allowed true-peak ceilings (dBTP)
+const TRUE_PEAK_RANGE: (f32, f32) = (-12.0, 0.0);
+/// Allowed output sample rates (Hz)
+const SAMPLE_RATE_RANGE: (u32, u32) = (8000, 96000);
+/// Allowed subtitle line lengths (characters)
+const SUBTITLE_LINE_RANGE: (usize, usize) = (10, 200);
+/// Allowed subtitle lines per cue
+const SUBTITLE_LINES_RANGE: (usize, usize) = (1, 4);
+/// Allowed maximum cue durations (ms)
+const SUBTITLE_DURATION_RANGE: (u64, u64) = (1000, 30000);
+/// Allowed caps on silences within the audio (ms)
+const MAX_SILENCE_RANGE: (u32, u32) = (50, 5000);
+/// Maximum length of a lexicon word or phrase
+const MAX_LEXICON_WORD_LENGTH: usize = 100;
+/// Maximum length of a lexicon respelling or phoneme string
+const MAX_PRONUNCIATION_LENGTH: usize = 500;
 
 /// Validate TTS request
 pub fn validate_tts_request(text: &str, language: Option<&str>) -> Result<(), ApiError> {
@@ -39,6 +61,181 @@ pub fn validate_tts_request(text: &str, language: Option<&str>) -> Result<(), Ap
     Ok(())
 }
 
+/// Validate a TTS request with phoneme input: the phonemes replace the text, so `text` must
+/// be empty and SSML is not allowed
+pub fn validate_phoneme_request(phonemes: &str, text: &str, ssml: bool, language: Option<&str>) -> Result<(), ApiError> {
+    if !text.is_empty() {
+        return Err(ApiError::InvalidInput("Provide either 'text' or 'phonemes', not both".to_string()));
+    }
+    if ssml {
+        return Err(ApiError::InvalidInput("'phonemes' cannot be combined with 'ssml'".to_string()));
+    }
+    if phonemes.trim().is_empty() {
+        return Err(ApiError::InvalidInput("Phonemes cannot be empty".to_string()));
+    }
+    // Same length limit and language check as text input
+    validate_tts_request(phonemes, language)
+}
+
+/// Validate speaker ID for multi-speaker voices
+/// (the upper bound depends on the voice and is checked at synthesis time)
+pub fn validate_speaker_id(speaker: Option<i64>) -> Result<(), ApiError> {
+    if let Some(sid) = speaker {
+        if sid < 0 {
+            return Err(ApiError::InvalidInput(format!(
+                "Invalid speaker ID: {}. Speaker IDs start at 0",
+                sid
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate optional prosody controls (speaking rate, length scale, noise scale, noise width)
+pub fn validate_prosody(
+    rate: Option<f32>,
+    length_scale: Option<f32>,
+    noise_scale: Option<f32>,
+    noise_w: Option<f32>,
+) -> Result<(), ApiError> {
+    if rate.is_some() && length_scale.is_some() {
+        return Err(ApiError::InvalidInput(
+            "Specify either rate or length_scale, not both".to_string(),
+        ));
+    }
+
+    let checks = [
+        ("rate", rate, RATE_RANGE),
+        ("length_scale", length_scale, RATE_RANGE),
+        ("noise_scale", noise_scale, NOISE_RANGE),
+        ("noise_w", noise_w, NOISE_RANGE),
+    ];
+    for (name, value, (min, max)) in checks {
+        if let Some(v) = value {
+            if !v.is_finite() || v < min || v > max {
+                return Err(ApiError::InvalidInput(format!(
+                    "{} out of range (expected {} to {})",
+                    name, min, max
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate an optional output sample rate
+pub fn validate_sample_rate(sample_rate: Option<u32>) -> Result<(), ApiError> {
+    if let Some(rate) = sample_rate {
+        if rate < SAMPLE_RATE_RANGE.0 || rate > SAMPLE_RATE_RANGE.1 {
+            return Err(ApiError::InvalidInput(format!(
+                "Invalid sample_rate: {}. Must be between {} and {} Hz",
+                rate, SAMPLE_RATE_RANGE.0, SAMPLE_RATE_RANGE.1
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate silence removal settings
+pub fn validate_silence(options: &tts_core::SilenceOptions) -> Result<(), ApiError> {
+    if let Some(ms) = options.max_silence_ms {
+        if ms < MAX_SILENCE_RANGE.0 || ms > MAX_SILENCE_RANGE.1 {
+            return Err(ApiError::InvalidInput(format!(
+                "Invalid max_silence_ms: {}. Must be between {} and {} ms",
+                ms, MAX_SILENCE_RANGE.0, MAX_SILENCE_RANGE.1
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate loudness normalization settings (target LUFS and true-peak ceiling)
+pub fn validate_loudness(target: &tts_core::LoudnessTarget) -> Result<(), ApiError> {
+    let checks = [
+        ("target_lufs", target.target_lufs, LOUDNESS_RANGE),
+        ("true_peak_db", target.true_peak_db, TRUE_PEAK_RANGE),
+    ];
+    for (name, value, (min, max)) in checks {
+        if let Some(v) = value {
+            if !v.is_finite() || v < min || v > max {
+                return Err(ApiError::InvalidInput(format!(
+                    "Invalid {}: {}. Must be between {} and {}",
+                    name, v, min, max
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse the requested output format (defaults to 16-bit WAV)
+pub fn parse_audio_format(format: Option<&str>) -> Result<tts_core::AudioFormat, ApiError> {
+    let Some(name) = format else {
+        return Ok(tts_core::AudioFormat::default());
+    };
+    let format: tts_core::AudioFormat = name
+        .parse()
+        .map_err(|e: anyhow::Error| ApiError::InvalidInput(e.to_string()))?;
+    if !format.is_available() {
+        return Err(ApiError::InvalidInput(format!(
+            "Audio format '{}' is not supported by this server build. Supported: {}",
+            format,
+            tts_core::AudioFormat::available().iter().map(|f| f.name()).collect::<Vec<_>>().join(", ")
+        )));
+    }
+    Ok(format)
+}
+
+/// Parse the subtitle format and cue limits; `None` if no subtitles were requested.
+/// Unset limits use the `SubtitleOptions` defaults.
+pub fn parse_subtitle_options(
+    format: Option<&str>,
+    max_line_length: Option<usize>,
+    max_lines: Option<usize>,
+    max_cue_duration_ms: Option<u64>,
+) -> Result<Option<(tts_core::SubtitleFormat, tts_core::SubtitleOptions)>, ApiError> {
+    let Some(format) = format else {
+        return Ok(None);
+    };
+    let format = tts_core::SubtitleFormat::parse(format).ok_or_else(|| {
+        ApiError::InvalidInput(format!("Unknown subtitle format '{}'. Supported: srt, vtt", format))
+    })?;
+
+    let defaults = tts_core::SubtitleOptions::default();
+    let checks = [
+        ("subtitle_line_length", max_line_length, SUBTITLE_LINE_RANGE),
+        ("subtitle_max_lines", max_lines, SUBTITLE_LINES_RANGE),
+    ];
+    for (name, value, (min, max)) in checks {
+        if let Some(v) = value {
+            if v < min || v > max {
+                return Err(ApiError::InvalidInput(format!(
+                    "Invalid {}: {}. Must be between {} and {}",
+                    name, v, min, max
+                )));
+            }
+        }
+    }
+    if let Some(ms) = max_cue_duration_ms {
+        if ms < SUBTITLE_DURATION_RANGE.0 || ms > SUBTITLE_DURATION_RANGE.1 {
+            return Err(ApiError::InvalidInput(format!(
+                "Invalid subtitle_max_duration_ms: {}. Must be between {} and {}",
+                ms, SUBTITLE_DURATION_RANGE.0, SUBTITLE_DURATION_RANGE.1
+            )));
+        }
+    }
+
+    Ok(Some((
+        format,
+        tts_core::SubtitleOptions {
+            max_line_length: max_line_length.unwrap_or(defaults.max_line_length),
+            max_lines: max_lines.unwrap_or(defaults.max_lines),
+            max_cue_duration_ms: max_cue_duration_ms.unwrap_or(defaults.max_cue_duration_ms),
+        },
+    )))
+}
+
 /// Validate chat request
 pub fn validate_chat_request(message: &str) -> Result<(), ApiError> {
     if message.is_empty() {
@@ -53,6 +250,39 @@ pub fn validate_chat_request(message: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Validate a lexicon entry added through the API
+pub fn validate_lexicon_entry(language: &str, entry: &tts_core::LexiconEntry) -> Result<(), ApiError> {
+    if !is_valid_language_code(language) {
+        return Err(ApiError::InvalidInput(format!(
+            "Invalid language code format: {}. Expected format: ll_CC (e.g., en_US, de_DE)",
+            language
+        )));
+    }
+    let word = entry.word.trim();
+    if !word.chars().any(char::is_alphanumeric) {
+        return Err(ApiError::InvalidInput("Lexicon word must contain a letter or digit".to_string()));
+    }
+    if word.chars().count() > MAX_LEXICON_WORD_LENGTH {
+        return Err(ApiError::InvalidInput(format!(
+            "Lexicon word too long (max {} characters)",
+            MAX_LEXICON_WORD_LENGTH
+        )));
+    }
+    let pronunciation = match &entry.pronunciation {
+        tts_core::Pronunciation::Respelling(text) | tts_core::Pronunciation::Phonemes(text) => text.trim(),
+    };
+    if pronunciation.is_empty() {
+        return Err(ApiError::InvalidInput("Pronunciation cannot be empty".to_string()));
+    }
+    if pronunciation.chars().count() > MAX_PRONUNCIATION_LENGTH {
+        return Err(ApiError::InvalidInput(format!(
+            "Pronunciation too long (max {} characters)",
+            MAX_PRONUNCIATION_LENGTH
+        )));
+    }
+    Ok(())
+}
+
 /// Validate language code format (e.g., en_US, de_DE)
 fn is_valid_language_code(code: &str) -> bool {
     // Language code should be in format: ll_CC (2 lowercase letters, underscore, 2 uppercase letters)
@@ -130,6 +360,26 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_speaker_id() {
+        assert!(validate_speaker_id(None).is_ok());
+        assert!(validate_speaker_id(Some(0)).is_ok());
+        assert!(validate_speaker_id(Some(903)).is_ok());
+        assert!(validate_speaker_id(Some(-1)).is_err());
+    }
+
+    #[test]
+    fn test_validate_prosody() {
+        assert!(validate_prosody(None, None, None, None).is_ok());
+        assert!(validate_prosody(Some(1.5), None, Some(0.667), Some(0.8)).is_ok());
+        assert!(validate_prosody(None, Some(0.8), None, None).is_ok());
+        assert!(validate_prosody(Some(1.0), Some(1.0), None, None).is_err());
+        assert!(validate_prosody(Some(10.0), None, None, None).is_err());
+        assert!(validate_prosody(None, Some(0.0), None, None).is_err());
+        assert!(validate_prosody(None, None, Some(-0.1), None).is_err());
+        assert!(validate_prosody(None, None, None, Some(f32::NAN)).is_err());
+    }
+
     #[test]
     fn test_validate_chat_request_valid() {
         assert!(validate_chat_request("Hello").is_ok());
@@ -159,4 +409,73 @@ mod tests {
         assert!(validate_conversation_id("invalid-uuid").is_err());
         assert!(validate_conversation_id("").is_err());
     }
+
+    #[test]
+    fn test_validate_sample_rate() {
+        assert!(validate_sample_rate(None).is_ok());
+        assert!(validate_sample_rate(Some(8000)).is_ok());
+        assert!(validate_sample_rate(Some(48000)).is_ok());
+        assert!(validate_sample_rate(Some(4000)).is_err());
+        assert!(validate_sample_rate(Some(192000)).is_err());
+    }
+
+    #[test]
+    fn test_validate_silence() {
+        let options = |max_silence_ms| tts_core::SilenceOptions { trim_silence: true, max_silence_ms };
+        assert!(validate_silence(&options(None)).is_ok());
+        assert!(validate_silence(&options(Some(300))).is_ok());
+        assert!(validate_silence(&options(Some(0))).is_err());
+        assert!(validate_silence(&options(Some(60_000))).is_err());
+    }
+
+    #[test]
+    fn test_validate_loudness() {
+        let target = |target_lufs, true_peak_db| tts_core::LoudnessTarget { target_lufs, true_peak_db };
+        assert!(validate_loudness(&target(None, None)).is_ok());
+        assert!(validate_loudness(&target(Some(-16.0), Some(-1.0))).is_ok());
+        assert!(validate_loudness(&target(Some(0.0), None)).is_err());
+        assert!(validate_loudness(&target(None, Some(3.0))).is_err());
+        assert!(validate_loudness(&target(Some(f32::NAN), None)).is_err());
+    }
+
+    #[test]
+    fn test_parse_audio_format() {
+        assert_eq!(parse_audio_format(None).unwrap(), tts_core::AudioFormat::Wav);
+        assert_eq!(parse_audio_format(Some("FLAC")).unwrap(), tts_core::AudioFormat::Flac);
+        assert_eq!(parse_audio_format(Some("ulaw")).unwrap(), tts_core::AudioFormat::Mulaw);
+        assert!(parse_audio_format(Some("aiff")).is_err());
+    }
+
+    #[test]
+    fn test_validate_lexicon_entry() {
+        let entry = |word: &str, respelling: &str| tts_core::LexiconEntry {
+            word: word.to_string(),
+            pronunciation: tts_core::Pronunciation::Respelling(respelling.to_string()),
+        };
+        assert!(validate_lexicon_entry("en_US", &entry("Qdrant", "Kwadrant")).is_ok());
+        assert!(validate_lexicon_entry("english", &entry("Qdrant", "Kwadrant")).is_err());
+        assert!(validate_lexicon_entry("en_US", &entry("  ", "Kwadrant")).is_err());
+        assert!(validate_lexicon_entry("en_US", &entry("Qdrant", "")).is_err());
+        assert!(validate_lexicon_entry("en_US", &entry(&"a".repeat(101), "x")).is_err());
+    }
+
+    #[test]
+    fn test_validate_phoneme_request() {
+        assert!(validate_phoneme_request("həlˈoʊ", "", false, Some("en_US")).is_ok());
+        assert!(validate_phoneme_request("həlˈoʊ", "Hello", false, Some("en_US")).is_err());
+        assert!(validate_phoneme_request("həlˈoʊ", "", true, Some("en_US")).is_err());
+        assert!(validate_phoneme_request(" \n ", "", false, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_subtitle_options() {
+        assert!(parse_subtitle_options(None, Some(5), None, None).unwrap().is_none());
+        let (format, options) = parse_subtitle_options(Some("srt"), Some(32), None, None).unwrap().unwrap();
+        assert_eq!(format, tts_core::SubtitleFormat::Srt);
+        assert_eq!(options.max_line_length, 32);
+        assert_eq!(options.max_lines, tts_core::SubtitleOptions::default().max_lines);
+        assert!(parse_subtitle_options(Some("ass"), None, None, None).is_err());
+        assert!(parse_subtitle_options(Some("vtt"), Some(5), None, None).is_err());
+        assert!(parse_subtitle_options(Some("vtt"), None, None, Some(100)).is_err());
+    }
 }
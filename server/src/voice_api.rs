@@ -0,0 +1,158 @@
+// Voice and lexicon administration: voice file checks, map.json reload, phonemization and
+// the pronunciation lexicons
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::validation::{validate_lexicon_entry, validate_tts_request};
+
+#[derive(Deserialize)]
+pub struct PhonemizeRequest {
+    text: String,
+    language: Option<String>,
+    voice: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PhonemizeResponse {
+    phonemes: String, // sentences joined by newlines (accepted as /tts `phonemes` input)
+    sentences: Vec<String>,
+}
+
+/// Query for GET and DELETE /lexicon
+#[derive(Deserialize)]
+pub struct LexiconQuery {
+    language: Option<String>,
+    voice: Option<String>, // voice lexicon; omit for the language lexicon
+    word: Option<String>, // required for DELETE
+}
+
+#[derive(Deserialize)]
+pub struct LexiconEntryRequest {
+    language: String,
+    voice: Option<String>,
+    #[serde(flatten)]
+    entry: tts_core::LexiconEntry, // word + respelling or phonemes
+}
+
+#[derive(Serialize)]
+pub struct LexiconInfo {
+    language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voice: Option<String>,
+    entries: Vec<tts_core::LexiconEntry>,
+}
+
+#[derive(Serialize)]
+pub struct LexiconUpdateResponse {
+    invalidated_cache_entries: usize,
+}
+
+#[derive(Serialize)]
+pub struct VoiceStatusReport {
+    ok: bool,
+    total: usize,
+    broken: usize,
+    voices: Vec<tts_core::VoiceStatus>,
+}
+
+/// Integrity of every voice's files (missing files, Git LFS pointers, invalid configs, checksums).
+/// Checksums are cached per file size and modification time, so only changed files are hashed.
+pub async fn voices_status(State(state): State<AppState>) -> Result<Json<VoiceStatusReport>, ApiError> {
+    let tts = state.tts.clone();
+    let voices = tokio::task::spawn_blocking(move || tts.voice_status())
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))?;
+    let broken = voices.iter().filter(|status| !status.is_ok()).count();
+    Ok(Json(VoiceStatusReport { ok: broken == 0, total: voices.len(), broken, voices }))
+}
+
+/// Reload the voice map (models/map.json) without a restart. A map that does not parse is a bad request; one
+/// that parses but fails validation is answered with 422 and the problems found.
+pub async fn reload_voices(State(state): State<AppState>) -> Result<Json<tts_core::MapReload>, ApiError> {
+    let map_file = &state.config.map_file;
+    state.tts.reload_mapfile(map_file).await.map(Json).map_err(|e| {
+        let message = format!("{map_file} rejected, keeping the current voices: {e:#}");
+        match e.downcast::<tts_core::InvalidVoiceMap>() {
+            Ok(invalid) => ApiError::Unprocessable(message, invalid.errors),
+            Err(_) => ApiError::InvalidInput(message),
+        }
+    })
+}
+
+pub async fn phonemize_endpoint(
+    State(state): State<AppState>,
+    Json(req): Json<PhonemizeRequest>,
+) -> Result<Json<PhonemizeResponse>, ApiError> {
+    validate_tts_request(&req.text, req.language.as_deref())?;
+
+    let tts = state.tts.clone();
+    let sentences = tokio::task::spawn_blocking(move || {
+        tts.phonemize(&req.text, req.language.as_deref(), req.voice.as_deref())
+    })
+    .await
+    .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+
+    Ok(Json(PhonemizeResponse { phonemes: sentences.join("\n"), sentences }))
+}
+
+pub async fn list_lexicons(
+    State(state): State<AppState>,
+    Query(query): Query<LexiconQuery>,
+) -> Json<Vec<LexiconInfo>> {
+    let lexicons = state
+        .tts
+        .lexicons()
+        .into_iter()
+        .filter(|(scope, _)| query.language.as_ref().is_none_or(|lang| &scope.language == lang))
+        .filter(|(scope, _)| query.voice.is_none() || scope.voice == query.voice)
+        .map(|(scope, entries)| LexiconInfo { language: scope.language, voice: scope.voice, entries })
+        .collect();
+    Json(lexicons)
+}
+
+pub async fn add_lexicon_entry(
+    State(state): State<AppState>,
+    Json(req): Json<LexiconEntryRequest>,
+) -> Result<Json<LexiconUpdateResponse>, ApiError> {
+    validate_lexicon_entry(&req.language, &req.entry)?;
+    let scope = lexicon_scope(&state, &req.language, req.voice.as_deref())?;
+    let mut entry = req.entry;
+    entry.word = entry.word.trim().to_string();
+
+    let invalidated_cache_entries = state.tts.add_lexicon_entry(scope, entry).await?;
+    Ok(Json(LexiconUpdateResponse { invalidated_cache_entries }))
+}
+
+pub async fn delete_lexicon_entry(
+    State(state): State<AppState>,
+    Query(query): Query<LexiconQuery>,
+) -> Result<Json<LexiconUpdateResponse>, ApiError> {
+    let (Some(language), Some(word)) = (query.language.as_deref(), query.word.as_deref()) else {
+        return Err(ApiError::InvalidInput("'language' and 'word' are required".to_string()));
+    };
+    let scope = lexicon_scope(&state, language, query.voice.as_deref())?;
+
+    match state.tts.remove_lexicon_entry(&scope, word.trim()).await? {
+        Some(invalidated_cache_entries) => Ok(Json(LexiconUpdateResponse { invalidated_cache_entries })),
+        None => Err(ApiError::NotFound(format!("No lexicon entry for '{}'", word))),
+    }
+}
+
+/// Resolve the lexicon scope of a request, checking that the language and voice exist
+fn lexicon_scope(state: &AppState, language: &str, voice: Option<&str>) -> Result<tts_core::LexiconScope, ApiError> {
+    if !state.tts.list_languages().iter().any(|lang| lang == language) {
+        return Err(ApiError::NotFound(format!("Unknown language '{}'", language)));
+    }
+    if let Some(voice) = voice {
+        if !state.tts.list_voices_for_language(language).iter().any(|(id, _)| id == voice) {
+            return Err(ApiError::NotFound(format!("Unknown voice '{}' for language '{}'", voice, language)));
+        }
+    }
+    Ok(tts_core::LexiconScope { language: language.to_string(), voice: voice.map(|v| v.to_string()) })
+}
@@ -0,0 +1,145 @@
+//! End-to-end tests for the complete chat pipeline
+//! Tests: User message -> LLM response -> TTS audio output
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+use crate::e2e_test_helpers::create_test_app;
+
+async fn post_json(app: &Router, uri: &str, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_complete_chat_pipeline_new_conversation() {
+    let app = create_test_app().await;
+
+    let (status, chat_response) = post_json(&app, "/chat", json!({
+        "message": "Hello, this is a test message.",
+        "language": "en_US"
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        chat_response["reply"],
+        "You said: Hello, this is a test message. This is turn 1 of our conversation."
+    );
+
+    // A new conversation gets a UUID
+    let conv_id = chat_response["conversation_id"].as_str().unwrap();
+    assert!(uuid::Uuid::parse_str(conv_id).is_ok(), "Conversation ID should be a UUID: {conv_id}");
+
+    // The reply is returned before it is spoken
+    assert!(chat_response["audio_base64"].is_null());
+    assert!(chat_response["sample_rate"].is_null());
+}
+
+#[tokio::test]
+async fn test_chat_pipeline_conversation_continuity() {
+    let app = create_test_app().await;
+
+    let (status, chat_response) = post_json(&app, "/chat", json!({ "message": "My name is Alice." })).await;
+    assert_eq!(status, StatusCode::OK);
+    let conversation_id = chat_response["conversation_id"].as_str().unwrap();
+
+    // The LLM sees the history of the conversation
+    let (status, continue_response) = post_json(&app, "/chat", json!({
+        "message": "What is my name?",
+        "conversation_id": conversation_id
+    }))
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(continue_response["conversation_id"], conversation_id, "Should maintain same conversation ID");
+    assert_eq!(continue_response["reply"], "You said: What is my name? This is turn 2 of our conversation.");
+
+    // Other conversations start over
+    let (_, other_response) = post_json(&app, "/chat", json!({ "message": "What is my name?" })).await;
+    assert_ne!(other_response["conversation_id"], conversation_id);
+    assert_eq!(other_response["reply"], "You said: What is my name? This is turn 1 of our conversation.");
+}
+
+#[tokio::test]
+async fn test_chat_pipeline_with_tts_audio() {
+    let app = create_test_app().await;
+
+    // With a language the reply is also spoken, in the background
+    let (status, chat_response) = post_json(&app, "/chat", json!({
+        "message": "Say hello in German.",
+        "language": "de_DE"
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(chat_response["reply"], "You said: Say hello in German. This is turn 1 of our conversation.");
+    assert!(chat_response["audio_base64"].is_null());
+    assert!(chat_response["duration_ms"].is_null());
+}
+
+#[tokio::test]
+async fn test_chat_pipeline_rejects_invalid_input() {
+    let app = create_test_app().await;
+
+    let (status, error) = post_json(&app, "/chat", json!({ "message": "" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], 400);
+    assert!(error["error"].as_str().unwrap().contains("Message cannot be empty"));
+
+    let (status, error) = post_json(&app, "/voice-chat", json!({
+        "message": "Hello.",
+        "conversation_id": "not a valid id!"
+    }))
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_voice_chat_endpoint() {
+    let app = create_test_app().await;
+
+    let (status, chat_response) = post_json(&app, "/voice-chat", json!({
+        "message": "Hello, voice chat test.",
+        "language": "en_US",
+        "alignment": true
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{chat_response}");
+    let reply = chat_response["reply"].as_str().unwrap();
+    assert_eq!(reply, "You said: Hello, voice chat test. This is turn 1 of our conversation.");
+    assert_eq!(chat_response["cleaned_text"], reply);
+    assert!(chat_response["conversation_id"].is_string());
+
+    // Synthesized by the mock engine: a WAV file ("RIFF" in base64)
+    assert_eq!(chat_response["mime_type"], "audio/wav");
+    assert!(chat_response["audio_base64"].as_str().unwrap().starts_with("UklGR"));
+    assert!(chat_response["duration_ms"].as_u64().unwrap() > 0);
+    assert!(chat_response["sample_rate"].as_u64().unwrap() > 0);
+
+    // Word timings point into the reply
+    let words = chat_response["words"].as_array().unwrap();
+    assert_eq!(words.first().unwrap()["word"], "You");
+    assert_eq!(words.last().unwrap()["word"], "conversation");
+    let duration_ms = chat_response["duration_ms"].as_u64().unwrap();
+    assert!(words.iter().all(|w| w["end_ms"].as_u64().unwrap() <= duration_ms));
+}
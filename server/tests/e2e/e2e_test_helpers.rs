@@ -0,0 +1,150 @@
+//! Test helpers for e2e tests
+
+use axum::Router;
+use futures_util::Stream;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{atomic::AtomicU64, Arc};
+use tts_core::TtsManager;
+use llm_core::{LlmClient, LlmProvider, LlmProviderTrait, Message};
+use server::{config::ServerConfig, metrics::AppMetrics, state::AppState};
+use tower::ServiceBuilder;
+use tower_http::cors::CorsLayer;
+
+/// LLM that answers without a model: the reply names the user message and how many user
+/// messages the conversation has, and streams word by word
+pub struct StubLlm;
+
+impl StubLlm {
+    pub fn reply(messages: &[Message]) -> String {
+        let turns = messages.iter().filter(|m| m.role == "user").count();
+        let last = messages.last().map(|m| m.content.as_str()).unwrap_or_default();
+        format!("You said: {last} This is turn {turns} of our conversation.")
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProviderTrait for StubLlm {
+    async fn chat(&self, messages: &[Message]) -> anyhow::Result<String> {
+        Ok(Self::reply(messages))
+    }
+
+    fn provider_type(&self) -> LlmProvider {
+        LlmProvider::Ollama
+    }
+
+    fn chat_stream(&self, messages: &[Message]) -> Pin<Box<dyn Stream<Item = anyhow::Result<String>> + Send>> {
+        let tokens: Vec<anyhow::Result<String>> =
+            Self::reply(messages).split_inclusive(' ').map(|token| Ok(token.to_string())).collect();
+        Box::pin(futures_util::stream::iter(tokens))
+    }
+}
+
+/// Bearer token of the admin routes in `create_test_app_with_config` apps that set one
+pub const TEST_ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Create a test app instance for e2e tests
+/// The routes are the server's handlers, on the mock TTS engine and the stub LLM
+pub async fn create_test_app() -> Router {
+    create_test_app_with_config(ServerConfig::default()).await
+}
+
+/// `create_test_app` with a server configuration, e.g. an admin token or another map file
+pub async fn create_test_app_with_config(config: ServerConfig) -> Router {
+    use axum::{
+        extract::State,
+        middleware::from_fn_with_state,
+        routing::{get, post},
+        Json,
+    };
+    use server::auth::require_admin;
+    use server::chat_api::{chat_endpoint, chat_stream_ws, voice_chat_endpoint};
+    use server::tts_api::tts_endpoint;
+    use server::voice_api::{add_lexicon_entry, delete_lexicon_entry, list_lexicons, phonemize_endpoint, reload_voices, voices_status};
+    
+    // Create minimal TTS manager for testing
+    let mut map = HashMap::new();
+    map.insert(
+        "de_DE".to_string(),
+        (
+            "models/de_DE/thorsten/config.onnx.json".to_string(),
+            None,
+        ),
+    );
+    // Add a few more test languages
+    map.insert(
+        "en_US".to_string(),
+        (
+            "models/en_US/en_US-lessac-medium.onnx.json".to_string(),
+            None,
+        ),
+    );
+    map.insert(
+        "fr_FR".to_string(),
+        (
+            "models/fr_FR/fr_FR-siwis-medium.onnx.json".to_string(),
+            None,
+        ),
+    );
+    
+    // The mock engine synthesizes without model files (they are Git LFS pointers in CI)
+    let tts = Arc::new(TtsManager::new(map).with_engine(tts_core::EngineKind::Mock));
+
+    let llm = Arc::new(LlmClient::with_provider(Arc::new(StubLlm)));
+
+    let state = AppState {
+        tts,
+        llm,
+        request_count: Arc::new(AtomicU64::new(0)),
+        config,
+        metrics: AppMetrics::new(),
+        llm_provider: LlmProvider::Ollama,
+    };
+    
+    // Admin routes behind the ADMIN_TOKEN check, as in the server
+    let admin_api = Router::new()
+        .route("/lexicon", post(add_lexicon_entry).delete(delete_lexicon_entry))
+        .route("/voices/reload", post(reload_voices))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
+    Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .route("/voices", get({
+            move |State(s): State<AppState>| async move {
+                Json(s.tts.list_languages())
+            }
+        }))
+        .route("/voices/detail", get({
+            move |State(s): State<AppState>| async move {
+                let mut out = Vec::new();
+                for (k, (cfg, spk)) in s.tts.map_iter() {
+                    out.push(serde_json::json!({
+                        "key": k,
+                        "config": cfg,
+                        "speaker": spk
+                    }));
+                }
+                Json(out)
+            }
+        }))
+        .route("/tts", post(tts_endpoint))
+        .route("/chat", post(chat_endpoint))
+        .route("/voice-chat", post(voice_chat_endpoint))
+        .route("/ws/chat/stream", get(chat_stream_ws))
+        .route("/voices/status", get(voices_status))
+        .route("/phonemize", post(phonemize_endpoint))
+        .route("/lexicon", get(list_lexicons))
+        .merge(admin_api)
+        .layer(ServiceBuilder::new().layer(CorsLayer::permissive()).into_inner())
+        .with_state(state)
+}
+
+/// Serve the test app on a free local port, for clients that need a real connection
+/// (WebSockets)
+pub async fn spawn_test_server() -> std::net::SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = create_test_app().await;
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
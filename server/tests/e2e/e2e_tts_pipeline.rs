@@ -62,7 +62,7 @@ async fn test_complete_tts_pipeline() {
     
     // Verify audio is valid base64
     let audio_base64 = tts_response["audio_base64"].as_str().unwrap();
-    assert!(audio_base64.len() > 0, "Audio should have content");
+    assert!(!audio_base64.is_empty(), "Audio should have content");
     
     // Verify reasonable values
     let sample_rate = tts_response["sample_rate"].as_u64().unwrap();
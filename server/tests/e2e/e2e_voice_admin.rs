@@ -0,0 +1,233 @@
+//! End-to-end tests for the voice and lexicon endpoints
+//! Tests: /voices/status, /phonemize, /lexicon and /voices/reload, including the admin token check
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    Router,
+};
+use serde_json::json;
+use server::config::ServerConfig;
+use tower::ServiceExt;
+
+use crate::e2e_test_helpers::{create_test_app, create_test_app_with_config, TEST_ADMIN_TOKEN};
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let body = match body {
+        Some(body) => {
+            request = request.header("content-type", "application/json");
+            Body::from(serde_json::to_string(&body).unwrap())
+        }
+        None => Body::empty(),
+    };
+    let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+}
+
+/// Test app with the admin token set, reloading its voices from `map_file`
+async fn create_admin_app(map_file: &str) -> Router {
+    create_test_app_with_config(ServerConfig {
+        admin_token: Some(TEST_ADMIN_TOKEN.to_string()),
+        map_file: map_file.to_string(),
+        ..ServerConfig::default()
+    })
+    .await
+}
+
+/// A map file in the temp directory, unique to this test process
+fn write_map_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("tts-e2e-{}-{name}.json", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path.display().to_string()
+}
+
+#[tokio::test]
+async fn test_voices_status() {
+    let app = create_test_app().await;
+
+    let (status, report) = send(&app, "GET", "/voices/status", None, None).await;
+    assert_eq!(status, StatusCode::OK, "{report}");
+    // Mock voices need no model files
+    assert_eq!(report["ok"], true);
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["broken"], 0);
+    assert_eq!(report["voices"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_phonemize() {
+    let app = create_test_app().await;
+
+    let (status, response) = send(&app, "POST", "/phonemize", None, Some(json!({
+        "text": "Hello world. How are you?",
+        "language": "en_US"
+    })))
+    .await;
+    assert_eq!(status, StatusCode::OK, "{response}");
+    // The mock engine's phonemes are the lowercased sentences
+    let sentences: Vec<&str> = response["sentences"].as_array().unwrap().iter().map(|s| s.as_str().unwrap()).collect();
+    assert_eq!(sentences, vec!["hello world.", "how are you?"]);
+    assert_eq!(response["phonemes"], sentences.join("\n"));
+}
+
+#[tokio::test]
+async fn test_phonemize_rejects_invalid_input() {
+    let app = create_test_app().await;
+
+    let (status, error) = send(&app, "POST", "/phonemize", None, Some(json!({ "text": "", "language": "en_US" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], 400);
+
+    let (status, error) = send(&app, "POST", "/phonemize", None, Some(json!({ "text": "Hello.", "language": "english" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("Invalid language code"));
+}
+
+#[tokio::test]
+async fn test_lexicon_edits_require_admin_token() {
+    let entry = json!({ "language": "en_US", "word": "Nginx", "respelling": "engine x" });
+
+    // Without ADMIN_TOKEN the admin routes are refused
+    let app = create_test_app().await;
+    let (status, error) = send(&app, "POST", "/lexicon", Some(TEST_ADMIN_TOKEN), Some(entry.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["code"], 401);
+
+    let app = create_admin_app("models/map.json").await;
+    let (status, _) = send(&app, "POST", "/lexicon", None, Some(entry.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "POST", "/lexicon", Some("wrong-token"), Some(entry)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "DELETE", "/lexicon?language=en_US&word=Nginx", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Nothing was added
+    let (status, lexicons) = send(&app, "GET", "/lexicon", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(lexicons, json!([]));
+}
+
+#[tokio::test]
+async fn test_lexicon_add_list_and_delete() {
+    let app = create_admin_app("models/map.json").await;
+
+    let (status, response) = send(&app, "POST", "/lexicon", Some(TEST_ADMIN_TOKEN), Some(json!({
+        "language": "en_US",
+        "word": " Nginx ",
+        "respelling": "engine x"
+    })))
+    .await;
+    assert_eq!(status, StatusCode::OK, "{response}");
+    assert_eq!(response["invalidated_cache_entries"], 0);
+
+    let (status, lexicons) = send(&app, "GET", "/lexicon?language=en_US", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(lexicons, json!([{ "language": "en_US", "entries": [{ "word": "Nginx", "respelling": "engine x" }] }]));
+    let (_, lexicons) = send(&app, "GET", "/lexicon?language=de_DE", None, None).await;
+    assert_eq!(lexicons, json!([]));
+
+    // The entry applies to phonemization
+    let (_, response) = send(&app, "POST", "/phonemize", None, Some(json!({ "text": "Nginx runs.", "language": "en_US" }))).await;
+    assert_eq!(response["phonemes"], "engine x runs.");
+
+    let (status, _) = send(&app, "DELETE", "/lexicon?language=en_US&word=Nginx", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, error) = send(&app, "DELETE", "/lexicon?language=en_US&word=Nginx", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["code"], 404);
+}
+
+#[tokio::test]
+async fn test_lexicon_rejects_invalid_entries() {
+    let app = create_admin_app("models/map.json").await;
+    let add = |entry: serde_json::Value| {
+        let app = app.clone();
+        async move { send(&app, "POST", "/lexicon", Some(TEST_ADMIN_TOKEN), Some(entry)).await }
+    };
+
+    let (status, error) = add(json!({ "language": "en_US", "word": "...", "respelling": "dots" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("letter or digit"));
+
+    let (status, error) = add(json!({ "language": "en_US", "word": "Nginx", "respelling": " " })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("Pronunciation cannot be empty"));
+
+    let (status, _) = add(json!({ "language": "english", "word": "Nginx", "respelling": "engine x" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Well-formed, but no such voice
+    let (status, _) = add(json!({ "language": "it_IT", "word": "Nginx", "respelling": "engine x" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = add(json!({ "language": "en_US", "voice": "nobody", "word": "Nginx", "respelling": "engine x" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, error) = send(&app, "DELETE", "/lexicon?language=en_US", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("'word'"));
+}
+
+#[tokio::test]
+async fn test_reload_voices() {
+    let map_file = write_map_file("reload", r#"{
+        "en_US": {
+            "default_voice": "amy",
+            "voices": { "amy": { "config": "models/en_US/amy/config.onnx.json" } }
+        }
+    }"#);
+    let app = create_admin_app(&map_file).await;
+
+    let (status, _) = send(&app, "POST", "/voices/reload", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, reload) = send(&app, "POST", "/voices/reload", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK, "{reload}");
+    assert_eq!(reload["added"], json!(["en_US/amy"]));
+    assert_eq!(reload["removed"], json!(["de_DE", "en_US", "fr_FR"]));
+
+    let (_, report) = send(&app, "GET", "/voices/status", None, None).await;
+    assert_eq!(report["total"], 1);
+    assert_eq!(report["voices"][0]["voice"], "en_US/amy");
+    let _ = std::fs::remove_file(map_file);
+}
+
+#[tokio::test]
+async fn test_reload_voices_rejects_invalid_maps() {
+    // Parses, but the default voice is not one of the voices: 422 with the problems found
+    let map_file = write_map_file("invalid", r#"{
+        "en_US": {
+            "default_voice": "norman",
+            "voices": { "amy": { "config": "models/en_US/amy/config.onnx.json" } }
+        }
+    }"#);
+    let app = create_admin_app(&map_file).await;
+    let (status, error) = send(&app, "POST", "/voices/reload", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{error}");
+    assert_eq!(error["code"], 422);
+    assert_eq!(error["details"], json!(["default voice 'norman' of en_US is not one of its voices"]));
+    let _ = std::fs::remove_file(map_file);
+
+    // Does not parse: 400
+    let map_file = write_map_file("malformed", "{ not json");
+    let app = create_admin_app(&map_file).await;
+    let (status, error) = send(&app, "POST", "/voices/reload", Some(TEST_ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("keeping the current voices"));
+    let _ = std::fs::remove_file(map_file);
+
+    // The current voices stay in place
+    let (_, report) = send(&app, "GET", "/voices/status", None, None).await;
+    assert_eq!(report["total"], 3);
+}
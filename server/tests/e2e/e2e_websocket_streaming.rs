@@ -0,0 +1,59 @@
+//! End-to-end tests for WebSocket streaming endpoints
+//! Tests: WebSocket connection -> Streaming tokens -> Streaming audio chunks
+
+use futures_util::StreamExt;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::e2e_test_helpers::spawn_test_server;
+
+/// All text frames of one /ws/chat/stream session, parsed
+async fn stream_frames(query: &str) -> Vec<serde_json::Value> {
+    let addr = spawn_test_server().await;
+    let (mut socket, _) = connect_async(format!("ws://{addr}/ws/chat/stream?{query}")).await.unwrap();
+    let mut frames = Vec::new();
+    // Rejected requests drop the socket without a close frame
+    while let Some(Ok(message)) = socket.next().await {
+        match message {
+            Message::Text(text) => frames.push(serde_json::from_str(text.as_str()).unwrap()),
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+    frames
+}
+
+#[tokio::test]
+async fn test_websocket_streams_tokens_and_audio() {
+    let frames = stream_frames("message=Hello%20stream.&language=en_US").await;
+    let reply = "You said: Hello stream. This is turn 1 of our conversation.";
+
+    assert_eq!(frames.first().unwrap()["type"], "status");
+    assert_eq!(frames.first().unwrap()["status"], "streaming");
+
+    // Tokens arrive one by one, each with the text so far
+    let tokens: Vec<&serde_json::Value> = frames.iter().filter(|f| f["type"] == "token").collect();
+    assert!(tokens.len() > 1);
+    assert_eq!(tokens.iter().map(|t| t["token"].as_str().unwrap()).collect::<String>(), reply);
+    assert_eq!(tokens.last().unwrap()["text"], reply);
+
+    // The reply is spoken in chunks: WAV files from the mock engine
+    let audio: Vec<&serde_json::Value> = frames.iter().filter(|f| f["type"] == "audio_chunk").collect();
+    assert!(!audio.is_empty());
+    assert!(audio.iter().all(|a| a["audio"].as_str().unwrap().starts_with("UklGR") && a["sample_rate"].as_u64().unwrap() > 0));
+
+    let last = frames.last().unwrap();
+    assert_eq!((last["type"].as_str(), last["status"].as_str()), (Some("status"), Some("complete")));
+    assert_eq!(last["text"], reply);
+}
+
+#[tokio::test]
+async fn test_websocket_rejects_invalid_input() {
+    let frames = stream_frames("language=en_US").await;
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0]["code"], 400);
+    assert_eq!(frames[0]["error"], "Message parameter is required");
+
+    let frames = stream_frames("message=Hi.&speaker=abc").await;
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0]["error"], "Invalid input: speaker must be an integer");
+}
@@ -4,6 +4,8 @@
 mod e2e_tts_pipeline;
 mod e2e_chat_pipeline;
 mod e2e_test_helpers;
+mod e2e_voice_admin;
+mod e2e_websocket_streaming;
 
 pub use e2e_test_helpers::*;
 
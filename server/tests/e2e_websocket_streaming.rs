@@ -1,25 +0,0 @@
-//! End-to-end tests for WebSocket streaming endpoints
-//! Tests: WebSocket connection -> Streaming tokens -> Streaming audio chunks
-//! 
-//! Note: Full WebSocket testing requires a running server instance.
-//! Use test_streaming.js for manual WebSocket testing.
-
-use crate::e2e_test_helpers::create_test_app;
-
-#[tokio::test]
-async fn test_websocket_streaming_placeholder() {
-    // Note: Full WebSocket testing requires a running server instance.
-    // 
-    // For manual WebSocket testing, use:
-    //   node tests/test_streaming.js "Hello, world!" en_US
-    //
-    // This placeholder test ensures the e2e test structure is complete.
-    // Future improvements could include:
-    // - Spawning a test server instance
-    // - Using tokio-tungstenite or similar for WebSocket client testing
-    // - Verifying streaming message formats
-    
-    let _app = create_test_app().await;
-    // WebSocket tests would go here with a running server
-}
-
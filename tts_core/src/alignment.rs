@@ -0,0 +1,300 @@
+//! Word-level timestamps.
+//!
+//! Piper does not report phoneme durations, so word times are estimated: each synthesized chunk
+//! is trimmed to its audible part and that span is divided among the chunk's words in
+//! proportion to their length. Chunks are synthesized from normalized text ("12 €" -> "zwölf
+//! Euro"), so `WordMap` aligns the words of the normalized text with the words of the input
+//! and the timings are reported with character offsets into the input.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// Characters trimmed from the ends of a word
+const PUNCTUATION: &[char] = &[
+    '.', ',', ';', ':', '!', '?', '"', '\'', '(', ')', '[', ']', '{', '}', '«', '»', '„', '“', '”',
+    '‘', '’', '‚', '…', '–', '—', '-', '¡', '¿', '*', '/',
+];
+
+/// Samples quieter than this fraction of the chunk's peak count as silence
+const SILENCE_RATIO: f32 = 0.05;
+
+/// When a word of the input text is spoken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    /// Character offset of the word in the input text
+    pub start: usize,
+    /// Character offset just past the word
+    pub end: usize,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Where a synthesized chunk (sentence or clause) lies in the input text and in the audio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkSpan {
+    /// Character offset of the chunk's first word
+    pub start: usize,
+    /// Character offset just past the chunk's closing punctuation
+    pub end: usize,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Word and chunk timings of a synthesis result
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Alignment {
+    pub words: Vec<WordTiming>,
+    pub chunks: Vec<ChunkSpan>,
+}
+
+impl Alignment {
+    /// Append the words of a chunk synthesized from `text` whose audio starts at `start_ms`.
+    /// Word times are relative to the chunk.
+    pub(crate) fn push_chunk(&mut self, text: &str, words: Vec<WordTiming>, start_ms: u64, duration_ms: u64) {
+        let (Some(first), Some(last)) = (words.first(), words.last()) else {
+            return;
+        };
+        // Keep the punctuation that closes the chunk
+        let end = last.end + text.chars().skip(last.end).take_while(|c| !c.is_whitespace()).count();
+        self.chunks.push(ChunkSpan { start: first.start, end, start_ms, end_ms: start_ms + duration_ms });
+
+        for word in words {
+            match self.words.last_mut() {
+                // A word split across chunks
+                Some(previous) if previous.start == word.start => previous.end_ms = word.end_ms + start_ms,
+                _ => self.words.push(WordTiming {
+                    start_ms: word.start_ms + start_ms,
+                    end_ms: word.end_ms + start_ms,
+                    ..word
+                }),
+            }
+        }
+    }
+}
+
+/// Char ranges of the words in `chars` (whitespace-separated, punctuation trimmed)
+fn words(chars: &[char]) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let mut end = i;
+        while end < chars.len() && !chars[end].is_whitespace() {
+            end += 1;
+        }
+        let (mut start, mut stop) = (i, end);
+        while start < stop && PUNCTUATION.contains(&chars[start]) {
+            start += 1;
+        }
+        while stop > start && PUNCTUATION.contains(&chars[stop - 1]) {
+            stop -= 1;
+        }
+        if start < stop {
+            words.push(start..stop);
+        }
+        i = end;
+    }
+    words
+}
+
+fn word_key(chars: &[char], range: &Range<usize>) -> String {
+    chars[range.clone()].iter().flat_map(|c| c.to_lowercase()).collect()
+}
+
+/// Maps the words of a rewritten text (normalized, cleaned) back to the words of the text it
+/// was derived from
+#[derive(Debug, Clone)]
+pub(crate) struct WordMap {
+    source: Vec<char>,
+    // Rewritten word -> source word, sorted by the rewritten word's start
+    links: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl WordMap {
+    /// Align the words of `source` and `rewritten` on their longest common subsequence. Words
+    /// between two matches (a number and its spelled-out form) are distributed over each other
+    /// in order.
+    pub(crate) fn new(source: &str, rewritten: &str) -> Self {
+        let source: Vec<char> = source.chars().collect();
+        let rewritten: Vec<char> = rewritten.chars().collect();
+        let source_words = words(&source);
+        let rewritten_words = words(&rewritten);
+        let source_keys: Vec<String> = source_words.iter().map(|w| word_key(&source, w)).collect();
+        let rewritten_keys: Vec<String> = rewritten_words.iter().map(|w| word_key(&rewritten, w)).collect();
+
+        // lcs[i][j]: common words of source_keys[i..] and rewritten_keys[j..]
+        let (n, m) = (source_keys.len(), rewritten_keys.len());
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if source_keys[i] == rewritten_keys[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+
+        let mut links = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        let (mut group_i, mut group_j) = (0, 0);
+        let link_group = |links: &mut Vec<(Range<usize>, Range<usize>)>, sources: Range<usize>, rewrites: Range<usize>| {
+            let (k, count) = (sources.len(), rewrites.len());
+            for (index, r) in rewrites.enumerate() {
+                let s = if k > 0 {
+                    sources.start + index * k / count
+                } else if sources.start > 0 {
+                    // Inserted words belong to the preceding word
+                    sources.start - 1
+                } else if sources.start < source_words.len() {
+                    sources.start
+                } else {
+                    continue;
+                };
+                links.push((rewritten_words[r].clone(), source_words[s].clone()));
+            }
+        };
+        while i < n && j < m {
+            if source_keys[i] == rewritten_keys[j] {
+                link_group(&mut links, group_i..i, group_j..j);
+                links.push((rewritten_words[j].clone(), source_words[i].clone()));
+                i += 1;
+                j += 1;
+                (group_i, group_j) = (i, j);
+            } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        link_group(&mut links, group_i..n, group_j..m);
+        links.sort_by_key(|(rewritten, _)| rewritten.start);
+
+        Self { source, links }
+    }
+
+    fn source_word(&self, rewritten: &Range<usize>) -> Option<&Range<usize>> {
+        let index = self.links.binary_search_by_key(&rewritten.start, |(r, _)| r.start).ok()?;
+        Some(&self.links[index].1)
+    }
+
+    /// Estimate the timing of the words in one synthesized chunk. `offset` is the chunk's char
+    /// offset in the rewritten text; times are relative to the start of `samples`.
+    pub(crate) fn time_chunk(&self, chunk: &str, offset: usize, samples: &[f32], sample_rate: u32) -> Vec<WordTiming> {
+        let chars: Vec<char> = chunk.chars().collect();
+        let chunk_words = words(&chars);
+        let weights: Vec<usize> = chunk_words
+            .iter()
+            .map(|w| chars[w.clone()].iter().filter(|c| c.is_alphanumeric()).count().max(1))
+            .collect();
+        let total: usize = weights.iter().sum();
+        let (start, end) = speech_bounds(samples);
+        let to_ms = |sample: f64| (sample * 1000.0 / sample_rate.max(1) as f64) as u64;
+        let span = (end - start) as f64;
+
+        let mut timings: Vec<WordTiming> = Vec::new();
+        let mut elapsed = 0;
+        for (word, weight) in chunk_words.iter().zip(weights) {
+            let start_ms = to_ms(start as f64 + span * elapsed as f64 / total as f64);
+            elapsed += weight;
+            let end_ms = to_ms(start as f64 + span * elapsed as f64 / total as f64);
+            let rewritten = word.start + offset..word.end + offset;
+            let Some(source) = self.source_word(&rewritten) else {
+                continue;
+            };
+            match timings.last_mut() {
+                // Several spoken words for one input word ("12" -> "twelve hundred")
+                Some(last) if last.start == source.start => last.end_ms = end_ms,
+                _ => timings.push(WordTiming {
+                    word: self.source[source.clone()].iter().collect(),
+                    start: source.start,
+                    end: source.end,
+                    start_ms,
+                    end_ms,
+                }),
+            }
+        }
+        timings
+    }
+}
+
+/// First and one past the last audible sample
+fn speech_bounds(samples: &[f32]) -> (usize, usize) {
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let threshold = peak * SILENCE_RATIO;
+    let start = samples.iter().position(|s| s.abs() > threshold);
+    let end = samples.iter().rposition(|s| s.abs() > threshold);
+    match (start, end) {
+        (Some(start), Some(end)) => (start, end + 1),
+        _ => (0, samples.len()),
+    }
+}
+
+/// Re-point word timings computed for `rewritten` (e.g. text with markdown removed) to the
+/// words of `source`. Timings of words that became part of the same source word are merged.
+pub fn map_word_offsets(words: Vec<WordTiming>, rewritten: &str, source: &str) -> Vec<WordTiming> {
+    let map = WordMap::new(source, rewritten);
+    let mut mapped: Vec<WordTiming> = Vec::with_capacity(words.len());
+    for word in words {
+        let Some(target) = map.source_word(&(word.start..word.end)) else {
+            continue;
+        };
+        match mapped.last_mut() {
+            Some(last) if last.start == target.start => last.end_ms = word.end_ms,
+            _ => mapped.push(WordTiming {
+                word: map.source[target.clone()].iter().collect(),
+                start: target.start,
+                end: target.end,
+                ..word
+            }),
+        }
+    }
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_map_links_rewritten_words() {
+        let source = "Am 3. Mai kostet es 12 €.";
+        let rewritten = "Am dritten Mai kostet es zwölf Euro.";
+        let map = WordMap::new(source, rewritten);
+        let samples = vec![0.5f32; 3600];
+
+        let timings = map.time_chunk(rewritten, 0, &samples, 1000);
+        let words: Vec<&str> = timings.iter().map(|t| t.word.as_str()).collect();
+        assert_eq!(words, vec!["Am", "3", "Mai", "kostet", "es", "12", "€"]);
+        assert_eq!((timings[1].start, timings[1].end), (3, 4));
+        assert_eq!(timings[0].start_ms, 0);
+        assert_eq!(timings.last().unwrap().end_ms, 3600);
+        assert!(timings.windows(2).all(|w| w[0].end_ms <= w[1].start_ms));
+    }
+
+    #[test]
+    fn test_time_chunk_skips_silence_and_offsets() {
+        let rewritten = "Hello. World again";
+        let map = WordMap::new(rewritten, rewritten);
+        let mut samples = vec![0.0f32; 1000];
+        samples[200..800].fill(0.5);
+
+        let timings = map.time_chunk("World again", 7, &samples, 1000);
+        assert_eq!(timings.len(), 2);
+        assert_eq!((timings[0].start, timings[0].start_ms), (7, 200));
+        assert_eq!((timings[1].word.as_str(), timings[1].end_ms), ("again", 800));
+    }
+
+    #[test]
+    fn test_map_word_offsets() {
+        let timing = |word: &str, start, end| WordTiming { word: word.to_string(), start, end, start_ms: 0, end_ms: 100 };
+        let mapped = map_word_offsets(vec![timing("bold", 0, 4), timing("text", 5, 9)], "bold text", "**bold** text");
+        assert_eq!((mapped[0].start, mapped[0].end), (2, 6));
+        assert_eq!((mapped[1].start, mapped[1].end), (9, 13));
+    }
+}
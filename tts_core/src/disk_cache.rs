@@ -0,0 +1,414 @@
+//! Persistent response cache.
+//!
+//! A second tier under the in-memory LRU that survives restarts. Entries are content-addressed:
+//! the file name is the SHA-256 of everything that determines the audio, including a hash of
+//! the voice's model and config files, so replacing a model makes its old entries unreachable
+//! (they age out through the size budget and TTL). Files are written under a temporary name and
+//! renamed into place, so readers never see a partial entry.
+//!
+//! Entry format: one line of JSON metadata, then the little-endian PCM samples.
+
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{Hash, Hasher},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{file_hash, model_cache, Alignment, CacheUsage, LoudnessReport};
+
+/// Bump when the entry format or the key derivation changes; old entries are then never read
+const FORMAT_VERSION: u32 = 3;
+const EXTENSION: &str = "tts";
+const TEMP_PREFIX: &str = ".tmp-";
+
+/// Where and how much to cache on disk
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    pub dir: PathBuf,
+    /// Size budget for all entries; least recently used entries are evicted above it
+    pub max_bytes: u64,
+    /// Entries older than this are discarded
+    pub ttl: Duration,
+}
+
+impl DiskCacheConfig {
+    /// 1 GiB, 30 days
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes: 1024 * 1024 * 1024,
+            ttl: Duration::from_secs(30 * 24 * 3600),
+        }
+    }
+}
+
+/// Metadata stored in front of the audio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DiskEntry {
+    pub(crate) sample_rate: u32,
+    pub(crate) duration_ms: u64,
+    pub(crate) loudness: LoudnessReport,
+    pub(crate) alignment: Option<Alignment>,
+    /// f32 samples instead of i16
+    pub(crate) float_samples: bool,
+    #[serde(skip)]
+    pub(crate) audio: Vec<u8>,
+}
+
+/// `Hasher` that feeds a SHA-256 digest, so that keys are the same in every build of the server
+/// (unlike the ahash keys of the in-memory cache)
+pub(crate) struct StableHasher(Sha256);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        let mut hasher = Self(Sha256::new());
+        FORMAT_VERSION.hash(&mut hasher);
+        hasher
+    }
+
+    pub(crate) fn digest(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().unwrap_or_default())
+    }
+}
+
+// Index record of one entry file
+struct IndexEntry {
+    size: u64,
+    written: SystemTime,
+    accessed: SystemTime,
+}
+
+#[derive(Default)]
+struct Index {
+    entries: HashMap<String, IndexEntry>,
+    total_bytes: u64,
+}
+
+pub(crate) struct DiskCache {
+    config: DiskCacheConfig,
+    index: Mutex<Index>,
+    temp_counter: AtomicU64,
+}
+
+impl std::fmt::Debug for DiskCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiskCache").field("config", &self.config).finish()
+    }
+}
+
+impl DiskCache {
+    /// Open (or create) the cache directory and index the entries in it. Leftover temporary
+    /// files and expired entries are removed.
+    pub(crate) fn open(config: DiskCacheConfig) -> anyhow::Result<Self> {
+        fs::create_dir_all(&config.dir)
+            .with_context(|| format!("Failed to create cache directory {}", config.dir.display()))?;
+        let cache = Self {
+            config,
+            index: Mutex::new(Index::default()),
+            temp_counter: AtomicU64::new(0),
+        };
+
+        let now = SystemTime::now();
+        let mut index = Index::default();
+        for dir_entry in fs::read_dir(&cache.config.dir)? {
+            let dir_entry = dir_entry?;
+            let name = dir_entry.file_name().to_string_lossy().to_string();
+            let path = dir_entry.path();
+            if name.starts_with(TEMP_PREFIX) {
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+            let Some(key) = name.strip_suffix(&format!(".{EXTENSION}")) else {
+                continue;
+            };
+            let metadata = dir_entry.metadata()?;
+            let written = metadata.modified().unwrap_or(now);
+            if cache.expired(written, now) {
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+            index.total_bytes += metadata.len();
+            index.entries.insert(key.to_string(), IndexEntry {
+                size: metadata.len(),
+                written,
+                accessed: metadata.accessed().unwrap_or(written),
+            });
+        }
+        *cache.lock_index() = index;
+        cache.evict();
+        Ok(cache)
+    }
+
+    fn lock_index(&self) -> std::sync::MutexGuard<'_, Index> {
+        self.index.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn expired(&self, written: SystemTime, now: SystemTime) -> bool {
+        now.duration_since(written).is_ok_and(|age| age >= self.config.ttl)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.config.dir.join(format!("{key}.{EXTENSION}"))
+    }
+
+    /// File name of the entry for a request (`request` is the digest of a `StableHasher`)
+    /// synthesized with the voice configured at `config_path`
+    pub(crate) fn key(&self, request: &[u8; 32], config_path: &Path) -> anyhow::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(request);
+        hasher.update(self.voice_hash(config_path)?);
+        Ok(hex(&hasher.finalize()))
+    }
+
+    /// SHA-256 of the voice's config and model file hashes (`voice.onnx.json` and `voice.onnx`)
+    fn voice_hash(&self, config_path: &Path) -> anyhow::Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        for file in [config_path.to_path_buf(), model_cache::model_path(config_path)] {
+            hasher.update(file_hash::sha256(&file).with_context(|| format!("Failed to read {}", file.display()))?);
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Read an entry; expired and unreadable entries are removed
+    pub(crate) fn get(&self, key: &str) -> Option<DiskEntry> {
+        let now = SystemTime::now();
+        {
+            let mut index = self.lock_index();
+            let written = index.entries.get(key)?.written;
+            if self.expired(written, now) {
+                self.remove_locked(&mut index, key);
+                return None;
+            }
+        }
+
+        match fs::read(self.path(key)).ok().and_then(|data| Self::decode(&data)) {
+            Some(entry) => {
+                if let Some(indexed) = self.lock_index().entries.get_mut(key) {
+                    indexed.accessed = now;
+                }
+                Some(entry)
+            }
+            None => {
+                let mut index = self.lock_index();
+                self.remove_locked(&mut index, key);
+                None
+            }
+        }
+    }
+
+    /// Write an entry atomically, then evict down to the size budget
+    pub(crate) fn put(&self, key: &str, entry: &DiskEntry) -> anyhow::Result<()> {
+        let mut data = serde_json::to_vec(entry)?;
+        data.push(b'\n');
+        data.extend_from_slice(&entry.audio);
+
+        let temp = self.config.dir.join(format!(
+            "{TEMP_PREFIX}{key}-{}-{}",
+            std::process::id(),
+            self.temp_counter.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = (|| {
+            let mut file = fs::File::create(&temp)?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+            fs::rename(&temp, self.path(key))
+        })();
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(anyhow::anyhow!("Failed to write cache entry: {e}"));
+        }
+
+        {
+            let now = SystemTime::now();
+            let mut index = self.lock_index();
+            let size = data.len() as u64;
+            if let Some(previous) = index.entries.insert(key.to_string(), IndexEntry { size, written: now, accessed: now }) {
+                index.total_bytes -= previous.size;
+            }
+            index.total_bytes += size;
+        }
+        self.evict();
+        Ok(())
+    }
+
+    pub(crate) fn usage(&self) -> CacheUsage {
+        let index = self.lock_index();
+        CacheUsage {
+            entries: index.entries.len(),
+            bytes: index.total_bytes,
+            max_bytes: self.config.max_bytes,
+        }
+    }
+
+    /// Drop expired entries, then least recently used ones until the cache fits its budget
+    fn evict(&self) {
+        let now = SystemTime::now();
+        let mut index = self.lock_index();
+        if index.total_bytes <= self.config.max_bytes {
+            return;
+        }
+        let mut candidates: Vec<(bool, SystemTime, String)> = index
+            .entries
+            .iter()
+            .map(|(key, entry)| (!self.expired(entry.written, now), entry.accessed, key.clone()))
+            .collect();
+        // Expired entries first, then by last access
+        candidates.sort();
+        for (_, _, key) in candidates {
+            if index.total_bytes <= self.config.max_bytes {
+                break;
+            }
+            self.remove_locked(&mut index, &key);
+        }
+    }
+
+    fn remove_locked(&self, index: &mut Index, key: &str) {
+        if let Some(entry) = index.entries.remove(key) {
+            index.total_bytes -= entry.size;
+            let _ = fs::remove_file(self.path(key));
+        }
+    }
+
+    fn decode(data: &[u8]) -> Option<DiskEntry> {
+        let newline = data.iter().position(|&b| b == b'\n')?;
+        let mut entry: DiskEntry = serde_json::from_slice(&data[..newline]).ok()?;
+        entry.audio = data[newline + 1..].to_vec();
+        Some(entry)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tts-disk-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entry(audio: &[u8]) -> DiskEntry {
+        DiskEntry {
+            sample_rate: 22050,
+            duration_ms: 100,
+            loudness: LoudnessReport::default(),
+            alignment: None,
+            float_samples: false,
+            audio: audio.to_vec(),
+        }
+    }
+
+    fn request(text: &str) -> [u8; 32] {
+        let mut hasher = StableHasher::new();
+        text.hash(&mut hasher);
+        hasher.digest()
+    }
+
+    #[test]
+    fn test_entries_survive_reopen_and_follow_the_model() {
+        let dir = temp_dir("reopen");
+        let config_path = dir.join("voice.onnx.json");
+        fs::write(&config_path, "{}").unwrap();
+        fs::write(dir.join("voice.onnx"), "model v1").unwrap();
+        let cache_dir = dir.join("cache");
+
+        let cache = DiskCache::open(DiskCacheConfig::new(&cache_dir)).unwrap();
+        let key = cache.key(&request("Hallo"), &config_path).unwrap();
+        assert_ne!(key, cache.key(&request("Hallo!"), &config_path).unwrap());
+        cache.put(&key, &entry(b"RIFF\n\x00audio")).unwrap();
+        drop(cache);
+
+        let cache = DiskCache::open(DiskCacheConfig::new(&cache_dir)).unwrap();
+        let cached = cache.get(&key).unwrap();
+        assert_eq!(cached.audio, b"RIFF\n\x00audio");
+        assert_eq!(cached.sample_rate, 22050);
+
+        // A different model file changes the key
+        fs::write(dir.join("voice.onnx"), "model version 2").unwrap();
+        assert_ne!(cache.key(&request("Hallo"), &config_path).unwrap(), key);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_over_budget() {
+        let dir = temp_dir("evict");
+        // Room for two entries (1000 bytes of audio plus a short header each)
+        let config = DiskCacheConfig { max_bytes: 2500, ..DiskCacheConfig::new(&dir) };
+        let cache = DiskCache::open(config).unwrap();
+        let audio = [0u8; 1000];
+
+        cache.put("a", &entry(&audio)).unwrap();
+        cache.put("b", &entry(&audio)).unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cache.get("a").is_some());
+        cache.put("c", &entry(&audio)).unwrap();
+
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some() && cache.get("c").is_some());
+        assert!(cache.usage().bytes <= 2500);
+        assert!(!dir.join("b.tts").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let dir = temp_dir("ttl");
+        let config = DiskCacheConfig { ttl: Duration::ZERO, ..DiskCacheConfig::new(&dir) };
+        let cache = DiskCache::open(config).unwrap();
+        cache.put("a", &entry(b"audio")).unwrap();
+        assert!(cache.get("a").is_none());
+        assert_eq!((cache.usage().entries, cache.usage().bytes), (0, 0));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_request_digest_follows_spoken_lexicon_words() {
+        use crate::{EngineKind, InputKind, LexiconEntry, LexiconScope, LoudnessTarget, Pronunciation, SynthesisOptions, TtsManager};
+
+        let map = [("de_DE".to_string(), ("/nonexistent/voice.onnx.json".to_string(), None))].into();
+        let tts = TtsManager::new(map).with_engine(EngineKind::Mock);
+        let spoken = TtsManager::prepare_text("Um 12 Uhr.", "de_DE");
+        let source = tts.cache_source("Um 12 Uhr.", spoken, Some("de_DE"), None, InputKind::Text);
+        let digest = || tts.disk_request_digest(&source, &SynthesisOptions::default(), &LoudnessTarget::default());
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let scope = LexiconScope { language: "de_DE".to_string(), voice: None };
+        let add = |word: &str| {
+            let entry = LexiconEntry { word: word.to_string(), pronunciation: Pronunciation::Respelling("x".to_string()) };
+            runtime.block_on(tts.add_lexicon_entry(scope.clone(), entry)).unwrap();
+        };
+
+        // The digits are spoken as "zwölf": only an entry for that word changes the digest
+        let before = digest();
+        add("12");
+        assert_eq!(digest(), before);
+        add("zwölf");
+        assert_ne!(digest(), before);
+    }
+}
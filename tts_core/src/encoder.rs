@@ -0,0 +1,522 @@
+//! Audio output formats.
+//!
+//! Every format is produced by an `AudioEncoder`. WAV, raw PCM, FLAC and G.711 are built in;
+//! Ogg/Opus and MP3 link native codecs and are only available with the `opus` and `mp3`
+//! cargo features.
+
+use std::{fmt, io::Cursor, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::resample::resample;
+
+/// Encodes mono f32 samples in [-1.0, 1.0]
+pub trait AudioEncoder: Send + Sync {
+    fn mime_type(&self) -> &'static str;
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio>;
+}
+
+/// Encoded audio and the sample rate it was encoded at
+/// (fixed-rate codecs such as Opus and G.711 differ from the voice's rate)
+#[derive(Debug, Clone)]
+pub struct EncodedAudio {
+    pub bytes: Vec<u8>,
+    pub sample_rate: u32,
+    pub mime_type: &'static str,
+}
+
+/// Output format selectable per request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioFormat {
+    /// 16-bit PCM WAV
+    #[default]
+    Wav,
+    /// 24-bit PCM WAV
+    Wav24,
+    /// 32-bit float WAV
+    WavF32,
+    /// Headerless 16-bit little-endian PCM
+    Pcm,
+    Flac,
+    /// Opus in an Ogg container (48 kHz)
+    Opus,
+    Mp3,
+    /// 8 kHz G.711 μ-law, headerless
+    Mulaw,
+    /// 8 kHz G.711 A-law, headerless
+    Alaw,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 9] = [
+        AudioFormat::Wav,
+        AudioFormat::Wav24,
+        AudioFormat::WavF32,
+        AudioFormat::Pcm,
+        AudioFormat::Flac,
+        AudioFormat::Opus,
+        AudioFormat::Mp3,
+        AudioFormat::Mulaw,
+        AudioFormat::Alaw,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Wav24 => "wav24",
+            AudioFormat::WavF32 => "wav_f32",
+            AudioFormat::Pcm => "pcm",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Opus => "opus",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Mulaw => "mulaw",
+            AudioFormat::Alaw => "alaw",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav | AudioFormat::Wav24 | AudioFormat::WavF32 => "audio/wav",
+            AudioFormat::Pcm => "audio/pcm",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::Opus => "audio/ogg; codecs=opus",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Mulaw => "audio/basic",
+            AudioFormat::Alaw => "audio/x-alaw-basic",
+        }
+    }
+
+    /// True for formats with more than 16 bits of resolution
+    pub fn is_high_resolution(&self) -> bool {
+        matches!(self, AudioFormat::Wav24 | AudioFormat::WavF32)
+    }
+
+    /// False for codecs whose cargo feature is not enabled in this build
+    pub fn is_available(&self) -> bool {
+        let disabled = (*self == AudioFormat::Opus && !cfg!(feature = "opus"))
+            || (*self == AudioFormat::Mp3 && !cfg!(feature = "mp3"));
+        !disabled
+    }
+
+    /// Formats this build can encode
+    pub fn available() -> Vec<AudioFormat> {
+        Self::ALL.into_iter().filter(AudioFormat::is_available).collect()
+    }
+
+    pub fn encoder(&self) -> anyhow::Result<Box<dyn AudioEncoder>> {
+        Ok(match self {
+            AudioFormat::Wav => Box::new(WavEncoder(WavSampleFormat::Int16)),
+            AudioFormat::Wav24 => Box::new(WavEncoder(WavSampleFormat::Int24)),
+            AudioFormat::WavF32 => Box::new(WavEncoder(WavSampleFormat::Float32)),
+            AudioFormat::Pcm => Box::new(PcmEncoder),
+            AudioFormat::Flac => Box::new(FlacEncoder),
+            AudioFormat::Mulaw => Box::new(G711Encoder::Mulaw),
+            AudioFormat::Alaw => Box::new(G711Encoder::Alaw),
+            #[cfg(feature = "opus")]
+            AudioFormat::Opus => Box::new(OpusEncoder),
+            #[cfg(feature = "mp3")]
+            AudioFormat::Mp3 => Box::new(Mp3Encoder),
+            #[allow(unreachable_patterns)]
+            other => anyhow::bail!(
+                "Audio format '{}' is not enabled in this build (cargo feature \"{}\")",
+                other.name(),
+                other.name()
+            ),
+        })
+    }
+
+    /// Encode with this format's encoder
+    pub fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        self.encoder()?.encode(samples, sample_rate)
+    }
+}
+
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for AudioFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "wav" | "wav16" => AudioFormat::Wav,
+            "wav24" => AudioFormat::Wav24,
+            "wav_f32" | "wav32f" | "wav_float" => AudioFormat::WavF32,
+            "pcm" | "pcm16" | "raw" => AudioFormat::Pcm,
+            "flac" => AudioFormat::Flac,
+            "opus" | "ogg" | "ogg_opus" => AudioFormat::Opus,
+            "mp3" => AudioFormat::Mp3,
+            "mulaw" | "ulaw" | "pcmu" => AudioFormat::Mulaw,
+            "alaw" | "pcma" => AudioFormat::Alaw,
+            _ => anyhow::bail!(
+                "Unknown audio format '{}'. Supported: {}",
+                s,
+                AudioFormat::available().iter().map(|f| f.name()).collect::<Vec<_>>().join(", ")
+            ),
+        })
+    }
+}
+
+pub(crate) fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+#[derive(Debug, Clone, Copy)]
+enum WavSampleFormat {
+    Int16,
+    Int24,
+    Float32,
+}
+
+struct WavEncoder(WavSampleFormat);
+
+impl AudioEncoder for WavEncoder {
+    fn mime_type(&self) -> &'static str {
+        "audio/wav"
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        let (bits_per_sample, sample_format) = match self.0 {
+            WavSampleFormat::Int16 => (16, hound::SampleFormat::Int),
+            WavSampleFormat::Int24 => (24, hound::SampleFormat::Int),
+            WavSampleFormat::Float32 => (32, hound::SampleFormat::Float),
+        };
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        };
+
+        // Pre-allocate buffer: WAV header (44 bytes) + samples
+        let estimated_size = 44 + samples.len() * (bits_per_sample as usize / 8);
+        let mut cursor = Cursor::new(Vec::<u8>::with_capacity(estimated_size));
+
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec)
+                .map_err(|e| anyhow::anyhow!("wav write err: {e}"))?;
+
+            const I24_MAX_F32: f32 = 8_388_607.0;
+            for &s in samples {
+                let result = match self.0 {
+                    WavSampleFormat::Int16 => writer.write_sample(to_i16(s)),
+                    WavSampleFormat::Int24 => writer.write_sample((s.clamp(-1.0, 1.0) * I24_MAX_F32) as i32),
+                    WavSampleFormat::Float32 => writer.write_sample(s.clamp(-1.0, 1.0)),
+                };
+                result.map_err(|e| anyhow::anyhow!("wav sample err: {e}"))?;
+            }
+            // `writer` drops here, which finalizes the WAV header/footer
+        }
+
+        Ok(EncodedAudio {
+            bytes: cursor.into_inner(),
+            sample_rate,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+struct PcmEncoder;
+
+impl AudioEncoder for PcmEncoder {
+    fn mime_type(&self) -> &'static str {
+        "audio/pcm"
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        let bytes = samples
+            .iter()
+            .flat_map(|&s| to_i16(s).to_le_bytes())
+            .collect();
+        Ok(EncodedAudio {
+            bytes,
+            sample_rate,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+struct FlacEncoder;
+
+impl AudioEncoder for FlacEncoder {
+    fn mime_type(&self) -> &'static str {
+        "audio/flac"
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        Ok(EncodedAudio {
+            bytes: crate::flac::encode(samples, sample_rate),
+            sample_rate,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+/// G.711 telephony codecs (always 8 kHz)
+enum G711Encoder {
+    Mulaw,
+    Alaw,
+}
+
+const G711_SAMPLE_RATE: u32 = 8000;
+
+impl AudioEncoder for G711Encoder {
+    fn mime_type(&self) -> &'static str {
+        match self {
+            G711Encoder::Mulaw => "audio/basic",
+            G711Encoder::Alaw => "audio/x-alaw-basic",
+        }
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        let samples = resample(samples, sample_rate, G711_SAMPLE_RATE);
+        let bytes = samples
+            .iter()
+            .map(|&s| match self {
+                G711Encoder::Mulaw => linear_to_mulaw(to_i16(s)),
+                G711Encoder::Alaw => linear_to_alaw(to_i16(s)),
+            })
+            .collect();
+        Ok(EncodedAudio {
+            bytes,
+            sample_rate: G711_SAMPLE_RATE,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+/// ITU-T G.711 μ-law compression of one 16-bit sample
+fn linear_to_mulaw(sample: i16) -> u8 {
+    const BIAS: i32 = 0x84;
+    const CLIP: i32 = 32635;
+
+    let mut value = sample as i32;
+    let sign = if value < 0 { 0x80 } else { 0 };
+    if value < 0 {
+        value = -value;
+    }
+    value = value.min(CLIP) + BIAS;
+
+    let exponent = (7 - (value >> 7).leading_zeros().saturating_sub(24)).min(7) as i32;
+    let mantissa = (value >> (exponent + 3)) & 0x0F;
+    !(sign | (exponent << 4) | mantissa) as u8
+}
+
+/// ITU-T G.711 A-law compression of one 16-bit sample
+fn linear_to_alaw(sample: i16) -> u8 {
+    let mut value = sample as i32 >> 3; // 13-bit magnitude range
+    let sign = if value >= 0 { 0x80 } else { 0 };
+    if value < 0 {
+        value = -value - 1;
+    }
+
+    let encoded = if value < 32 {
+        value >> 1
+    } else {
+        let exponent = 31 - (value as u32).leading_zeros() as i32 - 4; // 1..=7
+        let mantissa = (value >> exponent) & 0x0F;
+        ((exponent.min(7)) << 4) | mantissa
+    };
+    ((sign | encoded) ^ 0x55) as u8
+}
+
+#[cfg(feature = "opus")]
+struct OpusEncoder;
+
+#[cfg(feature = "opus")]
+impl AudioEncoder for OpusEncoder {
+    fn mime_type(&self) -> &'static str {
+        "audio/ogg; codecs=opus"
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        use audiopus::{coder::Encoder, Application, Bitrate, Channels, SampleRate};
+        use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+
+        const OPUS_RATE: u32 = 48_000;
+        const FRAME_SIZE: usize = 960; // 20 ms at 48 kHz
+        const SERIAL: u32 = 0x7474_7331;
+
+        let mut encoder = Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Voip)
+            .map_err(|e| anyhow::anyhow!("opus encoder err: {e}"))?;
+        encoder
+            .set_bitrate(Bitrate::BitsPerSecond(32_000))
+            .map_err(|e| anyhow::anyhow!("opus bitrate err: {e}"))?;
+        let pre_skip = encoder.lookahead().map_err(|e| anyhow::anyhow!("opus lookahead err: {e}"))? as u16;
+
+        let mut pcm = resample(samples, sample_rate, OPUS_RATE);
+        let total = pcm.len() as u64;
+        pcm.resize(pcm.len().div_ceil(FRAME_SIZE).max(1) * FRAME_SIZE, 0.0);
+
+        let mut writer = PacketWriter::new(Cursor::new(Vec::new()));
+
+        // OpusHead and OpusTags headers (RFC 7845), each on its own page
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1); // version
+        head.push(1); // channels
+        head.extend_from_slice(&pre_skip.to_le_bytes());
+        head.extend_from_slice(&sample_rate.to_le_bytes()); // original input rate
+        head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+        head.push(0); // mapping family
+        writer.write_packet(head.into_boxed_slice(), SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+        let vendor = b"tts_core";
+        let mut tags = Vec::new();
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        tags.extend_from_slice(vendor);
+        tags.extend_from_slice(&0u32.to_le_bytes()); // no user comments
+        writer.write_packet(tags.into_boxed_slice(), SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+        let frames = pcm.len() / FRAME_SIZE;
+        let mut packet = vec![0u8; 4000];
+        for (i, frame) in pcm.chunks(FRAME_SIZE).enumerate() {
+            let len = encoder
+                .encode_float(frame, &mut packet)
+                .map_err(|e| anyhow::anyhow!("opus encode err: {e}"))?;
+            let last = i + 1 == frames;
+            // Granule position counts 48 kHz samples including the pre-skip
+            let granule = if last {
+                total + pre_skip as u64
+            } else {
+                ((i + 1) * FRAME_SIZE) as u64 + pre_skip as u64
+            };
+            let end = if last { PacketWriteEndInfo::EndStream } else { PacketWriteEndInfo::NormalPacket };
+            writer.write_packet(packet[..len].into(), SERIAL, end, granule)?;
+        }
+
+        Ok(EncodedAudio {
+            bytes: writer.into_inner().into_inner(),
+            sample_rate: OPUS_RATE,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+#[cfg(feature = "mp3")]
+struct Mp3Encoder;
+
+#[cfg(feature = "mp3")]
+impl AudioEncoder for Mp3Encoder {
+    fn mime_type(&self) -> &'static str {
+        "audio/mpeg"
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32) -> anyhow::Result<EncodedAudio> {
+        use mp3lame_encoder::{Bitrate, Builder, FlushNoGap, MonoPcm, Quality};
+
+        let mut builder = Builder::new().ok_or_else(|| anyhow::anyhow!("mp3 encoder init failed"))?;
+        builder
+            .set_num_channels(1)
+            .map_err(|e| anyhow::anyhow!("mp3 channels err: {e:?}"))?;
+        builder
+            .set_sample_rate(sample_rate)
+            .map_err(|e| anyhow::anyhow!("mp3 sample rate err: {e:?}"))?;
+        builder
+            .set_brate(Bitrate::Kbps64)
+            .map_err(|e| anyhow::anyhow!("mp3 bitrate err: {e:?}"))?;
+        builder
+            .set_quality(Quality::Good)
+            .map_err(|e| anyhow::anyhow!("mp3 quality err: {e:?}"))?;
+        let mut encoder = builder
+            .build()
+            .map_err(|e| anyhow::anyhow!("mp3 encoder build err: {e:?}"))?;
+
+        let pcm: Vec<i16> = samples.iter().map(|&s| to_i16(s)).collect();
+        let mut out = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(pcm.len()));
+        encoder
+            .encode_to_vec(MonoPcm(&pcm), &mut out)
+            .map_err(|e| anyhow::anyhow!("mp3 encode err: {e:?}"))?;
+        encoder
+            .flush_to_vec::<FlushNoGap>(&mut out)
+            .map_err(|e| anyhow::anyhow!("mp3 flush err: {e:?}"))?;
+
+        Ok(EncodedAudio {
+            bytes: out,
+            sample_rate,
+            mime_type: self.mime_type(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_names_round_trip() {
+        for format in AudioFormat::ALL {
+            assert_eq!(format.name().parse::<AudioFormat>().unwrap(), format);
+        }
+        assert!("aiff".parse::<AudioFormat>().is_err());
+    }
+
+    #[test]
+    fn test_wav_variants() {
+        let samples = vec![0.0, 0.5, -0.5, 1.0];
+        for (format, bytes_per_sample) in [(AudioFormat::Wav, 2), (AudioFormat::Wav24, 3), (AudioFormat::WavF32, 4)] {
+            let encoded = format.encode(&samples, 22050).unwrap();
+            assert_eq!(&encoded.bytes[..4], b"RIFF");
+            assert!(encoded.bytes.len() >= 44 + samples.len() * bytes_per_sample);
+            assert_eq!(encoded.mime_type, "audio/wav");
+        }
+    }
+
+    #[test]
+    fn test_pcm_is_little_endian_i16() {
+        let encoded = AudioFormat::Pcm.encode(&[1.0, -1.0], 16000).unwrap();
+        assert_eq!(encoded.bytes, vec![0xFF, 0x7F, 0x01, 0x80]);
+    }
+
+    #[test]
+    fn test_g711_reference_values() {
+        assert_eq!(linear_to_mulaw(0), 0xFF);
+        assert_eq!(linear_to_mulaw(i16::MAX), 0x80);
+        assert_eq!(linear_to_mulaw(i16::MIN + 1), 0x00);
+        assert_eq!(linear_to_alaw(0), 0xD5);
+        assert_eq!(linear_to_alaw(i16::MAX), 0xAA);
+        assert_eq!(linear_to_alaw(i16::MIN), 0x2A);
+    }
+
+    #[cfg(feature = "opus")]
+    #[test]
+    fn test_opus_is_ogg_at_48khz() {
+        assert!(AudioFormat::available().contains(&AudioFormat::Opus));
+        let encoded = AudioFormat::Opus.encode(&vec![0.1; 22050], 22050).unwrap();
+        assert_eq!(&encoded.bytes[..4], b"OggS");
+        assert!(encoded.bytes.windows(8).any(|w| w == b"OpusHead"));
+        assert_eq!(encoded.sample_rate, 48_000);
+        assert_eq!(encoded.mime_type, "audio/ogg; codecs=opus");
+    }
+
+    #[cfg(feature = "mp3")]
+    #[test]
+    fn test_mp3_keeps_sample_rate() {
+        assert!(AudioFormat::available().contains(&AudioFormat::Mp3));
+        let encoded = AudioFormat::Mp3.encode(&vec![0.1; 22050], 22050).unwrap();
+        assert_eq!(encoded.sample_rate, 22050);
+        assert_eq!(encoded.mime_type, "audio/mpeg");
+    }
+
+    #[cfg(not(all(feature = "opus", feature = "mp3")))]
+    #[test]
+    fn test_disabled_codecs_are_not_offered() {
+        let error = "aiff".parse::<AudioFormat>().unwrap_err().to_string();
+        for format in AudioFormat::ALL.into_iter().filter(|f| !f.is_available()) {
+            assert!(format.encode(&[0.0], 16000).is_err());
+            assert!(!error.contains(format.name()));
+        }
+    }
+
+    #[test]
+    fn test_g711_resamples_to_8khz() {
+        let encoded = AudioFormat::Mulaw.encode(&vec![0.0; 22050], 22050).unwrap();
+        assert_eq!(encoded.sample_rate, 8000);
+        assert_eq!(encoded.bytes.len(), 8000);
+    }
+}
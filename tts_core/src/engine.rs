@@ -0,0 +1,207 @@
+//! Speech synthesis backends.
+//!
+//! `TtsManager` caches one engine per voice config and drives it through `TtsEngine`; Piper is
+//! the production backend, the mock engine (see `mock_engine`) lets the server run without
+//! model files.
+
+use std::{
+    fmt,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use piper_rs::synth::{PiperSpeechStreamParallel, PiperSpeechSynthesizer};
+use piper_rs::PiperModel;
+use serde::{Deserialize, Serialize};
+
+use crate::{MockEngine, Prosody, TtsManager};
+
+/// A loaded voice that turns text or phonemes into samples (mono, in [-1.0, 1.0])
+pub trait TtsEngine: Send + Sync {
+    fn sample_rate(&self) -> u32;
+
+    fn num_speakers(&self) -> u32;
+
+    /// Phoneme duration multiplier the model uses when a request does not set one
+    fn length_scale(&self) -> f32 {
+        1.0
+    }
+
+    /// Synthesize text. `speaker` is only set for multi-speaker voices.
+    fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>>;
+
+    /// Phonemes of a text, one string per sentence
+    fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>>;
+
+    /// Speak phonemes (as returned by `phonemize`) as one sentence
+    fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>>;
+}
+
+/// Engine a voice runs on: `"engine"` of a map.json entry, or the manager default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineKind {
+    #[default]
+    Piper,
+    /// Deterministic tones, no model files needed (tests and CI)
+    Mock,
+}
+
+impl EngineKind {
+    /// Load the engine for a voice config
+    pub(crate) fn load(self, cfg_path: &Path) -> anyhow::Result<Arc<dyn TtsEngine>> {
+        Ok(match self {
+            Self::Piper => Arc::new(PiperEngine::load(cfg_path)?),
+            Self::Mock => Arc::new(MockEngine::for_config(cfg_path)),
+        })
+    }
+}
+
+impl FromStr for EngineKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "piper" => Ok(Self::Piper),
+            "mock" => Ok(Self::Mock),
+            other => Err(anyhow::anyhow!("Unknown TTS engine '{}' (expected 'piper' or 'mock')", other)),
+        }
+    }
+}
+
+impl fmt::Display for EngineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Piper => "piper",
+            Self::Mock => "mock",
+        })
+    }
+}
+
+/// Shared handle to a loaded Piper model (used for speaker selection)
+type ModelHandle = Arc<dyn PiperModel + Send + Sync>;
+
+const POISONED: &str = "Synthesizer lock poisoned - this indicates a previous panic. Please restart the server.";
+
+/// Piper ONNX voice
+pub(crate) struct PiperEngine {
+    synth: RwLock<PiperSpeechSynthesizer>, // RwLock so that requests with model defaults run in parallel
+    model: ModelHandle, // Same model the synthesizer wraps, kept to switch speakers
+    num_speakers: u32,
+    sample_rate: u32,
+    length_scale: f32,
+}
+
+impl PiperEngine {
+    pub(crate) fn load(cfg_path: &Path) -> anyhow::Result<Self> {
+        let sample_rate = TtsManager::read_sample_rate(cfg_path)?;
+        let num_speakers = TtsManager::read_num_speakers(cfg_path)?;
+        let length_scale = TtsManager::read_length_scale(cfg_path)?;
+        let model = piper_rs::from_config_path(cfg_path)
+            .map_err(|e| anyhow::anyhow!("piper load error: {e}"))?;
+        let synth = PiperSpeechSynthesizer::new(model.clone())?;
+        Ok(Self { synth: RwLock::new(synth), model, num_speakers, sample_rate, length_scale })
+    }
+
+    /// Run `f` with the model switched to `speaker` and `prosody`
+    fn with_settings<F>(&self, speaker: Option<i64>, prosody: Prosody, f: F) -> anyhow::Result<Vec<f32>>
+    where
+        F: FnOnce(&PiperSpeechSynthesizer) -> anyhow::Result<Vec<f32>>,
+    {
+        if speaker.is_none() && prosody.is_default() {
+            // Model defaults: concurrent reads are fine
+            let synth = self.synth.read().map_err(|_| anyhow::anyhow!(POISONED))?;
+            return f(&synth);
+        }
+
+        // Speaker and inference settings are model state, so hold the write lock until they are restored
+        let synth = self.synth.write().map_err(|_| anyhow::anyhow!(POISONED))?;
+        let previous = self
+            .model
+            .get_fallback_synthesis_config()
+            .map_err(|e| anyhow::anyhow!("piper config error: {e}"))?;
+
+        if !prosody.is_default() {
+            let mut config = previous
+                .downcast_ref::<piper_rs::SynthesisConfig>()
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("piper config error: unsupported synthesis config type"))?;
+            if let Some(length_scale) = prosody.length_scale {
+                config.length_scale = length_scale;
+            }
+            if let Some(noise_scale) = prosody.noise_scale {
+                config.noise_scale = noise_scale;
+            }
+            if let Some(noise_w) = prosody.noise_w {
+                config.noise_w = noise_w;
+            }
+            self.model
+                .set_fallback_synthesis_config(&config)
+                .map_err(|e| anyhow::anyhow!("piper config error: {e}"))?;
+        }
+        if let Some(sid) = speaker {
+            if let Some(e) = self.model.set_speaker(sid) {
+                let _ = self.model.set_fallback_synthesis_config(previous.as_ref());
+                return Err(anyhow::anyhow!("piper speaker error: {e}"));
+            }
+        }
+
+        let result = f(&synth);
+
+        self.model
+            .set_fallback_synthesis_config(previous.as_ref())
+            .map_err(|e| anyhow::anyhow!("piper config error: {e}"))?;
+        result
+    }
+}
+
+impl TtsEngine for PiperEngine {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn num_speakers(&self) -> u32 {
+        self.num_speakers
+    }
+
+    fn length_scale(&self) -> f32 {
+        self.length_scale
+    }
+
+    fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+        self.with_settings(speaker, prosody, |synth| {
+            let iter: PiperSpeechStreamParallel = synth
+                .synthesize_parallel(text.to_string(), None)
+                .map_err(|e| anyhow::anyhow!("piper synth error: {e}"))?;
+
+            let mut samples: Vec<f32> = Vec::new();
+            for part in iter {
+                samples.extend(
+                    part.map_err(|e| anyhow::anyhow!("chunk error: {e}"))?
+                        .into_vec(),
+                );
+            }
+            Ok(samples)
+        })
+    }
+
+    fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>> {
+        let _synth = self.synth.read().map_err(|_| anyhow::anyhow!(POISONED))?;
+        let phonemes = self
+            .model
+            .phonemize_text(text)
+            .map_err(|e| anyhow::anyhow!("piper phonemize error: {e}"))?;
+        Ok(phonemes.sentences().to_vec())
+    }
+
+    fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+        self.with_settings(speaker, prosody, |_| {
+            let audio = self
+                .model
+                .speak_one_sentence(phonemes.to_string())
+                .map_err(|e| anyhow::anyhow!("piper synth error: {e}"))?;
+            Ok(audio.samples.into_vec())
+        })
+    }
+}
@@ -0,0 +1,165 @@
+//! Expressive speech: per-sentence prosody hints from a pluggable, per-language
+//! emotion detector.
+//!
+//! A detector maps a sentence to a `ProsodyHint` (rate and pitch multipliers). In expressive
+//! mode the rate is applied through Piper's length scale and the pitch through
+//! `pitch::pitch_shift` on the synthesized samples.
+
+use std::{collections::HashMap, sync::Arc};
+
+/// Prosody multipliers for one sentence (1.0 = neutral)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProsodyHint {
+    pub rate: f32,
+    pub pitch: f32,
+}
+
+impl ProsodyHint {
+    pub const NEUTRAL: ProsodyHint = ProsodyHint { rate: 1.0, pitch: 1.0 };
+
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::NEUTRAL
+    }
+}
+
+/// Detects the emotional tone of a sentence
+pub trait EmotionDetector: Send + Sync {
+    fn detect(&self, text: &str) -> ProsodyHint;
+}
+
+/// Keyword and punctuation based detector
+/// (exclamations and excitement words, questions, concern words)
+#[derive(Debug, Clone, Default)]
+pub struct KeywordEmotionDetector {
+    excitement: Vec<String>,
+    concern: Vec<String>,
+}
+
+impl KeywordEmotionDetector {
+    pub fn new<S: AsRef<str>>(excitement: &[S], concern: &[S]) -> Self {
+        Self {
+            excitement: excitement.iter().map(|w| w.as_ref().to_lowercase()).collect(),
+            concern: concern.iter().map(|w| w.as_ref().to_lowercase()).collect(),
+        }
+    }
+
+    /// Built-in keyword list for a language code (`en`, `de`, `fr`, `es`, `it`, `nl`, `uk`)
+    pub fn for_language(lang: &str) -> Option<Self> {
+        let (excitement, concern): (&[&str], &[&str]) = match lang {
+            "en" => (
+                &["amazing", "wonderful", "fantastic", "incredible", "excellent", "great"],
+                &["sorry", "unfortunately", "problem", "issue", "difficult", "challenge"],
+            ),
+            "de" => (
+                &["toll", "wunderbar", "fantastisch", "großartig", "unglaublich", "ausgezeichnet", "super"],
+                &["leider", "tut mir leid", "entschuldigung", "problem", "schwierig", "fehler"],
+            ),
+            "fr" => (
+                &["génial", "magnifique", "fantastique", "incroyable", "excellent", "merveilleux", "super"],
+                &["désolé", "désolée", "malheureusement", "problème", "difficile", "erreur"],
+            ),
+            "es" => (
+                &["increíble", "maravilloso", "fantástico", "excelente", "genial", "estupendo"],
+                &["lo siento", "desafortunadamente", "lamentablemente", "problema", "difícil", "error"],
+            ),
+            "it" => (
+                &["fantastico", "meraviglioso", "incredibile", "eccellente", "ottimo", "splendido"],
+                &["mi dispiace", "purtroppo", "problema", "difficile", "errore"],
+            ),
+            "nl" => (
+                &["geweldig", "fantastisch", "prachtig", "uitstekend", "ongelooflijk", "super"],
+                &["sorry", "helaas", "probleem", "moeilijk", "fout"],
+            ),
+            "uk" => (
+                &["чудово", "дивовижно", "фантастично", "неймовірно", "відмінно"],
+                &["вибачте", "на жаль", "проблема", "складно", "помилка"],
+            ),
+            _ => return None,
+        };
+        Some(Self::new(excitement, concern))
+    }
+}
+
+impl EmotionDetector for KeywordEmotionDetector {
+    fn detect(&self, text: &str) -> ProsodyHint {
+        let text_lower = text.to_lowercase();
+
+        // Excitement indicators (exclamation marks, exciting words)
+        let has_excitement = text.contains('!')
+            || self.excitement.iter().any(|w| text_lower.contains(w.as_str()));
+
+        // Question indicators (questions typically have rising intonation)
+        let is_question = text.trim_end().ends_with('?');
+
+        // Sadness/concern indicators
+        let has_concern = self.concern.iter().any(|w| text_lower.contains(w.as_str()));
+
+        if has_excitement {
+            // Slightly faster, higher pitch for excitement
+            ProsodyHint { rate: 1.05, pitch: 1.1 }
+        } else if is_question {
+            // Normal speed, slightly higher pitch for questions (rising intonation)
+            ProsodyHint { rate: 1.0, pitch: 1.05 }
+        } else if has_concern {
+            // Slightly slower, lower pitch for concern
+            ProsodyHint { rate: 0.95, pitch: 0.95 }
+        } else {
+            ProsodyHint::NEUTRAL
+        }
+    }
+}
+
+/// Per-language detector registry.
+/// Lookup tries the full key (`de_DE`), then the language code (`de`), then a
+/// punctuation-only fallback.
+#[derive(Clone)]
+pub struct EmotionDetectors {
+    by_language: HashMap<String, Arc<dyn EmotionDetector>>,
+    fallback: Arc<dyn EmotionDetector>,
+}
+
+impl EmotionDetectors {
+    /// Registry with the built-in keyword detectors
+    pub fn builtin() -> Self {
+        let mut by_language: HashMap<String, Arc<dyn EmotionDetector>> = HashMap::new();
+        for lang in ["en", "de", "fr", "es", "it", "nl", "uk"] {
+            if let Some(detector) = KeywordEmotionDetector::for_language(lang) {
+                by_language.insert(lang.to_string(), Arc::new(detector));
+            }
+        }
+        Self {
+            by_language,
+            fallback: Arc::new(KeywordEmotionDetector::default()),
+        }
+    }
+
+    /// Register (or replace) the detector for a language key (`de_DE`) or code (`de`)
+    pub fn register(&mut self, lang: &str, detector: Arc<dyn EmotionDetector>) {
+        self.by_language.insert(lang.to_string(), detector);
+    }
+
+    pub fn for_language(&self, lang: &str) -> Arc<dyn EmotionDetector> {
+        let code = lang.split('_').next().unwrap_or(lang);
+        self.by_language
+            .get(lang)
+            .or_else(|| self.by_language.get(code))
+            .cloned()
+            .unwrap_or_else(|| self.fallback.clone())
+    }
+}
+
+impl Default for EmotionDetectors {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl std::fmt::Debug for EmotionDetectors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut languages: Vec<&String> = self.by_language.keys().collect();
+        languages.sort();
+        f.debug_struct("EmotionDetectors")
+            .field("languages", &languages)
+            .finish()
+    }
+}
@@ -0,0 +1,64 @@
+//! SHA-256 of voice files, remembered per path with the size and modification time the file
+//! had. Shared by the disk cache keys and the voice checks, so a model is hashed once and only
+//! again after it changes. Files are hashed without holding the lock: two callers may hash the
+//! same file at once, but nobody waits on another file's hash.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, OnceLock},
+    time::SystemTime,
+};
+
+use sha2::{Digest, Sha256};
+
+/// SHA-256 of the files hashed so far, with the size and modification time they had
+type Hashes = HashMap<PathBuf, ((u64, Option<SystemTime>), [u8; 32])>;
+static HASHES: OnceLock<Mutex<Hashes>> = OnceLock::new();
+
+fn hashes() -> MutexGuard<'static, Hashes> {
+    HASHES.get_or_init(Default::default).lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// SHA-256 of a file; hashed again only when its size or modification time changed
+pub(crate) fn sha256(path: &Path) -> io::Result<[u8; 32]> {
+    let metadata = fs::metadata(path)?;
+    let stamp = (metadata.len(), metadata.modified().ok());
+    if let Some((known, digest)) = hashes().get(path) {
+        if *known == stamp {
+            return Ok(*digest);
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    let digest: [u8; 32] = hasher.finalize().into();
+    hashes().insert(path.to_path_buf(), (stamp, digest));
+    Ok(digest)
+}
+
+/// `sha256` as lowercase hex
+pub(crate) fn sha256_hex(path: &Path) -> io::Result<String> {
+    Ok(sha256(path)?.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rehashes_changed_files() {
+        let path = std::env::temp_dir().join(format!("tts-file-hash-{}", std::process::id()));
+        fs::write(&path, "weights").unwrap();
+        // SHA-256 of "weights"
+        let sha256 = "9a129038d9a00aed0cf6a7ea059ca50a813449061ab87848cf1a13eafdf33b2c";
+        assert_eq!(sha256_hex(&path).unwrap(), sha256);
+        assert!(hashes().contains_key(&path));
+
+        fs::write(&path, "new weights").unwrap();
+        assert_ne!(sha256_hex(&path).unwrap(), sha256);
+        let _ = fs::remove_file(&path);
+        assert!(sha256_hex(&path).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Minimal FLAC encoder (mono, 16-bit).
+//!
+//! Each block is coded as a constant subframe (silence) or with the best of FLAC's fixed
+//! polynomial predictors (orders 0-4) and partitioned Rice-coded residuals. No LPC search,
+//! so the output is a little larger than libFLAC's, but it decodes with any FLAC reader.
+
+const BLOCK_SIZE: usize = 4096;
+const BITS_PER_SAMPLE: u32 = 16;
+const MAX_PARTITION_ORDER: u32 = 6;
+/// Rice parameter 15 is the escape code in 4-bit parameter mode
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Encode mono samples in [-1.0, 1.0] as a FLAC stream
+pub(crate) fn encode(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let pcm: Vec<i32> = samples
+        .iter()
+        .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i32)
+        .collect();
+
+    let mut out = Vec::with_capacity(pcm.len() + 64);
+    out.extend_from_slice(b"fLaC");
+    write_stream_info(&mut out, &pcm, sample_rate);
+
+    for (frame_number, block) in pcm.chunks(BLOCK_SIZE).enumerate() {
+        write_frame(&mut out, block, frame_number as u64);
+    }
+    out
+}
+
+/// STREAMINFO metadata block (marked as the last metadata block)
+fn write_stream_info(out: &mut Vec<u8>, pcm: &[i32], sample_rate: u32) {
+    // Fixed block size stream; only the last block may be shorter
+    let block_size = pcm.len().clamp(16, BLOCK_SIZE) as u16;
+
+    let mut w = BitWriter::default();
+    w.write(block_size as u64, 16);
+    w.write(block_size as u64, 16);
+    w.write(0, 24); // min frame size unknown
+    w.write(0, 24); // max frame size unknown
+    w.write(sample_rate as u64, 20);
+    w.write(0, 3); // channels - 1
+    w.write((BITS_PER_SAMPLE - 1) as u64, 5);
+    w.write(pcm.len() as u64, 36);
+    w.write(0, 64); // MD5 signature unknown
+    w.write(0, 64);
+    let body = w.finish();
+
+    out.push(0x80); // last-metadata-block flag | STREAMINFO
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+    out.extend_from_slice(&body);
+}
+
+fn write_frame(out: &mut Vec<u8>, block: &[i32], frame_number: u64) {
+    let mut w = BitWriter::default();
+
+    // Frame header
+    w.write(0x3FFE, 14); // sync code
+    w.write(0, 1); // reserved
+    w.write(0, 1); // fixed block size stream
+    let block_size_code = if block.len() == BLOCK_SIZE { 0b1100 } else { 0b0111 };
+    w.write(block_size_code, 4);
+    w.write(0b0000, 4); // sample rate from STREAMINFO
+    w.write(0b0000, 4); // mono
+    w.write(0b100, 3); // 16 bits per sample
+    w.write(0, 1); // reserved
+    write_utf8_number(&mut w, frame_number);
+    if block_size_code == 0b0111 {
+        w.write((block.len() - 1) as u64, 16);
+    }
+    let header_crc = crc8(&w.bytes_so_far());
+    w.write(header_crc as u64, 8);
+
+    write_subframe(&mut w, block);
+
+    let mut frame = w.finish();
+    let crc = crc16(&frame);
+    frame.extend_from_slice(&crc.to_be_bytes());
+    out.extend_from_slice(&frame);
+}
+
+fn write_subframe(w: &mut BitWriter, block: &[i32]) {
+    if block.iter().all(|&s| s == block[0]) {
+        w.write(0, 1);
+        w.write(0b000000, 6); // constant
+        w.write(0, 1); // no wasted bits
+        w.write_signed(block[0] as i64, BITS_PER_SAMPLE);
+        return;
+    }
+
+    // Pick the fixed predictor order with the smallest total residual magnitude
+    let max_order = block.len().saturating_sub(1).min(4);
+    let (order, residual) = (0..=max_order)
+        .map(|order| (order, fixed_residual(block, order)))
+        .min_by_key(|(_, residual)| residual.iter().map(|r| r.unsigned_abs()).sum::<u64>())
+        .unwrap_or((0, fixed_residual(block, 0)));
+
+    w.write(0, 1);
+    w.write(0b001000 | order as u64, 6); // fixed predictor
+    w.write(0, 1); // no wasted bits
+    for &warmup in &block[..order] {
+        w.write_signed(warmup as i64, BITS_PER_SAMPLE);
+    }
+    write_residual(w, &residual, block.len(), order);
+}
+
+/// Residual of FLAC's fixed polynomial predictor of the given order
+fn fixed_residual(block: &[i32], order: usize) -> Vec<i64> {
+    let s = |i: usize| block[i] as i64;
+    (order..block.len())
+        .map(|i| match order {
+            0 => s(i),
+            1 => s(i) - s(i - 1),
+            2 => s(i) - 2 * s(i - 1) + s(i - 2),
+            3 => s(i) - 3 * s(i - 1) + 3 * s(i - 2) - s(i - 3),
+            _ => s(i) - 4 * s(i - 1) + 6 * s(i - 2) - 4 * s(i - 3) + s(i - 4),
+        })
+        .collect()
+}
+
+fn write_residual(w: &mut BitWriter, residual: &[i64], block_size: usize, order: usize) {
+    let zigzag: Vec<u64> = residual
+        .iter()
+        .map(|&r| ((r << 1) ^ (r >> 63)) as u64)
+        .collect();
+
+    // Try every valid partition order and keep the cheapest
+    let mut best: Option<(u32, Vec<u32>, u64)> = None;
+    for partition_order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1usize << partition_order;
+        if !block_size.is_multiple_of(partitions) || block_size / partitions <= order {
+            break;
+        }
+        let mut params = Vec::with_capacity(partitions);
+        let mut bits = 0u64;
+        for (start, end) in partition_bounds(block_size, partition_order, order) {
+            let (param, cost) = best_rice_param(&zigzag[start..end]);
+            params.push(param);
+            bits += cost + 4;
+        }
+        if best.as_ref().is_none_or(|(_, _, b)| bits < *b) {
+            best = Some((partition_order, params, bits));
+        }
+    }
+    let (partition_order, params, _) = best.unwrap_or_else(|| (0, vec![best_rice_param(&zigzag).0], 0));
+
+    w.write(0b00, 2); // Rice coding with 4-bit parameters
+    w.write(partition_order as u64, 4);
+    for ((start, end), param) in partition_bounds(block_size, partition_order, order).zip(params) {
+        w.write(param as u64, 4);
+        for &value in &zigzag[start..end] {
+            w.write_unary(value >> param);
+            w.write(value & ((1 << param) - 1), param);
+        }
+    }
+}
+
+/// Residual index ranges for each partition (the first one is shortened by the warm-up)
+fn partition_bounds(block_size: usize, partition_order: u32, order: usize) -> impl Iterator<Item = (usize, usize)> {
+    let partition_len = block_size >> partition_order;
+    (0..1usize << partition_order).map(move |p| {
+        let start = if p == 0 { 0 } else { p * partition_len - order };
+        (start, (p + 1) * partition_len - order)
+    })
+}
+
+/// Rice parameter with the smallest encoded size, and that size in bits
+fn best_rice_param(values: &[u64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|k| {
+            let bits: u64 = values.iter().map(|&v| (v >> k) + 1 + k as u64).sum();
+            (k, bits)
+        })
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, 0))
+}
+
+/// Frame numbers use the UTF-8 style variable-length encoding
+fn write_utf8_number(w: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        w.write(value, 8);
+        return;
+    }
+    let mut continuation = 1;
+    while value >> (6 * continuation) >= (1 << (6 - continuation)) {
+        continuation += 1;
+    }
+    let lead_marker = (0xFF00u64 >> (continuation + 1)) & 0xFF;
+    w.write(lead_marker | (value >> (6 * continuation)), 8);
+    for i in (0..continuation).rev() {
+        w.write(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Write the low `n` bits of `value`, MSB first
+    fn write(&mut self, value: u64, n: u32) {
+        for i in (0..n).rev() {
+            self.acc = (self.acc << 1) | ((value >> i) & 1);
+            self.bits += 1;
+            if self.bits == 8 {
+                self.bytes.push(self.acc as u8);
+                self.acc = 0;
+                self.bits = 0;
+            }
+        }
+    }
+
+    fn write_signed(&mut self, value: i64, n: u32) {
+        self.write(value as u64 & ((1u64 << n) - 1), n);
+    }
+
+    fn write_unary(&mut self, zeros: u64) {
+        for _ in 0..zeros {
+            self.write(0, 1);
+        }
+        self.write(1, 1);
+    }
+
+    /// Bytes written so far (only valid on a byte boundary)
+    fn bytes_so_far(&self) -> Vec<u8> {
+        debug_assert_eq!(self.bits, 0);
+        self.bytes.clone()
+    }
+
+    /// Zero-pad to a byte boundary and return the buffer
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_header() {
+        let encoded = encode(&vec![0.0; 1000], 22050);
+        assert_eq!(&encoded[..4], b"fLaC");
+        assert_eq!(encoded[4], 0x80);
+        assert_eq!(&encoded[5..8], &[0, 0, 34]);
+        // Sample rate is the first 20 bits after the block and frame sizes
+        let rate = ((encoded[18] as u32) << 12) | ((encoded[19] as u32) << 4) | ((encoded[20] as u32) >> 4);
+        assert_eq!(rate, 22050);
+    }
+
+    #[test]
+    fn test_silence_uses_constant_subframes() {
+        let encoded = encode(&vec![0.0; BLOCK_SIZE * 3], 16000);
+        // 42 bytes of header plus three tiny frames
+        assert!(encoded.len() < 42 + 3 * 16, "got {} bytes", encoded.len());
+    }
+
+    #[test]
+    fn test_tone_compresses() {
+        let samples: Vec<f32> = (0..22050)
+            .map(|i| (2.0 * std::f32::consts::PI * 220.0 * i as f32 / 22050.0).sin() * 0.5)
+            .collect();
+        let encoded = encode(&samples, 22050);
+        // At most half the size of raw 16-bit PCM
+        assert!(encoded.len() < samples.len(), "got {} bytes", encoded.len());
+    }
+
+    #[test]
+    fn test_crcs() {
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc16(b"123456789"), 0xFEE8);
+    }
+}
@@ -0,0 +1,232 @@
+//! Joining synthesized chunks.
+//!
+//! Piper pads every sentence with silence and does not always end on a zero crossing, so
+//! chunks glued together with plain silence click and their pauses come out longer than
+//! intended. `ChunkJoiner` trims the padding, fades the chunk edges that border a pause and
+//! crossfades chunks that follow each other without one. Pause lengths and the fade shape are
+//! per language and voice (`"pauses"` in map.json).
+
+use serde::{Deserialize, Serialize};
+
+/// Samples below this level at the chunk edges count as padding (about -46 dBFS)
+const TRIM_THRESHOLD: f32 = 0.005;
+/// Padding kept before the first and after the last audible sample
+const TRIM_KEEP_MS: u32 = 10;
+const DEFAULT_FADE_MS: u32 = 10;
+const DEFAULT_CROSSFADE_MS: u32 = 20;
+
+/// Gain curve of fades and crossfades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseShape {
+    /// No fades: chunks and silence are butted together (the old behavior)
+    Cut,
+    Linear,
+    /// Quarter sine/cosine: constant power through a crossfade
+    #[default]
+    EqualPower,
+}
+
+impl PauseShape {
+    /// Fade-in gain at `t` in [0, 1]; the fade-out gain is the same curve at `1 - t`
+    fn gain(self, t: f32) -> f32 {
+        match self {
+            Self::Cut => 1.0,
+            Self::Linear => t,
+            Self::EqualPower => (t * std::f32::consts::FRAC_PI_2).sin(),
+        }
+    }
+}
+
+/// Pauses between chunks and how chunks are joined (per voice or language in map.json).
+/// `None` fields fall back to the language, then to the built-in defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PauseConfig {
+    /// After `.`, `!` and `?`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentence_ms: Option<u32>,
+    /// After `;` and `:`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clause_ms: Option<u32>,
+    /// After `,`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comma_ms: Option<u32>,
+    /// After chunks without closing punctuation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_ms: Option<u32>,
+    /// Fade at chunk edges that border a pause
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fade_ms: Option<u32>,
+    /// Overlap of chunks joined without a pause (a pause length of 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crossfade_ms: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<PauseShape>,
+    /// Trim the silence the engine adds around each chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim: Option<bool>,
+}
+
+impl PauseConfig {
+    /// Fill unset fields from `defaults`
+    pub fn or(self, defaults: PauseConfig) -> Self {
+        Self {
+            sentence_ms: self.sentence_ms.or(defaults.sentence_ms),
+            clause_ms: self.clause_ms.or(defaults.clause_ms),
+            comma_ms: self.comma_ms.or(defaults.comma_ms),
+            other_ms: self.other_ms.or(defaults.other_ms),
+            fade_ms: self.fade_ms.or(defaults.fade_ms),
+            crossfade_ms: self.crossfade_ms.or(defaults.crossfade_ms),
+            shape: self.shape.or(defaults.shape),
+            trim: self.trim.or(defaults.trim),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Read the `"pauses"` object of a map.json language or voice entry
+    pub(crate) fn from_json(obj: &serde_json::Map<String, serde_json::Value>) -> anyhow::Result<Self> {
+        match obj.get("pauses") {
+            Some(pauses) => serde_json::from_value(pauses.clone())
+                .map_err(|e| anyhow::anyhow!("invalid 'pauses': {}", e)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Pause after a chunk, by its closing punctuation
+    pub fn pause_ms(&self, chunk: &str) -> u32 {
+        let trimmed = chunk.trim_end();
+        if trimmed.ends_with(['.', '!', '?']) {
+            self.sentence_ms.unwrap_or(400)
+        } else if trimmed.ends_with([';', ':']) {
+            self.clause_ms.unwrap_or(250)
+        } else if trimmed.ends_with(',') {
+            self.comma_ms.unwrap_or(150)
+        } else {
+            self.other_ms.unwrap_or(100)
+        }
+    }
+
+    fn shape(&self) -> PauseShape {
+        self.shape.unwrap_or_default()
+    }
+}
+
+/// Shapes consecutive chunks of one output. Chunks go through `trim`, then `join`; the
+/// pauses themselves are plain silence added by the caller.
+pub(crate) struct ChunkJoiner {
+    config: PauseConfig,
+    sample_rate: u32,
+    // End of the previous chunk, held back to be crossfaded into the next one
+    carry: Vec<f32>,
+}
+
+impl ChunkJoiner {
+    pub(crate) fn new(config: PauseConfig, sample_rate: u32) -> Self {
+        Self { config, sample_rate, carry: Vec::new() }
+    }
+
+    fn samples(&self, ms: u32) -> usize {
+        (self.sample_rate as u64 * ms as u64 / 1000) as usize
+    }
+
+    /// Cut the leading and trailing padding of a chunk (if enabled)
+    pub(crate) fn trim(&self, mut samples: Vec<f32>) -> Vec<f32> {
+        if !self.config.trim.unwrap_or(true) {
+            return samples;
+        }
+        let Some(first) = samples.iter().position(|s| s.abs() >= TRIM_THRESHOLD) else {
+            return Vec::new();
+        };
+        let last = samples.iter().rposition(|s| s.abs() >= TRIM_THRESHOLD).unwrap_or(first);
+        let keep = self.samples(TRIM_KEEP_MS);
+        samples.truncate((last + 1 + keep).min(samples.len()));
+        samples.drain(..first.saturating_sub(keep));
+        samples
+    }
+
+    /// Fade or crossfade the edges of a chunk. `pause_after` is the pause that follows it
+    /// (`None` for the last chunk); with a pause of 0 the end of the chunk is held back and
+    /// crossfaded into the next one.
+    pub(crate) fn join(&mut self, mut samples: Vec<f32>, pause_after: Option<u32>) -> Vec<f32> {
+        let shape = self.config.shape();
+        if shape == PauseShape::Cut {
+            let mut joined = std::mem::take(&mut self.carry);
+            joined.extend(samples);
+            return joined;
+        }
+
+        if self.carry.is_empty() {
+            let fade = self.samples(self.config.fade_ms.unwrap_or(DEFAULT_FADE_MS)).min(samples.len() / 2);
+            for (i, sample) in samples.iter_mut().take(fade).enumerate() {
+                *sample *= shape.gain((i as f32 + 0.5) / fade as f32);
+            }
+        } else {
+            let carry = std::mem::take(&mut self.carry);
+            if samples.len() < carry.len() {
+                samples.resize(carry.len(), 0.0);
+            }
+            let overlap = carry.len();
+            for (i, (sample, tail)) in samples.iter_mut().zip(carry).enumerate() {
+                let t = (i as f32 + 0.5) / overlap as f32;
+                *sample = *sample * shape.gain(t) + tail * shape.gain(1.0 - t);
+            }
+        }
+
+        if pause_after == Some(0) {
+            let overlap = self.samples(self.config.crossfade_ms.unwrap_or(DEFAULT_CROSSFADE_MS)).min(samples.len() / 2);
+            self.carry = samples.split_off(samples.len() - overlap);
+        } else {
+            let fade = self.samples(self.config.fade_ms.unwrap_or(DEFAULT_FADE_MS)).min(samples.len() / 2);
+            let len = samples.len();
+            for (i, sample) in samples[len - fade..].iter_mut().enumerate() {
+                *sample *= shape.gain(1.0 - (i as f32 + 0.5) / fade as f32);
+            }
+        }
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trims_and_fades() {
+        let config = PauseConfig { sentence_ms: Some(300), ..PauseConfig::default() };
+        assert_eq!(config.pause_ms("Hallo. "), 300);
+        assert_eq!(config.pause_ms("Hallo,"), 150);
+        assert_eq!(PauseConfig { comma_ms: Some(90), ..PauseConfig::default() }.or(config).pause_ms("a,"), 90);
+
+        // 1000 Hz: 50 samples of padding, 100 of speech, 50 of padding; 10 ms are kept
+        let mut joiner = ChunkJoiner::new(PauseConfig::default(), 1000);
+        let chunk: Vec<f32> = [vec![0.0; 50], vec![0.5; 100], vec![0.001; 50]].concat();
+        let trimmed = joiner.trim(chunk);
+        assert_eq!(trimmed.len(), 120);
+
+        assert!(joiner.trim(vec![0.001; 10]).is_empty());
+
+        // Edges start and end near zero, the middle is untouched
+        let joined = joiner.join(vec![0.5; 100], Some(200));
+        assert_eq!(joined.len(), 100);
+        assert!(joined[0] < 0.05 && joined[99] < 0.05);
+        assert_eq!(joined[50], 0.5);
+    }
+
+    #[test]
+    fn test_crossfades_without_pause() {
+        let config = PauseConfig { trim: Some(false), crossfade_ms: Some(20), ..PauseConfig::default() };
+        let mut joiner = ChunkJoiner::new(config, 1000);
+        let first = joiner.join(vec![0.5; 100], Some(0));
+        let second = joiner.join(vec![0.5; 100], None);
+        // 20 samples overlap; equal power keeps a constant signal above its level
+        assert_eq!(first.len() + second.len(), 180);
+        assert!(second[..20].iter().all(|s| (0.5..=0.71).contains(s)));
+
+        let mut joiner = ChunkJoiner::new(PauseConfig { shape: Some(PauseShape::Cut), ..config }, 1000);
+        let cut = [joiner.join(vec![0.5; 100], Some(0)), joiner.join(vec![0.5; 100], None)].concat();
+        assert_eq!(cut, vec![0.5; 200]);
+    }
+}
@@ -0,0 +1,319 @@
+//! Custom pronunciation lexicons.
+//!
+//! A lexicon maps words or short phrases to a respelling that the voice reads instead
+//! ("Qdrant = Kwadrant") or to phonemes that bypass the phonemizer ("Nguyen = /ŋwiən/").
+//! Lexicons are loaded per language and per voice from files referenced in map.json and can be
+//! edited at runtime; entries of a voice lexicon take precedence over the language lexicon.
+//!
+//! File format: one `word = respelling` or `word = /phonemes/` per line, `#` starts a comment
+//! line. Words match whole words, case-insensitively; whitespace inside a phrase matches any run
+//! of whitespace.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// How a lexicon word is spoken
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pronunciation {
+    /// Text read in place of the word
+    Respelling(String),
+    /// Phonemes passed to the model unchanged (eSpeak IPA, as produced by the phonemizer)
+    Phonemes(String),
+}
+
+/// One lexicon entry. Serializes as `{"word": ..., "respelling": ...}` or
+/// `{"word": ..., "phonemes": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LexiconEntry {
+    pub word: String,
+    #[serde(flatten)]
+    pub pronunciation: Pronunciation,
+}
+
+/// Language (and optionally voice) a lexicon applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LexiconScope {
+    pub language: String,
+    /// `None` for the language-wide lexicon
+    pub voice: Option<String>,
+}
+
+/// Text with lexicon entries applied, split where phonemes are inserted
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Piece {
+    Text(String),
+    Phonemes(String),
+}
+
+/// Word -> pronunciation mappings for one scope
+#[derive(Debug, Clone, Default)]
+pub struct Lexicon {
+    // lowercased word -> entry
+    entries: HashMap<String, LexiconEntry>,
+}
+
+impl Lexicon {
+    /// Parse a lexicon file (see the module docs for the format)
+    pub fn parse(data: &str) -> anyhow::Result<Self> {
+        let mut lexicon = Self::default();
+        for (number, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, value) = line
+                .split_once(" = ")
+                .ok_or_else(|| anyhow::anyhow!("line {}: expected 'word = pronunciation'", number + 1))?;
+            let (word, value) = (word.trim(), value.trim());
+            if word.is_empty() || value.is_empty() {
+                return Err(anyhow::anyhow!("line {}: empty word or pronunciation", number + 1));
+            }
+            let pronunciation = match value.strip_prefix('/').and_then(|v| v.strip_suffix('/')) {
+                Some(phonemes) => Pronunciation::Phonemes(phonemes.trim().to_string()),
+                None => Pronunciation::Respelling(value.to_string()),
+            };
+            lexicon.insert(LexiconEntry { word: word.to_string(), pronunciation });
+        }
+        Ok(lexicon)
+    }
+
+    /// Load a lexicon file
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read lexicon {}", path.as_ref().display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid lexicon {}", path.as_ref().display()))
+    }
+
+    /// Add or replace an entry; returns the replaced one
+    pub fn insert(&mut self, entry: LexiconEntry) -> Option<LexiconEntry> {
+        self.entries.insert(entry.word.to_lowercase(), entry)
+    }
+
+    /// Remove the entry for `word` (case-insensitive)
+    pub fn remove(&mut self, word: &str) -> Option<LexiconEntry> {
+        self.entries.remove(&word.to_lowercase())
+    }
+
+    pub fn get(&self, word: &str) -> Option<&LexiconEntry> {
+        self.entries.get(&word.to_lowercase())
+    }
+
+    /// Entries sorted by word
+    pub fn entries(&self) -> Vec<LexiconEntry> {
+        let mut entries: Vec<LexiconEntry> = self.entries.values().cloned().collect();
+        entries.sort_by_key(|entry| entry.word.to_lowercase());
+        entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add all entries of `other`, replacing entries for the same words
+    pub(crate) fn extend(&mut self, other: &Lexicon) {
+        self.entries.extend(other.entries.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// Replace lexicon words in `text`. Respellings are substituted in place; phoneme entries
+    /// split the text into separate pieces.
+    pub(crate) fn apply(&self, text: &str) -> Vec<Piece> {
+        if self.entries.is_empty() {
+            return vec![Piece::Text(text.to_string())];
+        }
+        // Longest words first so phrases win over the words they contain
+        let mut words: Vec<(Vec<char>, &LexiconEntry)> =
+            self.entries.iter().map(|(word, entry)| (word.chars().collect(), entry)).collect();
+        words.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+
+        let chars: Vec<char> = text.chars().collect();
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let found = words.iter().find_map(|(word, entry)| match_word(&chars, i, word).map(|len| (*entry, len)));
+            match found {
+                Some((entry, len)) => {
+                    match &entry.pronunciation {
+                        Pronunciation::Respelling(respelling) => current.push_str(respelling),
+                        Pronunciation::Phonemes(phonemes) => {
+                            if !current.is_empty() {
+                                pieces.push(Piece::Text(std::mem::take(&mut current)));
+                            }
+                            pieces.push(Piece::Phonemes(phonemes.clone()));
+                        }
+                    }
+                    i += len;
+                }
+                None => {
+                    current.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        if !current.is_empty() || pieces.is_empty() {
+            pieces.push(Piece::Text(current));
+        }
+        pieces
+    }
+}
+
+/// True if `text` contains `word` as a whole word (case-insensitive)
+pub(crate) fn mentions(text: &str, word: &str) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    let word: Vec<char> = word.to_lowercase().chars().collect();
+    (0..chars.len()).any(|i| match_word(&chars, i, &word).is_some())
+}
+
+/// Length in chars if the lowercased `word` starts at `start` as a whole word
+fn match_word(chars: &[char], start: usize, word: &[char]) -> Option<usize> {
+    if word.is_empty() || (start > 0 && chars[start - 1].is_alphanumeric()) {
+        return None;
+    }
+    let mut i = start;
+    for &w in word {
+        let c = *chars.get(i)?;
+        if w.is_whitespace() {
+            if !c.is_whitespace() {
+                return None;
+            }
+            while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                i += 1;
+            }
+            continue;
+        }
+        if !c.to_lowercase().eq(w.to_lowercase()) {
+            return None;
+        }
+        i += 1;
+    }
+    // Words ending in a letter or digit must end there ("AI" does not match "AIM")
+    let ends_word = !word.last().is_some_and(|c| c.is_alphanumeric())
+        || chars.get(i).is_none_or(|c| !c.is_alphanumeric());
+    ends_word.then_some(i - start)
+}
+
+/// All lexicons of a manager. `generation` changes with every edit so that results synthesized
+/// with an older lexicon are not cached.
+#[derive(Debug, Default)]
+pub(crate) struct Lexicons {
+    scopes: HashMap<LexiconScope, Lexicon>,
+    pub(crate) generation: u64,
+}
+
+impl Lexicons {
+    /// Language lexicon merged with the voice lexicon
+    pub(crate) fn for_voice(&self, language: &str, voice: Option<&str>) -> Lexicon {
+        let mut lexicon = Lexicon::default();
+        let language_scope = LexiconScope { language: language.to_string(), voice: None };
+        if let Some(language_lexicon) = self.scopes.get(&language_scope) {
+            lexicon.extend(language_lexicon);
+        }
+        if let Some(voice) = voice {
+            let voice_scope = LexiconScope { language: language.to_string(), voice: Some(voice.to_string()) };
+            if let Some(voice_lexicon) = self.scopes.get(&voice_scope) {
+                lexicon.extend(voice_lexicon);
+            }
+        }
+        lexicon
+    }
+
+    pub(crate) fn set(&mut self, scope: LexiconScope, lexicon: Lexicon) {
+        self.scopes.insert(scope, lexicon);
+        self.generation += 1;
+    }
+
+    pub(crate) fn insert(&mut self, scope: LexiconScope, entry: LexiconEntry) -> Option<LexiconEntry> {
+        self.generation += 1;
+        self.scopes.entry(scope).or_default().insert(entry)
+    }
+
+    pub(crate) fn remove(&mut self, scope: &LexiconScope, word: &str) -> Option<LexiconEntry> {
+        let removed = self.scopes.get_mut(scope)?.remove(word);
+        if removed.is_some() {
+            self.generation += 1;
+        }
+        removed
+    }
+
+    /// Replace the lexicons loaded from map.json after a reload. Scopes `keep` rejects (their
+    /// language or voice is gone) are dropped; entries added at runtime elsewhere stay unless
+    /// the map file sets a lexicon for their scope.
+    pub(crate) fn reload(&mut self, loaded: Lexicons, keep: impl Fn(&LexiconScope) -> bool) {
+        self.scopes.retain(|scope, _| keep(scope));
+        self.scopes.extend(loaded.scopes);
+        self.generation += 1;
+    }
+
+    /// Scopes with at least one entry, sorted
+    pub(crate) fn all(&self) -> Vec<(LexiconScope, Vec<LexiconEntry>)> {
+        let mut all: Vec<(LexiconScope, Vec<LexiconEntry>)> = self
+            .scopes
+            .iter()
+            .filter(|(_, lexicon)| !lexicon.is_empty())
+            .map(|(scope, lexicon)| (scope.clone(), lexicon.entries()))
+            .collect();
+        all.sort_by(|a, b| a.0.cmp(&b.0));
+        all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_apply() {
+        let lexicon = Lexicon::parse(
+            "# product names\nQdrant = Kwadrant\nNew York = Nju Jork\nNguyen = /ŋwiən/\n",
+        )
+        .unwrap();
+        assert_eq!(
+            lexicon.apply("qdrant in new  York, not Qdrants."),
+            vec![Piece::Text("Kwadrant in Nju Jork, not Qdrants.".to_string())]
+        );
+        assert_eq!(
+            lexicon.apply("Hallo Nguyen!"),
+            vec![
+                Piece::Text("Hallo ".to_string()),
+                Piece::Phonemes("ŋwiən".to_string()),
+                Piece::Text("!".to_string()),
+            ]
+        );
+        assert!(Lexicon::parse("missing separator").is_err());
+    }
+
+    #[test]
+    fn test_voice_entries_override_language_entries() {
+        let mut lexicons = Lexicons::default();
+        let word = |respelling: &str| LexiconEntry {
+            word: "GIF".to_string(),
+            pronunciation: Pronunciation::Respelling(respelling.to_string()),
+        };
+        lexicons.insert(LexiconScope { language: "en_US".to_string(), voice: None }, word("gif"));
+        lexicons.insert(LexiconScope { language: "en_US".to_string(), voice: Some("amy".to_string()) }, word("jif"));
+
+        assert_eq!(lexicons.for_voice("en_US", Some("amy")).apply("GIF"), vec![Piece::Text("jif".to_string())]);
+        assert_eq!(lexicons.for_voice("en_US", Some("norman")).apply("GIF"), vec![Piece::Text("gif".to_string())]);
+        assert!(mentions("A gif file", "GIF"));
+        assert!(!mentions("GIFs", "GIF"));
+        assert_eq!(lexicons.generation, 2);
+    }
+
+    #[test]
+    fn test_changes_invalidate_by_normalized_text() {
+        let map = [("de_DE".to_string(), ("/nonexistent/voice.onnx.json".to_string(), None))].into();
+        let tts = crate::TtsManager::new(map).with_engine(crate::EngineKind::Mock);
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let options = crate::SynthesisOptions::default();
+        runtime.block_on(tts.synthesize_with_cache("Um 12 Uhr.", Some("de_DE"), None, &options)).unwrap();
+
+        // The lexicon sees "zwölf", not the digits
+        let scope = LexiconScope { language: "de_DE".to_string(), voice: None };
+        let entry = |word: &str| LexiconEntry { word: word.to_string(), pronunciation: Pronunciation::Respelling("x".to_string()) };
+        assert_eq!(runtime.block_on(tts.add_lexicon_entry(scope.clone(), entry("12"))).unwrap(), 0);
+        assert_eq!(runtime.block_on(tts.add_lexicon_entry(scope, entry("zwölf"))).unwrap(), 1);
+    }
+}
@@ -1,20 +1,77 @@
 mod wav;
 mod melspec;
+mod stream;
+mod prosody;
+mod options;
+mod expression;
+mod pitch;
+pub mod ssml;
+mod encoder;
+mod flac;
+mod resample;
+mod loudness;
+mod normalize;
+mod segment;
+mod lexicon;
+mod alignment;
+mod subtitles;
+mod disk_cache;
+mod response_cache;
+mod model_cache;
+mod voice_map;
+mod voice_status;
+mod file_hash;
+mod engine;
+mod mock_engine;
+mod synth_pool;
+mod join;
+mod silence;
+
+pub use stream::{AudioChunk, ChunkKind, SpeechChunks, SynthesisStream};
+pub use prosody::Prosody;
+pub use options::SynthesisOptions;
+pub use expression::{EmotionDetector, EmotionDetectors, KeywordEmotionDetector, ProsodyHint};
+pub use pitch::pitch_shift;
+pub use resample::resample;
+pub use loudness::{integrated_loudness, LoudnessReport, LoudnessTarget};
+pub use ssml::{SsmlDocument, SsmlError, SsmlSegment};
+pub use encoder::{AudioEncoder, AudioFormat, EncodedAudio};
+pub use normalize::normalize_text;
+pub use segment::{abbreviations_for, expand_abbreviations, segment_text, Abbreviation, Boundary, Segment};
+pub use lexicon::{Lexicon, LexiconEntry, LexiconScope, Pronunciation};
+pub use alignment::{map_word_offsets, Alignment, ChunkSpan, WordTiming};
+pub use subtitles::{build_cues, Cue, SubtitleFormat, SubtitleOptions};
+pub use disk_cache::DiskCacheConfig;
+pub use response_cache::CacheUsage;
+pub use model_cache::{LoadedModel, ModelCacheConfig};
+pub use voice_map::{InvalidVoiceMap, MapReload, VoiceMetadata};
+pub use voice_status::{VoiceIssue, VoiceStatus};
+pub use engine::{EngineKind, TtsEngine};
+pub use mock_engine::MockEngine;
+pub use synth_pool::{PoolConfig, PoolStats, VoiceBusy};
+pub use join::{PauseConfig, PauseShape};
+pub use silence::SilenceOptions;
+
+use lexicon::{Lexicons, Piece};
+use alignment::WordMap;
+use disk_cache::{DiskCache, DiskEntry, StableHasher};
+use response_cache::{CachedPcm, CachedResponse, ResponseCache};
+use synth_pool::SynthPool;
+use join::ChunkJoiner;
+use voice_map::VoiceMap;
 
 use std::{collections::HashMap, fs, path::Path, sync::{Arc, RwLock}, hash::{Hash, Hasher}, time::Instant};
 
 use anyhow::Context;
 use base64::Engine; // for STANDARD.encode()
-use hound;
 use image::{ImageBuffer, Luma};
 use mel_spec::prelude::*;
 use ndarray::Array1;
 use num_complex::Complex;
 //use piper_rs::PiperError;
 use serde::{Deserialize, Serialize};
-use piper_rs::synth::{PiperSpeechStreamParallel, PiperSpeechSynthesizer};
+use piper_rs::synth::PiperSpeechSynthesizer;
 use dashmap::DashMap;
-use lru::LruCache;
 use tokio::sync::RwLock as TokioRwLock;
 use tokio::time::Duration;
 use ahash::AHasher;
@@ -26,31 +83,93 @@ pub struct MapEntry {
     pub default_speaker: Option<i64>,
 }
 
-// Cached synthesizer and sample rate
+/// Memory budget of the response cache unless configured
+const DEFAULT_RESPONSE_CACHE_BYTES: usize = 128 * 1024 * 1024;
+
+// Cached engine pool and sample rate
 struct CachedSynth {
-    synth: Arc<RwLock<PiperSpeechSynthesizer>>, // Changed from Mutex to RwLock for parallel reads
+    pool: Arc<SynthPool>,
+    kind: EngineKind,
+    num_speakers: u32,
     sample_rate: u32,
+    length_scale: f32, // model default
+    memory_bytes: u64, // approximate, see model_cache
     last_accessed: Instant, // Track access time for LRU
 }
 
-// Manual Debug implementation since PiperSpeechSynthesizer doesn't implement Debug
+// Manual Debug implementation since engines don't implement Debug
 impl std::fmt::Debug for CachedSynth {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CachedSynth")
-            .field("synth", &"<PiperSpeechSynthesizer>")
+            .field("kind", &self.kind)
+            .field("num_speakers", &self.num_speakers)
             .field("sample_rate", &self.sample_rate)
+            .field("memory_bytes", &self.memory_bytes)
             .field("last_accessed", &self.last_accessed)
             .finish()
     }
 }
 
-// Cached audio response
+// Kind of request input (part of the response cache key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum InputKind {
+    Text,
+    Ssml,
+    Phonemes,
+}
+
+// What a cached response was synthesized from, to find the entries a lexicon change affects
 #[derive(Clone)]
-struct CachedResponse {
-    audio_base64: String,
-    sample_rate: u32,
-    duration_ms: u64,
-    cached_at: Instant,
+pub(crate) struct CacheSource {
+    language: String,
+    voice: Option<String>, // resolved voice ID (None for legacy map entries)
+    text: String,
+    // What the lexicons are applied to: the normalized text (of each SSML text segment)
+    spoken: String,
+    kind: InputKind,
+}
+
+impl CacheSource {
+    /// True if changing `word` in the `scope` lexicon may change this response
+    fn affected_by(&self, scope: &LexiconScope, word: &str) -> bool {
+        let in_scope = match self.kind {
+            InputKind::Text => self.language == scope.language && (scope.voice.is_none() || self.voice == scope.voice),
+            // SSML can switch language and voice mid-document, so only the text is checked
+            InputKind::Ssml => true,
+            // Phoneme input bypasses lexicons
+            InputKind::Phonemes => false,
+        };
+        in_scope && lexicon::mentions(&self.spoken, word)
+    }
+}
+
+/// Encoded synthesis result returned by the cached synthesis methods
+#[derive(Debug, Clone)]
+pub struct SynthesizedAudio {
+    /// Audio encoded in the requested format
+    pub audio: Vec<u8>,
+    pub mime_type: &'static str,
+    /// Sample rate of the encoded audio
+    pub sample_rate: u32,
+    pub duration_ms: u64,
+    /// Measured loudness before and after normalization
+    pub loudness: LoudnessReport,
+    /// Word and chunk timings with character offsets into the synthesized text (if requested)
+    pub alignment: Option<Alignment>,
+    pub cache_hit: bool,
+}
+
+impl SynthesizedAudio {
+    pub fn audio_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.audio)
+    }
+}
+
+/// A speaker of a multi-speaker voice (from `speaker_id_map` in config.onnx.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerInfo {
+    pub id: i64,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,152 +179,190 @@ pub struct VoiceEntry {
     pub display_name: Option<String>,
     pub gender: Option<String>,
     pub quality: Option<String>,
+    #[serde(flatten)]
+    pub prosody: Prosody, // per-voice defaults for length_scale / noise_scale / noise_w
+    #[serde(flatten)]
+    pub loudness: LoudnessTarget, // per-voice defaults for target_lufs / true_peak_db
+    #[serde(default, skip_serializing_if = "PauseConfig::is_default")]
+    pub pauses: PauseConfig, // per-voice pause lengths and chunk joining
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>, // expected checksum of the .onnx model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<VoiceMetadata>, // metadata.json of the voice folder
 }
 
 #[derive(Debug, Clone)]
 pub struct TtsManager {
-    // language key -> (default voice, voices map)
-    // For backwards compatibility: language key -> (config path, default speaker)
-    pub(crate) map: HashMap<String, (String, Option<i64>)>,
-    // New format: language -> (default_voice_id, voices_map)
-    pub(crate) voices_map: HashMap<String, (String, HashMap<String, VoiceEntry>)>,
+    // Languages and voices from map.json, swapped as a whole on reload
+    voices: Arc<RwLock<Arc<VoiceMap>>>,
     // Cache: config path -> (synthesizer, sample_rate)
     // Using DashMap for concurrent access without blocking
     cache: Arc<DashMap<String, CachedSynth>>,
-    // LRU cache wrapper to limit cache size
-    max_cache_size: usize,
-    // Response cache: (text + language + voice) -> CachedResponse
+    // Model count, memory budget and idle TTL of the synthesizer cache
+    model_cache: ModelCacheConfig,
+    // Engine of voices whose map.json entry does not name one
+    engine: EngineKind,
+    // Instances, concurrency limit and wait queue of each voice's engine pool
+    pool: PoolConfig,
+    // Response cache: (text + language + voice + options) -> normalized PCM, bounded by bytes
     // Using TokioRwLock for async access
-    response_cache: Arc<TokioRwLock<LruCache<u64, CachedResponse>>>,
+    response_cache: Arc<TokioRwLock<ResponseCache>>,
     response_cache_ttl: Duration,
+    // Optional persistent tier under the response cache
+    disk_cache: Option<Arc<DiskCache>>,
+    // Per-language emotion detectors for expressive mode
+    emotion_detectors: Arc<RwLock<EmotionDetectors>>,
+    // Pronunciation lexicons per language and voice (loaded from map.json, editable at runtime)
+    lexicons: Arc<RwLock<Lexicons>>,
 }
 
 impl TtsManager {
     /// Create from a prebuilt map
     pub fn new(map: HashMap<String, (String, Option<i64>)>) -> Self {
         Self { 
-            map,
-            voices_map: HashMap::new(),
+            voices: Arc::new(RwLock::new(Arc::new(VoiceMap { map, ..VoiceMap::default() }))),
             cache: Arc::new(DashMap::new()),
-            max_cache_size: 15, // Increased: cache up to 15 models (better for multi-language scenarios)
-            response_cache: Arc::new(TokioRwLock::new(LruCache::new(std::num::NonZeroUsize::new(500).unwrap()))), // Increased: 500 entries for better hit rate
+            model_cache: ModelCacheConfig::default(), // up to 15 models / 2 GiB
+            engine: EngineKind::Piper,
+            pool: PoolConfig::default(),
+            response_cache: Arc::new(TokioRwLock::new(ResponseCache::new(DEFAULT_RESPONSE_CACHE_BYTES))),
             response_cache_ttl: Duration::from_secs(3600), // 1 hour TTL
+            disk_cache: None,
+            emotion_detectors: Arc::new(RwLock::new(EmotionDetectors::builtin())),
+            lexicons: Arc::new(RwLock::new(Lexicons::default())),
         }
     }
     
     /// Create with custom cache size limit
     pub fn new_with_cache_size(map: HashMap<String, (String, Option<i64>)>, max_cache_size: usize) -> Self {
         Self {
-            map,
-            voices_map: HashMap::new(),
+            voices: Arc::new(RwLock::new(Arc::new(VoiceMap { map, ..VoiceMap::default() }))),
             cache: Arc::new(DashMap::new()),
-            max_cache_size,
-            response_cache: Arc::new(TokioRwLock::new(LruCache::new(std::num::NonZeroUsize::new(500).unwrap()))), // Increased: 500 entries
+            model_cache: ModelCacheConfig { max_models: max_cache_size, ..ModelCacheConfig::default() },
+            engine: EngineKind::Piper,
+            pool: PoolConfig::default(),
+            response_cache: Arc::new(TokioRwLock::new(ResponseCache::new(DEFAULT_RESPONSE_CACHE_BYTES))),
             response_cache_ttl: Duration::from_secs(3600), // 1 hour TTL
+            disk_cache: None,
+            emotion_detectors: Arc::new(RwLock::new(EmotionDetectors::builtin())),
+            lexicons: Arc::new(RwLock::new(Lexicons::default())),
         }
     }
 
     /// Load from `models/map.json`
     /// Supports both new format (with multiple voices) and legacy format
     pub fn new_from_mapfile<P: AsRef<Path>>(p: P) -> anyhow::Result<Self> {
-        let text = fs::read_to_string(p.as_ref())
-            .with_context(|| format!("Failed to load {}", p.as_ref().display()))?;
-        let json: serde_json::Value = serde_json::from_str(&text)
-            .with_context(|| "map.json is not valid JSON")?;
-
-        let mut map: HashMap<String, (String, Option<i64>)> = HashMap::new();
-        let mut voices_map: HashMap<String, (String, HashMap<String, VoiceEntry>)> = HashMap::new();
-
-        if let Some(obj) = json.as_object() {
-            for (lang, v) in obj {
-                // Check if this is the new format with "voices" key
-                if let serde_json::Value::Object(o) = v {
-                    if o.contains_key("voices") {
-                        // New format: { "default_voice": "...", "voices": {...} }
-                        let default_voice = o
-                            .get("default_voice")
-                            .and_then(|x| x.as_str())
-                            .ok_or_else(|| anyhow::anyhow!("missing 'default_voice' for language {}", lang))?
-                            .to_string();
-                        
-                        let voices_obj = o
-                            .get("voices")
-                            .and_then(|x| x.as_object())
-                            .ok_or_else(|| anyhow::anyhow!("missing 'voices' object for language {}", lang))?;
-                        
-                        let mut voices: HashMap<String, VoiceEntry> = HashMap::new();
-                        for (voice_id, voice_data) in voices_obj {
-                            if let serde_json::Value::Object(vo) = voice_data {
-                                let config = vo
-                                    .get("config")
-                                    .and_then(|x| x.as_str())
-                                    .ok_or_else(|| anyhow::anyhow!("missing 'config' for voice {}", voice_id))?
-                                    .to_string();
-                                
-                                let speaker_id = vo.get("speaker_id").and_then(|x| x.as_i64());
-                                let voice_entry = VoiceEntry {
-                                    config: config.clone(),
-                                    speaker_id,
-                                    display_name: vo.get("display_name").and_then(|x| x.as_str()).map(|s| s.to_string()),
-                                    gender: vo.get("gender").and_then(|x| x.as_str()).map(|s| s.to_string()),
-                                    quality: vo.get("quality").and_then(|x| x.as_str()).map(|s| s.to_string()),
-                                };
-                                
-                                voices.insert(voice_id.clone(), voice_entry);
-                                
-                                // Also populate legacy map with default voice for backwards compatibility
-                                if voice_id == &default_voice {
-                                    map.insert(lang.clone(), (config, speaker_id));
-                                }
-                            }
-                        }
-                        
-                        voices_map.insert(lang.clone(), (default_voice, voices));
-                        continue;
-                    }
-                }
-                
-                // Legacy format handling
-                match v {
-                    serde_json::Value::String(path) => {
-                        map.insert(lang.clone(), (path.clone(), None));
-                    }
-                    serde_json::Value::Object(o) => {
-                        let config = o
-                            .get("config")
-                            .and_then(|x| x.as_str())
-                            .ok_or_else(|| anyhow::anyhow!("missing 'config' for key {}", lang))?
-                            .to_string();
-                        let spk = o.get("default_speaker").and_then(|x| x.as_i64());
-                        map.insert(lang.clone(), (config, spk));
-                    }
-                    _ => {
-                        return Err(anyhow::anyhow!(
-                            "invalid entry for key {} (expected string or object)",
-                            lang
-                        ));
-                    }
-                }
-            }
-        } else {
-            return Err(anyhow::anyhow!("map.json must be a JSON object"));
-        }
+        let (voices, lexicons) = VoiceMap::load(p.as_ref())?;
 
         Ok(Self { 
-            map,
-            voices_map,
+            voices: Arc::new(RwLock::new(Arc::new(voices))),
             cache: Arc::new(DashMap::new()),
-            max_cache_size: 15, // Increased: cache up to 15 models
-            response_cache: Arc::new(TokioRwLock::new(LruCache::new(std::num::NonZeroUsize::new(500).unwrap()))), // Increased: 500 entries
+            model_cache: ModelCacheConfig::default(), // up to 15 models / 2 GiB
+            engine: EngineKind::Piper,
+            pool: PoolConfig::default(),
+            response_cache: Arc::new(TokioRwLock::new(ResponseCache::new(DEFAULT_RESPONSE_CACHE_BYTES))),
             response_cache_ttl: Duration::from_secs(3600), // 1 hour TTL
+            disk_cache: None,
+            emotion_detectors: Arc::new(RwLock::new(EmotionDetectors::builtin())),
+            lexicons: Arc::new(RwLock::new(lexicons)),
+        })
+    }
+
+    /// Add the voices found in a models directory: every `<language>/<voice>/` folder with a
+    /// Piper config and a metadata.json. Entries of the map file take precedence over
+    /// discovered values, and later reloads of the map file scan the directory again.
+    pub fn with_voice_discovery<P: AsRef<Path>>(self, models_dir: P) -> anyhow::Result<Self> {
+        let discovered = VoiceMap::discover(models_dir.as_ref())?;
+        let voices = VoiceMap::overlay(discovered, &self.voices());
+        *self.voices.write().map_err(|_| anyhow::anyhow!("Voice map lock poisoned"))? = Arc::new(voices);
+        Ok(self)
+    }
+
+    /// Reload map.json in place. The new map is validated first and rejected (keeping the
+    /// current one) if it fails, with an `InvalidVoiceMap` error if it parsed but is
+    /// inconsistent or a new or changed voice is broken; otherwise it replaces the current map
+    /// atomically. Voices that were already broken are kept, as at startup. Loaded models of
+    /// voices still in the map stay loaded, models no voice uses anymore are unloaded, the
+    /// lexicons of the map file are reloaded and cached responses are dropped.
+    pub async fn reload_mapfile<P: AsRef<Path>>(&self, p: P) -> anyhow::Result<MapReload> {
+        let (mut voices, lexicons) = VoiceMap::load(p.as_ref())?;
+        if let Some(models_dir) = self.voices().models_dir.clone() {
+            voices = VoiceMap::overlay(VoiceMap::discover(&models_dir)?, &voices);
+        }
+        let broken = voices.validate(self.engine, &self.voices())?;
+        let config_paths = voices.config_paths();
+        let voices = Arc::new(voices);
+
+        let previous = {
+            let mut current = self.voices.write().map_err(|_| anyhow::anyhow!("Voice map lock poisoned"))?;
+            std::mem::replace(&mut *current, Arc::clone(&voices))
+        };
+
+        let unloaded_models: Vec<String> = self
+            .cache
+            .iter()
+            .map(|entry| entry.key().clone())
+            .filter(|config| !config_paths.contains(config))
+            .collect();
+        for config in &unloaded_models {
+            self.cache.remove(config);
+        }
+
+        self.lexicons
+            .write()
+            .map_err(|_| anyhow::anyhow!("Lexicon lock poisoned"))?
+            .reload(lexicons, |scope| voices.has_scope(scope));
+        let invalidated_cache_entries = self.response_cache.write().await.clear();
+
+        let (old_ids, new_ids) = (previous.voice_ids(), voices.voice_ids());
+        Ok(MapReload {
+            added: new_ids.difference(&old_ids).cloned().collect(),
+            removed: old_ids.difference(&new_ids).cloned().collect(),
+            unloaded_models,
+            broken,
+            invalidated_cache_entries,
         })
     }
 
+    // Current voice map (a snapshot: a reload replaces it rather than changing it)
+    fn voices(&self) -> Arc<VoiceMap> {
+        match self.voices.read() {
+            Ok(voices) => Arc::clone(&voices),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Add a persistent response cache under the in-memory one. Entries survive restarts and
+    /// are keyed by the voice's model files, so replacing a model invalidates them.
+    pub fn with_disk_cache(mut self, config: DiskCacheConfig) -> anyhow::Result<Self> {
+        self.disk_cache = Some(Arc::new(DiskCache::open(config)?));
+        Ok(self)
+    }
+
+    /// Memory budget of the response cache in bytes (default 128 MiB); clears the cache
+    pub fn with_response_cache_bytes(mut self, max_bytes: usize) -> Self {
+        self.response_cache = Arc::new(TokioRwLock::new(ResponseCache::new(max_bytes)));
+        self
+    }
+
+    /// Size of the in-memory response cache
+    pub async fn response_cache_usage(&self) -> CacheUsage {
+        self.response_cache.read().await.usage()
+    }
+
+    /// Size of the disk cache, if enabled
+    pub fn disk_cache_usage(&self) -> Option<CacheUsage> {
+        self.disk_cache.as_ref().map(|disk_cache| disk_cache.usage())
+    }
+
     /// List supported language keys
     pub fn list_languages(&self) -> Vec<String> {
         // Combine languages from both maps
-        let mut langs: Vec<String> = self.map.keys().cloned().collect();
-        for lang in self.voices_map.keys() {
+        let voices = self.voices();
+        let mut langs: Vec<String> = voices.map.keys().cloned().collect();
+        for lang in voices.voices_map.keys() {
             if !langs.contains(lang) {
                 langs.push(lang.clone());
             }
@@ -215,17 +372,20 @@ impl TtsManager {
     }
 
     /// Iterate raw mapping (for /voices/detail)
-    pub fn map_iter(&self) -> impl Iterator<Item = (&String, &(String, Option<i64>))> {
-        self.map.iter()
+    pub fn map_iter(&self) -> impl Iterator<Item = (String, (String, Option<i64>))> {
+        let mut entries: Vec<_> = self.voices().map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        entries.sort();
+        entries.into_iter()
     }
 
     /// Resolve config (and default speaker) for a language key
     /// If voice_opt is provided, uses that voice; otherwise uses default voice
     pub fn config_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> anyhow::Result<(String, Option<i64>)> {
         let lang = lang_opt.unwrap_or("de_DE");
+        let map = self.voices();
         
         // Try new format first
-        if let Some((default_voice, voices)) = self.voices_map.get(lang) {
+        if let Some((default_voice, voices)) = map.voices_map.get(lang) {
             let voice_id = voice_opt.unwrap_or(default_voice);
             if let Some(voice_entry) = voices.get(voice_id) {
                 return Ok((voice_entry.config.clone(), voice_entry.speaker_id));
@@ -239,15 +399,134 @@ impl TtsManager {
         }
         
         // Fall back to legacy format
-        self.map
+        map.map
             .get(lang)
             .cloned()
             .ok_or_else(|| anyhow::anyhow!(format!("Unknown language key: {lang}. Use /voices to list.")))
     }
     
+    /// Per-voice prosody defaults from map.json (empty for legacy entries)
+    fn prosody_defaults_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> Prosody {
+        let lang = lang_opt.unwrap_or("de_DE");
+        self.voices()
+            .voices_map
+            .get(lang)
+            .and_then(|(default_voice, voices)| voices.get(voice_opt.unwrap_or(default_voice)))
+            .map(|entry| entry.prosody)
+            .unwrap_or_default()
+    }
+    
+    /// Per-voice loudness target from map.json (empty for legacy entries)
+    fn loudness_defaults_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> LoudnessTarget {
+        let lang = lang_opt.unwrap_or("de_DE");
+        self.voices()
+            .voices_map
+            .get(lang)
+            .and_then(|(default_voice, voices)| voices.get(voice_opt.unwrap_or(default_voice)))
+            .map(|entry| entry.loudness)
+            .unwrap_or_default()
+    }
+    
+    /// Pause lengths and chunk joining from map.json: the voice's, then the language's
+    fn pauses_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> PauseConfig {
+        let lang = lang_opt.unwrap_or("de_DE");
+        let voices = self.voices();
+        let voice_pauses = voices
+            .voices_map
+            .get(lang)
+            .and_then(|(default_voice, voices)| voices.get(voice_opt.unwrap_or(default_voice)))
+            .map(|entry| entry.pauses)
+            .unwrap_or_default();
+        voice_pauses.or(voices.pauses.get(lang).copied().unwrap_or_default())
+    }
+    
+    /// Register (or replace) the emotion detector used in expressive mode for a
+    /// language key (`de_DE`) or language code (`de`)
+    pub fn register_emotion_detector(&self, lang: &str, detector: Arc<dyn EmotionDetector>) {
+        if let Ok(mut detectors) = self.emotion_detectors.write() {
+            detectors.register(lang, detector);
+        }
+    }
+
+    /// Emotion detector for a language (falls back to punctuation-only detection)
+    pub fn emotion_detector_for(&self, lang: &str) -> Arc<dyn EmotionDetector> {
+        match self.emotion_detectors.read() {
+            Ok(detectors) => detectors.for_language(lang),
+            Err(_) => Arc::new(KeywordEmotionDetector::default()),
+        }
+    }
+    
+    /// Voice ID a request resolves to (`None` for legacy map entries)
+    fn voice_id_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> Option<String> {
+        let lang = lang_opt.unwrap_or("de_DE");
+        self.voices()
+            .voices_map
+            .get(lang)
+            .map(|(default_voice, _)| voice_opt.unwrap_or(default_voice).to_string())
+    }
+
+    /// Lexicon applied to a request: the language lexicon merged with the voice lexicon
+    fn lexicon_for(&self, lang_opt: Option<&str>, voice_opt: Option<&str>) -> Lexicon {
+        let voice = self.voice_id_for(lang_opt, voice_opt);
+        match self.lexicons.read() {
+            Ok(lexicons) => lexicons.for_voice(lang_opt.unwrap_or("de_DE"), voice.as_deref()),
+            Err(_) => Lexicon::default(),
+        }
+    }
+
+    /// All non-empty lexicons with their entries, sorted by language and voice
+    pub fn lexicons(&self) -> Vec<(LexiconScope, Vec<LexiconEntry>)> {
+        match self.lexicons.read() {
+            Ok(lexicons) => lexicons.all(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Add or replace a lexicon entry and drop the cached responses that contain the word.
+    /// Returns the number of invalidated cache entries.
+    pub async fn add_lexicon_entry(&self, scope: LexiconScope, entry: LexiconEntry) -> anyhow::Result<usize> {
+        let word = entry.word.clone();
+        self.lexicons
+            .write()
+            .map_err(|_| anyhow::anyhow!("Lexicon lock poisoned"))?
+            .insert(scope.clone(), entry);
+        Ok(self.invalidate_lexicon_word(&scope, &word).await)
+    }
+
+    /// Remove a lexicon entry and drop the cached responses that contain the word.
+    /// Returns the number of invalidated cache entries, or `None` if there was no such entry.
+    pub async fn remove_lexicon_entry(&self, scope: &LexiconScope, word: &str) -> anyhow::Result<Option<usize>> {
+        let removed = self
+            .lexicons
+            .write()
+            .map_err(|_| anyhow::anyhow!("Lexicon lock poisoned"))?
+            .remove(scope, word);
+        match removed {
+            Some(_) => Ok(Some(self.invalidate_lexicon_word(scope, word).await)),
+            None => Ok(None),
+        }
+    }
+
+    async fn invalidate_lexicon_word(&self, scope: &LexiconScope, word: &str) -> usize {
+        let mut cache = self.response_cache.write().await;
+        let stale: Vec<u64> = cache
+            .iter()
+            .filter(|(_, cached)| cached.source.affected_by(scope, word))
+            .map(|(key, _)| *key)
+            .collect();
+        for key in &stale {
+            cache.pop(key);
+        }
+        stale.len()
+    }
+
+    fn lexicon_generation(&self) -> u64 {
+        self.lexicons.read().map(|lexicons| lexicons.generation).unwrap_or(0)
+    }
+
     /// List all voices for a language
     pub fn list_voices_for_language(&self, lang: &str) -> Vec<(String, VoiceEntry)> {
-        if let Some((_, voices)) = self.voices_map.get(lang) {
+        if let Some((_, voices)) = self.voices().voices_map.get(lang) {
             voices.iter().map(|(id, entry)| (id.clone(), entry.clone())).collect()
         } else {
             Vec::new()
@@ -256,11 +535,18 @@ impl TtsManager {
     
     /// Get default voice for a language
     pub fn get_default_voice(&self, lang: &str) -> Option<String> {
-        self.voices_map.get(lang).map(|(default, _)| default.clone())
+        self.voices().voices_map.get(lang).map(|(default, _)| default.clone())
+    }
+
+    /// Check the files of every voice without loading them: missing files, Git LFS pointers,
+    /// configs without a sample rate and checksum mismatches (blocking: checksums read whole
+    /// models)
+    pub fn voice_status(&self) -> Vec<VoiceStatus> {
+        voice_status::check_all(&self.voices(), self.engine)
     }
 
     /// Read sample rate from model config JSON
-    fn read_sample_rate<P: AsRef<Path>>(cfg_path: P) -> anyhow::Result<u32> {
+    pub(crate) fn read_sample_rate<P: AsRef<Path>>(cfg_path: P) -> anyhow::Result<u32> {
         let text = fs::read_to_string(cfg_path.as_ref())
             .with_context(|| format!("Failed to read config file: {}", cfg_path.as_ref().display()))?;
         let json: serde_json::Value = serde_json::from_str(&text)
@@ -275,62 +561,169 @@ impl TtsManager {
         Ok(sample_rate as u32)
     }
 
-    /// Get or create a cached synthesizer for a config path
-    pub fn get_or_create_synth<P: AsRef<Path>>(
-        &self,
-        cfg_path: P,
-    ) -> anyhow::Result<(Arc<RwLock<PiperSpeechSynthesizer>>, u32)> {
+    /// Read speaker count from model config JSON (single-speaker models report 1)
+    pub(crate) fn read_num_speakers<P: AsRef<Path>>(cfg_path: P) -> anyhow::Result<u32> {
+        let text = fs::read_to_string(cfg_path.as_ref())
+            .with_context(|| format!("Failed to read config file: {}", cfg_path.as_ref().display()))?;
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .with_context(|| "Config file is not valid JSON")?;
+
+        Ok(json.get("num_speakers").and_then(|n| n.as_u64()).unwrap_or(1).max(1) as u32)
+    }
+
+    /// Read the default phoneme duration multiplier (`inference.length_scale`, 1.0 if unset)
+    /// from model config JSON
+    pub(crate) fn read_length_scale<P: AsRef<Path>>(cfg_path: P) -> anyhow::Result<f32> {
+        let text = fs::read_to_string(cfg_path.as_ref())
+            .with_context(|| format!("Failed to read config file: {}", cfg_path.as_ref().display()))?;
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .with_context(|| "Config file is not valid JSON")?;
+
+        let length_scale = json.get("inference").and_then(|i| i.get("length_scale")).and_then(|l| l.as_f64());
+        Ok(length_scale.filter(|&l| l > 0.0).unwrap_or(1.0) as f32)
+    }
+
+    /// List the speakers of a voice from `speaker_id_map` in its config, sorted by ID.
+    /// Single-speaker voices return an empty list.
+    pub fn read_speakers<P: AsRef<Path>>(cfg_path: P) -> anyhow::Result<Vec<SpeakerInfo>> {
+        let text = fs::read_to_string(cfg_path.as_ref())
+            .with_context(|| format!("Failed to read config file: {}", cfg_path.as_ref().display()))?;
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .with_context(|| "Config file is not valid JSON")?;
+
+        let mut speakers: Vec<SpeakerInfo> = json
+            .get("speaker_id_map")
+            .and_then(|m| m.as_object())
+            .map(|m| {
+                m.iter()
+                    .filter_map(|(name, id)| id.as_i64().map(|id| SpeakerInfo { id, name: name.clone() }))
+                    .collect()
+            })
+            .unwrap_or_default();
+        speakers.sort_by_key(|s| s.id);
+
+        Ok(speakers)
+    }
+
+    /// Get or create the cached engine pool for a config path together with the state
+    /// needed to select a speaker on it
+    fn get_or_create_voice<P: AsRef<Path>>(&self, cfg_path: P) -> anyhow::Result<VoiceHandle> {
         let cfg_path_str = cfg_path.as_ref().to_string_lossy().to_string();
+        let kind = self.engine_for(&cfg_path_str);
         
         // Check cache first (DashMap allows concurrent reads without blocking)
         if let Some(mut cached) = self.cache.get_mut(&cfg_path_str) {
-            cached.last_accessed = Instant::now();
-            return Ok((cached.synth.clone(), cached.sample_rate));
+            if cached.kind == kind {
+                cached.last_accessed = Instant::now();
+                return Ok(VoiceHandle::from_cached(&cached));
+            }
         }
         
-        // Not in cache, load it
-        let sample_rate = Self::read_sample_rate(&cfg_path)?;
-        let model = piper_rs::from_config_path(cfg_path.as_ref())
-            .map_err(|e| anyhow::anyhow!("piper load error: {e}"))?;
-        let synth = PiperSpeechSynthesizer::new(model)?;
+        // Not in cache (or the voice moved to another engine), load the pool's instances
+        let engines = (0..self.pool.instances.max(1))
+            .map(|_| kind.load(cfg_path.as_ref()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
         
-        // Cache it with LRU eviction if needed
-        let synth_arc = Arc::new(RwLock::new(synth));
+        // Cache it, evicting least recently used models to stay within the count and memory budget
+        let memory_bytes = match kind {
+            EngineKind::Piper => model_cache::estimate_memory(cfg_path.as_ref()) * engines.len() as u64,
+            EngineKind::Mock => 0,
+        };
         let cached = CachedSynth { 
-            synth: synth_arc.clone(), 
-            sample_rate,
+            num_speakers: engines[0].num_speakers(),
+            sample_rate: engines[0].sample_rate(),
+            length_scale: engines[0].length_scale(),
+            pool: Arc::new(SynthPool::new(cfg_path_str.clone(), engines, self.pool)),
+            kind,
+            memory_bytes,
             last_accessed: Instant::now(),
         };
+        let voice = VoiceHandle::from_cached(&cached);
         
-        // If cache is full, remove least recently used entry (optimized LRU)
-        if self.cache.len() >= self.max_cache_size {
-            // Optimized: use iterator with early exit and avoid unnecessary clones
-            let mut oldest_key: Option<String> = None;
-            let mut oldest_time = Instant::now();
-            
-            // Find least recently used entry (single pass, O(n) but optimized)
-            for entry in self.cache.iter() {
-                let access_time = entry.last_accessed;
-                if access_time < oldest_time {
-                    oldest_time = access_time;
-                    // Only clone when we find a candidate (reduces allocations)
-                    oldest_key = Some(entry.key().clone());
-                }
-            }
-            
-            if let Some(key) = oldest_key {
-                self.cache.remove(&key);
-            }
+        for key in model_cache::evictions(self.model_usage(), &self.model_cache, Some(memory_bytes), Instant::now()) {
+            self.cache.remove(&key);
         }
         
         self.cache.insert(cfg_path_str, cached);
         
-        Ok((synth_arc, sample_rate))
+        Ok(voice)
+    }
+
+    /// Engine instances per voice and how many requests run and wait at once; applies to
+    /// voices loaded afterwards
+    pub fn with_pool(mut self, config: PoolConfig) -> Self {
+        self.pool = config;
+        self
+    }
+
+    /// Utilization and queue wait of the loaded voices' pools, busiest first
+    pub fn pool_stats(&self) -> Vec<PoolStats> {
+        let mut stats: Vec<PoolStats> = self.cache.iter().map(|entry| entry.pool.stats()).collect();
+        stats.sort_by(|a, b| b.utilization.total_cmp(&a.utilization).then_with(|| a.config.cmp(&b.config)));
+        stats
+    }
+
+    /// Engine of all voices that do not set `"engine"` in map.json (default: Piper)
+    pub fn with_engine(mut self, engine: EngineKind) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Engine a voice config runs on
+    pub fn engine_for(&self, cfg_path: &str) -> EngineKind {
+        self.voices().engine_for(cfg_path, self.engine)
+    }
+
+    // (config path, memory, last access) of the loaded models
+    fn model_usage(&self) -> Vec<(String, u64, Instant)> {
+        self.cache
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.memory_bytes, entry.last_accessed))
+            .collect()
+    }
+
+    /// Limit the synthesizer cache by model count, approximate memory and idle time
+    pub fn with_model_cache(mut self, config: ModelCacheConfig) -> Self {
+        self.model_cache = config;
+        self
+    }
+
+    /// Models currently loaded, most recently used first
+    pub fn loaded_models(&self) -> Vec<LoadedModel> {
+        let now = Instant::now();
+        let mut models: Vec<LoadedModel> = self
+            .cache
+            .iter()
+            .map(|entry| LoadedModel {
+                config: entry.key().clone(),
+                memory_bytes: entry.memory_bytes,
+                sample_rate: entry.sample_rate,
+                num_speakers: entry.num_speakers,
+                idle_secs: now.duration_since(entry.last_accessed).as_secs(),
+            })
+            .collect();
+        models.sort_by_key(|model| model.idle_secs);
+        models
+    }
+
+    pub fn model_cache_config(&self) -> ModelCacheConfig {
+        self.model_cache
     }
 
-    /// Build a Piper synthesizer from a config path (legacy method, now uses cache)
+    /// Unload models idle past the TTL (and any over the budget); returns their config paths.
+    /// Requests still using an unloaded model finish normally.
+    pub fn unload_idle_models(&self) -> Vec<String> {
+        let unloaded = model_cache::evictions(self.model_usage(), &self.model_cache, None, Instant::now());
+        for key in &unloaded {
+            self.cache.remove(key);
+        }
+        unloaded
+    }
+
+    /// Build a Piper synthesizer from a config path (legacy method, bypasses the cache and the
+    /// configured engine)
     /// Note: This creates a new synthesizer each time for compatibility.
-    /// For better performance, use get_or_create_synth directly.
+    /// For better performance, use the cached voices of the synthesis methods.
     pub fn build_synth<P: AsRef<Path>>(
         &self,
         cfg_path: P,
@@ -351,17 +744,24 @@ impl TtsManager {
             return Ok(cached.sample_rate);
         }
         
-        // Load and cache (reuse get_or_create_synth logic)
-        let (_, sample_rate) = self.get_or_create_synth(&cfg_path)?;
-        Ok(sample_rate)
+        // Load and cache
+        Ok(self.get_or_create_voice(&cfg_path)?.sample_rate)
     }
 
     /// Generate cache key for response cache using faster ahash
-    fn cache_key(text: &str, lang_opt: Option<&str>, voice_opt: Option<&str>) -> u64 {
+    fn cache_key(
+        text: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+        kind: InputKind,
+    ) -> u64 {
         let mut hasher = AHasher::default();
+        kind.hash(&mut hasher);
         text.hash(&mut hasher);
         lang_opt.hash(&mut hasher);
         voice_opt.hash(&mut hasher);
+        options.for_cache_key().hash(&mut hasher);
         hasher.finish()
     }
 
@@ -375,30 +775,18 @@ impl TtsManager {
         &self,
         text: &str,
         lang_opt: Option<&str>,
-        _speaker_override: Option<i64>, // kept for future use
+        speaker_override: Option<i64>, // speaker ID for multi-speaker voices
         voice_opt: Option<&str>, // voice ID (e.g., "norman", "thorsten")
     ) -> anyhow::Result<Vec<f32>> {
-        let (cfg_path, _default_speaker) = self.config_for(lang_opt, voice_opt)?;
+        let (cfg_path, default_speaker) = self.config_for(lang_opt, voice_opt)?;
 
         // Get or create cached synthesizer
-        let (synth_arc, _) = self.get_or_create_synth(&cfg_path)?;
-        // Use map_err to handle lock poisoning gracefully instead of panicking
-        let synth = synth_arc.read()
-            .map_err(|_| anyhow::anyhow!("Synthesizer lock poisoned - this indicates a previous panic. Please restart the server."))?;
-
-        // In your piper-rs version, pass None (no public speaker selection)
-        let iter: PiperSpeechStreamParallel = synth
-            .synthesize_parallel(text.to_string(), None)
-            .map_err(|e| anyhow::anyhow!("piper synth error: {e}"))?;
-
-        let mut samples: Vec<f32> = Vec::new();
-        for part in iter {
-            samples.extend(
-                part.map_err(|e| anyhow::anyhow!("chunk error: {e}"))?
-                    .into_vec(),
-            );
-        }
-        Ok(samples)
+        let voice = self.get_or_create_voice(&cfg_path)?;
+        let speaker = voice.resolve_speaker(speaker_override.or(default_speaker))?;
+        let prosody = self.prosody_defaults_for(lang_opt, voice_opt);
+        // Lexicon entries match the normalized text, as on the other paths
+        let text = Self::prepare_text(text, lang_opt.unwrap_or("de_DE"));
+        voice.synthesize_pieces(&self.lexicon_for(lang_opt, voice_opt).apply(&text), speaker, prosody)
     }
 
     
@@ -407,315 +795,580 @@ impl TtsManager {
         &self,
         text: &str,
         lang_opt: Option<&str>,
-        _speaker_override: Option<i64>, // kept for future use
+        speaker_override: Option<i64>, // speaker ID for multi-speaker voices
         voice_opt: Option<&str>, // voice ID (e.g., "norman", "thorsten")
     ) -> anyhow::Result<(Vec<f32>, u32)> {
         // Use enhanced synthesis with pauses for more natural speech
-        self.synthesize_with_pauses(text, lang_opt, voice_opt)
+        let (samples, sample_rate, _) = self.synthesize_with_pauses(text, lang_opt, voice_opt, &SynthesisOptions::with_speaker(speaker_override))?;
+        Ok((samples, sample_rate))
+    }
+
+    /// Synthesize with per-request options and return samples along with sample rate
+    pub fn synthesize_with_options(
+        &self,
+        text: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<(Vec<f32>, u32)> {
+        let (samples, sample_rate, _) = self.synthesize_with_pauses(text, lang_opt, voice_opt, options)?;
+        Ok((samples, sample_rate))
     }
 
     /// Synthesize text with natural pauses at commas and sentence endings
-    /// Splits text at punctuation, synthesizes chunks separately, and inserts silence
+    /// Splits text at punctuation, synthesizes chunks separately, and joins them with the
+    /// voice's pauses (trimmed, faded or crossfaded; see `PauseConfig`)
     fn synthesize_with_pauses(
         &self,
         text: &str,
         lang_opt: Option<&str>,
         voice_opt: Option<&str>,
-    ) -> anyhow::Result<(Vec<f32>, u32)> {
-        let (cfg_path, _default_speaker) = self.config_for(lang_opt, voice_opt)?;
-        let sample_rate = self.get_sample_rate(&cfg_path)?;
-        let (synth_arc, _) = self.get_or_create_synth(&cfg_path)?;
-        let synth = synth_arc.read()
-            .map_err(|_| anyhow::anyhow!("Synthesizer lock poisoned - this indicates a previous panic. Please restart the server."))?;
-
-        // Split text into chunks at punctuation for natural pauses
-        let chunks = Self::split_text_with_pauses(text);
-        
-        if chunks.is_empty() {
-            return Ok((Vec::new(), sample_rate));
-        }
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<(Vec<f32>, u32, Alignment)> {
+        let chunks = self.synthesize_chunks(text, lang_opt, voice_opt, options)?;
+        let sample_rate = chunks.sample_rate();
 
         let mut all_samples: Vec<f32> = Vec::new();
-        
-        // Synthesize each chunk separately and add pauses between them
-        for (i, chunk) in chunks.iter().enumerate() {
-            let chunk = chunk.trim();
-            if chunk.is_empty() {
-                continue;
-            }
+        let mut alignment = Alignment::default();
+        for chunk in chunks {
+            let mut chunk = chunk?;
+            let offset_ms = (all_samples.len() as u64 * 1000) / sample_rate as u64;
+            alignment.push_chunk(text, std::mem::take(&mut chunk.words), offset_ms, chunk.duration_ms());
+            all_samples.extend(chunk.samples);
+        }
 
-            // Synthesize this chunk
-            let iter: PiperSpeechStreamParallel = synth
-                .synthesize_parallel(chunk.to_string(), None)
-                .map_err(|e| anyhow::anyhow!("piper synth error: {e}"))?;
+        Ok((all_samples, sample_rate, alignment))
+    }
 
-            let mut chunk_samples: Vec<f32> = Vec::new();
-            for part in iter {
-                chunk_samples.extend(
-                    part.map_err(|e| anyhow::anyhow!("chunk error: {e}"))?
-                        .into_vec(),
-                );
-            }
+    /// Incremental synthesis: returns a blocking iterator that synthesizes one sentence or
+    /// clause per step (`options.parallelism` at once) and yields the pauses between them as
+    /// separate chunks.
+    /// Unset prosody fields fall back to the voice defaults from map.json.
+    pub fn synthesize_chunks(
+        &self,
+        text: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<SpeechChunks> {
+        let (cfg_path, default_speaker) = self.config_for(lang_opt, voice_opt)?;
+        let voice = self.get_or_create_voice(&cfg_path)?;
+        let speaker = voice.resolve_speaker(options.speaker.or(default_speaker))?;
+        let prosody = options.prosody.or(self.prosody_defaults_for(lang_opt, voice_opt));
 
-            // Add chunk audio
-            all_samples.extend(chunk_samples);
+        // Spell out abbreviations, numbers, dates and units, then split at punctuation for natural pauses
+        let language = lang_opt.unwrap_or("de_DE");
+        let original = text;
+        let text = Self::prepare_text(text, language);
+        let chunks = Self::split_text_with_pauses(&text, language);
 
-            // Add pause after chunk (except for the last chunk)
-            if i < chunks.len() - 1 {
-                let pause_duration_ms = Self::get_pause_duration(&chunks[i]);
-                let pause_samples = (pause_duration_ms as f32 / 1000.0 * sample_rate as f32) as usize;
-                all_samples.extend(vec![0.0; pause_samples]);
-            }
+        let mut speech = SpeechChunks::new(voice, speaker, prosody, chunks)
+            .with_lexicon(self.lexicon_for(lang_opt, voice_opt))
+            .with_pauses(self.pauses_for(lang_opt, voice_opt))
+            .with_silence(options.silence)
+            .parallel(options.parallelism);
+        if options.alignment {
+            speech = speech.with_alignment(WordMap::new(original, &text));
+        }
+        if let Some(rate) = options.sample_rate {
+            speech = speech.resample_to(rate);
+        }
+        if options.expressive {
+            speech = speech.expressive(self.emotion_detector_for(lang_opt.unwrap_or("de_DE")));
         }
+        Ok(speech)
+    }
+
+    /// Async streaming synthesis: chunks are yielded as soon as they are synthesized,
+    /// tagged with sequence numbers. Dropping the stream stops the remaining work.
+    /// Must be called from within a Tokio runtime.
+    pub fn synthesize_stream(
+        &self,
+        text: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: SynthesisOptions,
+    ) -> SynthesisStream {
+        let manager = self.clone();
+        let text = text.to_string();
+        let lang_opt = lang_opt.map(|s| s.to_string());
+        let voice_opt = voice_opt.map(|s| s.to_string());
 
-        Ok((all_samples, sample_rate))
+        SynthesisStream::spawn(move || {
+            manager.synthesize_chunks(&text, lang_opt.as_deref(), voice_opt.as_deref(), &options)
+        })
     }
 
-    /// Split text into chunks at punctuation marks for natural pauses
-    fn split_text_with_pauses(text: &str) -> Vec<String> {
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-        let chars: Vec<char> = text.chars().collect();
-        
-        let mut i = 0;
-        while i < chars.len() {
-            current_chunk.push(chars[i]);
-            
-            // Check if this is a punctuation mark that should trigger a pause
-            match chars[i] {
-                // Sentence endings: longer pause
-                '.' | '!' | '?' => {
-                    // Check if this is an abbreviation (e.g., "Dr.", "Mr.", "etc.")
-                    let is_abbrev = if i >= 2 && i + 1 < chars.len() && chars[i + 1] != ' ' {
-                        let start = i.saturating_sub(3);
-                        let end = (i + 1).min(chars.len());
-                        let context: String = chars[start..end].iter().collect();
-                        context.ends_with("Dr.") || context.ends_with("Mr.") || 
-                        context.ends_with("Mrs.") || context.ends_with("Ms.") ||
-                        context.ends_with("Prof.") || context.ends_with("etc.") ||
-                        context.ends_with("vs.") || context.ends_with("e.g.") ||
-                        context.ends_with("i.e.") || context.ends_with("a.m.") ||
-                        context.ends_with("p.m.") || context.ends_with("Inc.") ||
-                        context.ends_with("Ltd.") || context.ends_with("Corp.")
-                    } else {
-                        false
-                    };
-                    
-                    if !is_abbrev {
-                        // Include following space if present
-                        if i + 1 < chars.len() && chars[i + 1] == ' ' {
-                            current_chunk.push(chars[i + 1]);
-                            i += 1;
-                        }
-                        chunks.push(current_chunk.clone());
-                        current_chunk.clear();
+    /// Render a parsed SSML document segment by segment. Text segments are normalized but
+    /// not split (no punctuation splitting or implicit pauses); silences come only from
+    /// `<break>`, `<s>` and `<p>`. Voice switches resolve within the segment's language, and
+    /// segments are resampled to `options.sample_rate` (default: the first voice's rate).
+    /// The request speaker applies to the request voice only; other voices use their default.
+    pub fn synthesize_ssml(
+        &self,
+        document: &SsmlDocument,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<(Vec<f32>, u32)> {
+        let mut samples: Vec<f32> = Vec::new();
+        let mut sample_rate: Option<u32> = options.sample_rate;
+        // Leading silence is buffered until the output sample rate is known
+        let mut pending_silence_ms: u32 = 0;
+
+        for segment in &document.segments {
+            match segment {
+                SsmlSegment::Break { duration_ms } => {
+                    let duration_ms = options.silence.cap_pause_ms(*duration_ms);
+                    match sample_rate {
+                        Some(rate) => samples.extend(std::iter::repeat_n(0.0, (rate as u64 * duration_ms as u64 / 1000) as usize)),
+                        None => pending_silence_ms += duration_ms,
                     }
                 }
-                // Commas: short pause
-                // Skip commas in numbers (e.g., "1,000" or "3,14" for European decimal)
-                ',' => {
-                    // Check if comma is in a number context (digit before and after)
-                    let is_number_comma = if i > 0 && i + 1 < chars.len() {
-                        let prev_char = chars[i - 1];
-                        let next_char = chars[i + 1];
-                        (prev_char.is_ascii_digit() && next_char.is_ascii_digit()) ||
-                        (prev_char.is_whitespace() && i > 1 && chars[i - 2].is_ascii_digit())
+                SsmlSegment::Text { text, language, voice, rate } => {
+                    let seg_lang = language.as_deref().or(lang_opt);
+                    let seg_voice = voice.as_deref().or(if language.is_some() { None } else { voice_opt });
+                    let is_request_voice = language.is_none() && voice.is_none();
+
+                    let (cfg_path, default_speaker) = self.config_for(seg_lang, seg_voice)?;
+                    let handle = self.get_or_create_voice(&cfg_path)?;
+                    let speaker = if is_request_voice {
+                        handle.resolve_speaker(options.speaker.or(default_speaker))?
                     } else {
-                        false
+                        handle.resolve_speaker(default_speaker)?
                     };
-                    
-                    if !is_number_comma {
-                        // Include following space if present
-                        if i + 1 < chars.len() && chars[i + 1] == ' ' {
-                            current_chunk.push(chars[i + 1]);
-                            i += 1;
-                        }
-                        chunks.push(current_chunk.clone());
-                        current_chunk.clear();
+
+                    let request_prosody = if is_request_voice { options.prosody } else { Prosody::default() };
+                    let mut prosody = request_prosody.or(self.prosody_defaults_for(seg_lang, seg_voice));
+                    if (*rate - 1.0).abs() > f32::EPSILON {
+                        prosody.length_scale = Some(prosody.length_scale.unwrap_or(handle.length_scale) / rate);
                     }
-                }
-                // Semicolons and colons: medium pause
-                ';' | ':' => {
-                    // Include following space if present
-                    if i + 1 < chars.len() && chars[i + 1] == ' ' {
-                        current_chunk.push(chars[i + 1]);
-                        i += 1;
+
+                    let text = Self::prepare_text(text.trim(), seg_lang.unwrap_or("de_DE"));
+                    let pieces = self.lexicon_for(seg_lang, seg_voice).apply(&text);
+                    let mut audio = handle.synthesize_pieces(&pieces, speaker, prosody)?;
+                    silence::remove_silence(&mut audio, handle.sample_rate, &options.silence);
+                    let out_rate = *sample_rate.get_or_insert(handle.sample_rate);
+                    if pending_silence_ms > 0 {
+                        samples.extend(std::iter::repeat_n(0.0, (out_rate as u64 * pending_silence_ms as u64 / 1000) as usize));
+                        pending_silence_ms = 0;
                     }
-                    chunks.push(current_chunk.clone());
-                    current_chunk.clear();
+                    if handle.sample_rate != out_rate {
+                        audio = resample(&audio, handle.sample_rate, out_rate);
+                    }
+                    samples.extend(audio);
                 }
-                _ => {}
             }
-            
-            i += 1;
         }
-        
-        // Add remaining chunk
-        if !current_chunk.trim().is_empty() {
-            chunks.push(current_chunk);
+
+        let sample_rate = match sample_rate {
+            Some(rate) => rate,
+            // Only breaks: render silence at the request voice's rate
+            None => {
+                let (cfg_path, _) = self.config_for(lang_opt, voice_opt)?;
+                let rate = self.get_sample_rate(&cfg_path)?;
+                samples.extend(std::iter::repeat_n(0.0, (rate as u64 * pending_silence_ms as u64 / 1000) as usize));
+                rate
+            }
+        };
+
+        Ok((samples, sample_rate))
+    }
+
+    /// Phonemes the voice would speak for `text`, one string per synthesized chunk. The text
+    /// goes through the same normalization, splitting and lexicon as `synthesize_chunks`.
+    pub fn phonemize(&self, text: &str, lang_opt: Option<&str>, voice_opt: Option<&str>) -> anyhow::Result<Vec<String>> {
+        let (cfg_path, _) = self.config_for(lang_opt, voice_opt)?;
+        let voice = self.get_or_create_voice(&cfg_path)?;
+        let lexicon = self.lexicon_for(lang_opt, voice_opt);
+
+        let language = lang_opt.unwrap_or("de_DE");
+        let text = Self::prepare_text(text, language);
+        Self::split_text_with_pauses(&text, language)
+            .iter()
+            .map(|chunk| chunk.trim())
+            .filter(|chunk| !chunk.is_empty())
+            .map(|chunk| Ok(voice.phonemize(&lexicon.apply(chunk))?.join(" ")))
+            .collect()
+    }
+
+    /// Synthesize phonemes as given (eSpeak IPA as returned by `phonemize`, one sentence per
+    /// line), bypassing normalization, lexicons and the phonemizer. Lines are separated by the
+    /// pause their final punctuation calls for.
+    pub fn synthesize_phonemes(
+        &self,
+        phonemes: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<(Vec<f32>, u32)> {
+        let (cfg_path, default_speaker) = self.config_for(lang_opt, voice_opt)?;
+        let voice = self.get_or_create_voice(&cfg_path)?;
+        let speaker = voice.resolve_speaker(options.speaker.or(default_speaker))?;
+        let prosody = options.prosody.or(self.prosody_defaults_for(lang_opt, voice_opt));
+
+        let pauses = self.pauses_for(lang_opt, voice_opt);
+        let mut joiner = ChunkJoiner::new(pauses, voice.sample_rate);
+
+        let sentences: Vec<&str> = phonemes.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let mut samples: Vec<f32> = Vec::new();
+        for (i, sentence) in sentences.iter().enumerate() {
+            let mut audio = joiner.trim(voice.synthesize_pieces(&[Piece::Phonemes(sentence.to_string())], speaker, prosody)?);
+            silence::remove_silence(&mut audio, voice.sample_rate, &options.silence);
+            let pause_ms = (i < sentences.len() - 1).then(|| pauses.pause_ms(sentence));
+            samples.extend(joiner.join(audio, pause_ms));
+            if let Some(pause_ms) = pause_ms {
+                let pause_ms = options.silence.cap_pause_ms(pause_ms);
+                samples.extend(std::iter::repeat_n(0.0, (voice.sample_rate as u64 * pause_ms as u64 / 1000) as usize));
+            }
         }
-        
+
+        let sample_rate = options.sample_rate.unwrap_or(voice.sample_rate);
+        Ok((resample(&samples, voice.sample_rate, sample_rate), sample_rate))
+    }
+
+    /// Expand abbreviations and verbalize numbers before the text is split
+    fn prepare_text(text: &str, language: &str) -> String {
+        normalize_text(&expand_abbreviations(text, language), language)
+    }
+
+    /// Split text into chunks at punctuation marks for natural pauses
+    fn split_text_with_pauses(text: &str, language: &str) -> Vec<String> {
+        let chunks: Vec<String> = segment_text(text, language).into_iter().map(|s| s.text).collect();
+
         // If no punctuation found, return original text as single chunk
         if chunks.is_empty() {
-            chunks.push(text.to_string());
+            return vec![text.to_string()];
         }
-        
         chunks
     }
 
-    /// Get pause duration in milliseconds based on the chunk's ending punctuation
-    fn get_pause_duration(chunk: &str) -> u32 {
-        let trimmed = chunk.trim_end();
-        if trimmed.ends_with('.') || trimmed.ends_with('!') || trimmed.ends_with('?') {
-            // Sentence endings: 300-500ms pause
-            400
-        } else if trimmed.ends_with(';') || trimmed.ends_with(':') {
-            // Semicolons and colons: 200-300ms pause
-            250
-        } else if trimmed.ends_with(',') {
-            // Commas: 100-200ms pause
-            150
-        } else {
-            // Default: 100ms
-            100
+    /// `spoken` is the text as the lexicons see it (see `CacheSource`)
+    fn cache_source(&self, text: &str, spoken: String, lang_opt: Option<&str>, voice_opt: Option<&str>, kind: InputKind) -> CacheSource {
+        CacheSource {
+            language: lang_opt.unwrap_or("de_DE").to_string(),
+            voice: self.voice_id_for(lang_opt, voice_opt),
+            text: text.to_string(),
+            spoken,
+            kind,
         }
     }
 
+    /// The text segments of an SSML document as `synthesize_ssml` hands them to the lexicons
+    fn ssml_spoken_text(document: &SsmlDocument, lang_opt: Option<&str>) -> String {
+        document
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                SsmlSegment::Text { text, language, .. } => {
+                    Some(Self::prepare_text(text.trim(), language.as_deref().or(lang_opt).unwrap_or("de_DE")))
+                }
+                SsmlSegment::Break { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Digest of everything besides the voice files that determines a response: the request,
+    /// the voice defaults from map.json and the lexicon entries that the normalized input mentions.
+    /// Stable across restarts, for the disk cache.
+    fn disk_request_digest(&self, source: &CacheSource, options: &SynthesisOptions, loudness_target: &LoudnessTarget) -> [u8; 32] {
+        let lang_opt = Some(source.language.as_str());
+        let voice_opt = source.voice.as_deref();
+        let mut hasher = StableHasher::new();
+        source.kind.hash(&mut hasher);
+        source.text.hash(&mut hasher);
+        source.language.hash(&mut hasher);
+        source.voice.hash(&mut hasher);
+        options.for_cache_key().hash(&mut hasher);
+        loudness_target.hash(&mut hasher);
+        self.prosody_defaults_for(lang_opt, voice_opt).hash(&mut hasher);
+        self.pauses_for(lang_opt, voice_opt).hash(&mut hasher);
+        let config = self.config_for(lang_opt, voice_opt).ok();
+        config.as_ref().map(|(_, speaker)| *speaker).hash(&mut hasher);
+        config.map(|(path, _)| self.engine_for(&path)).hash(&mut hasher);
+        let lexicon_entries = match source.kind {
+            InputKind::Text => self.lexicon_for(lang_opt, voice_opt).entries(),
+            // SSML can switch language and voice mid-document
+            InputKind::Ssml => self.lexicons().into_iter().flat_map(|(_, entries)| entries).collect(),
+            InputKind::Phonemes => Vec::new(),
+        };
+        for entry in lexicon_entries.iter().filter(|entry| lexicon::mentions(&source.spoken, &entry.word)) {
+            entry.hash(&mut hasher);
+        }
+        hasher.digest()
+    }
+
     /// Synthesize with caching - async version for response cache
     pub async fn synthesize_with_cache(
         &self,
         text: &str,
         lang_opt: Option<&str>,
         voice_opt: Option<&str>,
-    ) -> anyhow::Result<(String, u32, u64, bool)> {
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<SynthesizedAudio> {
+        let cache_key = Self::cache_key(text, lang_opt, voice_opt, options, InputKind::Text);
+        let spoken = Self::prepare_text(text, lang_opt.unwrap_or("de_DE"));
+        let source = self.cache_source(text, spoken, lang_opt, voice_opt, InputKind::Text);
+        let loudness = options.loudness.or(self.loudness_defaults_for(lang_opt, voice_opt));
+        let text = text.to_string();
+        let lang_opt = lang_opt.map(|s| s.to_string());
+        let voice_opt = voice_opt.map(|s| s.to_string());
+        let request_options = options.clone();
+
+        self.cached_synthesis(cache_key, source, options, loudness, move |manager| {
+            let (samples, sample_rate, alignment) =
+                manager.synthesize_with_pauses(&text, lang_opt.as_deref(), voice_opt.as_deref(), &request_options)?;
+            Ok((samples, sample_rate, request_options.alignment.then_some(alignment)))
+        })
+        .await
+    }
+
+    /// SSML variant of `synthesize_with_cache`. Malformed markup fails before synthesis with
+    /// an error that downcasts to `SsmlError`.
+    pub async fn synthesize_ssml_with_cache(
+        &self,
+        ssml: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<SynthesizedAudio> {
+        let document = ssml::parse(ssml, lang_opt.unwrap_or("de_DE"))?;
+        let cache_key = Self::cache_key(ssml, lang_opt, voice_opt, options, InputKind::Ssml);
+        let source = self.cache_source(ssml, Self::ssml_spoken_text(&document, lang_opt), lang_opt, voice_opt, InputKind::Ssml);
+        let loudness = options.loudness.or(self.loudness_defaults_for(lang_opt, voice_opt));
+        let lang_opt = lang_opt.map(|s| s.to_string());
+        let voice_opt = voice_opt.map(|s| s.to_string());
+        let request_options = options.clone();
+
+        self.cached_synthesis(cache_key, source, options, loudness, move |manager| {
+            let (samples, sample_rate) = manager.synthesize_ssml(&document, lang_opt.as_deref(), voice_opt.as_deref(), &request_options)?;
+            Ok((samples, sample_rate, None))
+        })
+        .await
+    }
+
+    /// Phoneme variant of `synthesize_with_cache` (see `synthesize_phonemes`)
+    pub async fn synthesize_phonemes_with_cache(
+        &self,
+        phonemes: &str,
+        lang_opt: Option<&str>,
+        voice_opt: Option<&str>,
+        options: &SynthesisOptions,
+    ) -> anyhow::Result<SynthesizedAudio> {
+        let cache_key = Self::cache_key(phonemes, lang_opt, voice_opt, options, InputKind::Phonemes);
+        let source = self.cache_source(phonemes, String::new(), lang_opt, voice_opt, InputKind::Phonemes);
+        let loudness = options.loudness.or(self.loudness_defaults_for(lang_opt, voice_opt));
+        let phonemes = phonemes.to_string();
+        let lang_opt = lang_opt.map(|s| s.to_string());
+        let voice_opt = voice_opt.map(|s| s.to_string());
+        let request_options = options.clone();
+
+        self.cached_synthesis(cache_key, source, options, loudness, move |manager| {
+            let (samples, sample_rate) = manager.synthesize_phonemes(&phonemes, lang_opt.as_deref(), voice_opt.as_deref(), &request_options)?;
+            Ok((samples, sample_rate, None))
+        })
+        .await
+    }
+
+    /// Response cache lookup (memory, then disk); on a miss runs `synthesize`,
+    /// loudness processing and the encoding in one blocking task. Cached PCM is encoded in the requested
+    /// format on the way out. The returned sample rate is the encoded one (Opus and G.711 use
+    /// fixed rates).
+    async fn cached_synthesis<F>(
+        &self,
+        cache_key: u64,
+        source: CacheSource,
+        options: &SynthesisOptions,
+        loudness_target: LoudnessTarget,
+        synthesize: F,
+    ) -> anyhow::Result<SynthesizedAudio>
+    where
+        F: FnOnce(&TtsManager) -> anyhow::Result<(Vec<f32>, u32, Option<Alignment>)> + Send + 'static,
+    {
+        let format = options.format;
+
         // Check response cache first
-        let cache_key = Self::cache_key(text, lang_opt, voice_opt);
-        {
+        let cached = {
             let cache = self.response_cache.read().await;
-            if let Some(cached) = cache.peek(&cache_key) {
+            cache
+                .peek(&cache_key)
                 // Check if cache entry is still valid (not expired)
-                if Instant::now().duration_since(cached.cached_at) < self.response_cache_ttl {
-                    return Ok((
-                        cached.audio_base64.clone(),
-                        cached.sample_rate,
-                        cached.duration_ms,
-                        true, // cache hit
-                    ));
+                .filter(|cached| Instant::now().duration_since(cached.cached_at) < self.response_cache_ttl)
+                .cloned()
+        };
+        if let Some(cached) = cached {
+            let pcm = cached.pcm.clone();
+            let encoded = tokio::task::spawn_blocking(move || pcm.encode(cached.sample_rate, format))
+                .await
+                .map_err(|e| anyhow::anyhow!("Task join error: {e}"))??;
+            return Ok(SynthesizedAudio {
+                audio: encoded.bytes,
+                mime_type: encoded.mime_type,
+                sample_rate: encoded.sample_rate,
+                duration_ms: cached.duration_ms,
+                loudness: cached.loudness,
+                alignment: cached.alignment,
+                cache_hit: true,
+            });
+        }
+
+        let lexicon_generation = self.lexicon_generation();
+
+        // Then the disk cache (file IO and, on first use of a voice, hashing its model)
+        let mut disk_key = None;
+        if let (Some(disk_cache), Ok((cfg_path, _))) =
+            (&self.disk_cache, self.config_for(Some(&source.language), source.voice.as_deref()))
+        {
+            let disk_cache = Arc::clone(disk_cache);
+            let request = self.disk_request_digest(&source, options, &loudness_target);
+            let lookup = tokio::task::spawn_blocking(move || {
+                let key = disk_cache.key(&request, Path::new(&cfg_path)).ok()?;
+                let hit = disk_cache.get(&key).and_then(|entry| {
+                    let pcm = CachedPcm::from_bytes(&entry.audio, entry.float_samples)?;
+                    let encoded = pcm.encode(entry.sample_rate, format);
+                    Some((entry, pcm, encoded))
+                });
+                Some((key, hit))
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("Task join error: {e}"))?;
+
+            if let Some((key, hit)) = lookup {
+                if let Some((entry, pcm, encoded)) = hit {
+                    let encoded = encoded?;
+                    // Promote to the memory tier
+                    if self.lexicon_generation() == lexicon_generation {
+                        let cached_response = CachedResponse {
+                            pcm,
+                            sample_rate: entry.sample_rate,
+                            duration_ms: entry.duration_ms,
+                            loudness: entry.loudness,
+                            alignment: entry.alignment.clone(),
+                            cached_at: Instant::now(),
+                            source,
+                        };
+                        self.response_cache.write().await.put(cache_key, cached_response);
+                    }
+                    return Ok(SynthesizedAudio {
+                        audio: encoded.bytes,
+                        mime_type: encoded.mime_type,
+                        sample_rate: encoded.sample_rate,
+                        duration_ms: entry.duration_ms,
+                        loudness: entry.loudness,
+                        alignment: entry.alignment,
+                        cache_hit: true,
+                    });
                 }
+                disk_key = Some(key);
             }
         }
 
         // Cache miss - synthesize and encode in a single blocking task (reduces overhead)
-        // Clone only the data we need, not the entire manager with async types
-        let text = text.to_string();
-        let lang_opt = lang_opt.map(|s| s.to_string());
-        let voice_opt = voice_opt.map(|s| s.to_string());
-        
         // Clone the manager's data structures needed for synthesis
-        let map = self.map.clone();
-        let voices_map = self.voices_map.clone();
+        // The voice map as of this request, even if map.json is reloaded meanwhile
+        let voices = Arc::new(RwLock::new(self.voices()));
         let cache = Arc::clone(&self.cache);
-        let max_cache_size = self.max_cache_size;
+        let model_cache = self.model_cache;
+        let engine = self.engine;
+        let pool = self.pool;
+        let emotion_detectors = Arc::clone(&self.emotion_detectors);
+        let lexicons = Arc::clone(&self.lexicons);
         
         // Combined blocking task: synthesize + encode in one go (faster, less overhead)
-        let (audio_base64, sample_rate, duration_ms) = tokio::task::spawn_blocking(move || {
+        let (pcm, encoded, sample_rate, duration_ms, loudness, alignment) = tokio::task::spawn_blocking(move || {
             // Create a temporary manager for blocking synthesis
             // This avoids cloning async types (TokioRwLock)
             let temp_manager = TtsManager {
-                map,
-                voices_map,
+                voices,
                 cache,
-                max_cache_size,
-                response_cache: Arc::new(TokioRwLock::new(LruCache::new(std::num::NonZeroUsize::new(1).unwrap()))), // Dummy cache, not used
+                model_cache,
+                engine,
+                pool,
+                response_cache: Arc::new(TokioRwLock::new(ResponseCache::new(0))), // Dummy cache, not used
                 response_cache_ttl: Duration::from_secs(3600), // Dummy, not used
+                disk_cache: None, // Not used
+                emotion_detectors,
+                lexicons,
             };
             
             // Synthesize audio
-            let (samples, sample_rate) = temp_manager.synthesize_with_sample_rate(
-                &text,
-                lang_opt.as_deref(),
-                None,
-                voice_opt.as_deref()
-            )?;
+            // Silence is already removed (per chunk), so the duration describes the output
+            let (mut samples, sample_rate, alignment) = synthesize(&temp_manager)?;
+
+            // Measure loudness, normalize and limit (measure only without a target)
+            let loudness = loudness::normalize(&mut samples, sample_rate, &loudness_target);
             
             // Calculate duration
             let sample_rate_f32 = sample_rate as f32;
             let duration_ms = (samples.len() as f32 / sample_rate_f32 * 1000.0) as u64;
             
-            // Encode to WAV base64 (in same task, no extra cloning needed)
-            let audio_base64 = Self::encode_wav_base64(&samples, sample_rate)?;
+            // Encode from the PCM that gets cached, so cache hits return the same bytes
+            let pcm = CachedPcm::new(&samples, format);
+            let encoded = pcm.encode(sample_rate, format)?;
             
-            Ok::<(String, u32, u64), anyhow::Error>((audio_base64, sample_rate, duration_ms))
+            Ok::<(CachedPcm, EncodedAudio, u32, u64, LoudnessReport, Option<Alignment>), anyhow::Error>((pcm, encoded, sample_rate, duration_ms, loudness, alignment))
         })
         .await
         .map_err(|e| anyhow::anyhow!("Task join error: {e}"))?
         .map_err(|e| anyhow::anyhow!("Synthesis/encoding error: {e}"))?;
 
-        // Cache the result
-        let cached_response = CachedResponse {
-            audio_base64: audio_base64.clone(),
-            sample_rate,
-            duration_ms,
-            cached_at: Instant::now(),
-        };
+        // Skip caching if a lexicon changed during synthesis (the result may be stale)
+        if self.lexicon_generation() == lexicon_generation {
+            if let (Some(disk_cache), Some(key)) = (&self.disk_cache, disk_key) {
+                let disk_cache = Arc::clone(disk_cache);
+                let entry = DiskEntry {
+                    sample_rate,
+                    duration_ms,
+                    loudness,
+                    alignment: alignment.clone(),
+                    float_samples: pcm.is_float(),
+                    audio: pcm.to_bytes(),
+                };
+                // Written in the background so the response does not wait for the fsync; a
+                // failed write only costs a future cache hit
+                drop(tokio::task::spawn_blocking(move || disk_cache.put(&key, &entry)));
+            }
 
-        {
+            let cached_response = CachedResponse {
+                pcm,
+                sample_rate,
+                duration_ms,
+                loudness,
+                alignment: alignment.clone(),
+                cached_at: Instant::now(),
+                source,
+            };
             let mut cache = self.response_cache.write().await;
             cache.put(cache_key, cached_response);
         }
 
-        Ok((audio_base64, sample_rate, duration_ms, false)) // cache miss
+        Ok(SynthesizedAudio {
+            audio: encoded.bytes,
+            mime_type: encoded.mime_type,
+            sample_rate: encoded.sample_rate,
+            duration_ms,
+            loudness,
+            alignment,
+            cache_hit: false,
+        })
     }
 
     /// Preload frequently used models
     pub fn preload_models(&self, languages: &[&str]) -> anyhow::Result<()> {
         for lang in languages {
             if let Ok((cfg_path, _)) = self.config_for(Some(lang), None) {
-                let _ = self.get_or_create_synth(&cfg_path)?;
+                let _ = self.get_or_create_voice(&cfg_path)?;
             }
         }
         Ok(())
     }
 
 
-    /// Convenience: WAV base64 (optimized with pre-allocated buffer)
+    /// Convenience: 16-bit WAV base64
     pub fn encode_wav_base64(samples: &[f32], sample_rate: u32) -> anyhow::Result<String> {
-        use std::io::Cursor;
-        use base64::Engine; // enables `.encode(...)`
-
-        let spec = hound::WavSpec {
-            channels: 1,
-            sample_rate,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
-
-        // Pre-allocate buffer: WAV header (44 bytes) + samples (2 bytes per sample)
-        // This reduces reallocations during writing
-        let estimated_size = 44 + (samples.len() * 2);
-        let mut cursor = Cursor::new(Vec::<u8>::with_capacity(estimated_size));
-        
-        {
-            let mut writer = hound::WavWriter::new(&mut cursor, spec)
-                .map_err(|e| anyhow::anyhow!("wav write err: {e}"))?;
-
-            // Optimized: batch write samples (hound handles buffering internally)
-            // Pre-compute conversion constants for better performance
-            const I16_MAX_F32: f32 = i16::MAX as f32;
-            
-            for &s in samples {
-                // Clamp and convert f32 [-1.0, 1.0] -> i16
-                // Using clamp is clear and optimized by the compiler
-                let v = (s.clamp(-1.0, 1.0) * I16_MAX_F32) as i16;
-                writer
-                    .write_sample(v)
-                    .map_err(|e| anyhow::anyhow!("wav sample err: {e}"))?;
-            }
-            // `writer` drops here, which finalizes the WAV header/footer
-        }
-
-        let buf = cursor.into_inner();
-        Ok(base64::engine::general_purpose::STANDARD.encode(buf))
+        let encoded = AudioFormat::Wav.encode(samples, sample_rate)?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(encoded.bytes))
     }
 
 
@@ -791,3 +1444,79 @@ impl TtsManager {
     }
 
 }
+
+/// A cached engine pool resolved for one request
+#[derive(Clone)]
+pub(crate) struct VoiceHandle {
+    pool: Arc<SynthPool>,
+    num_speakers: u32,
+    pub(crate) sample_rate: u32,
+    // Model default, scaled by speaking rate changes
+    pub(crate) length_scale: f32,
+}
+
+impl VoiceHandle {
+    fn from_cached(cached: &CachedSynth) -> Self {
+        Self {
+            pool: cached.pool.clone(),
+            num_speakers: cached.num_speakers,
+            sample_rate: cached.sample_rate,
+            length_scale: cached.length_scale,
+        }
+    }
+
+    /// Check a requested speaker ID against the model's speaker count.
+    /// Returns `None` for single-speaker models so synthesis can keep the shared read path.
+    /// Multi-speaker models always get a speaker (0 unless one is requested): Piper keeps the
+    /// last speaker set, so a request without one would otherwise get the previous request's.
+    fn resolve_speaker(&self, speaker: Option<i64>) -> anyhow::Result<Option<i64>> {
+        match speaker {
+            Some(sid) if sid < 0 || sid >= self.num_speakers as i64 => Err(anyhow::anyhow!(
+                "Speaker {} out of range (voice has {} speaker(s))",
+                sid,
+                self.num_speakers
+            )),
+            _ if self.num_speakers > 1 => Ok(Some(speaker.unwrap_or(0))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Synthesize text with lexicon entries applied. Text without phoneme entries goes through
+    /// the normal path; otherwise the text pieces are phonemized and spoken together with the
+    /// lexicon phonemes as one sentence.
+    pub(crate) fn synthesize_pieces(&self, pieces: &[Piece], speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+        Self::synthesize_pieces_with(&*self.pool.acquire()?, pieces, speaker, prosody)
+    }
+
+    /// `synthesize_pieces` on an instance already taken from the pool
+    pub(crate) fn synthesize_pieces_with(
+        engine: &dyn TtsEngine,
+        pieces: &[Piece],
+        speaker: Option<i64>,
+        prosody: Prosody,
+    ) -> anyhow::Result<Vec<f32>> {
+        if let [Piece::Text(text)] = pieces {
+            return engine.synthesize(text, speaker, prosody);
+        }
+        let phonemes = Self::phonemize_with(engine, pieces)?;
+        engine.speak_phonemes(&phonemes.join(" "), speaker, prosody)
+    }
+
+    /// Phonemes for the text pieces (one string per sentence found by the phonemizer);
+    /// phoneme pieces are passed through
+    pub(crate) fn phonemize(&self, pieces: &[Piece]) -> anyhow::Result<Vec<String>> {
+        Self::phonemize_with(&*self.pool.acquire()?, pieces)
+    }
+
+    fn phonemize_with(engine: &dyn TtsEngine, pieces: &[Piece]) -> anyhow::Result<Vec<String>> {
+        let mut phonemes: Vec<String> = Vec::new();
+        for piece in pieces {
+            match piece {
+                Piece::Text(text) if text.trim().is_empty() => {}
+                Piece::Text(text) => phonemes.extend(engine.phonemize(text)?),
+                Piece::Phonemes(p) => phonemes.push(p.clone()),
+            }
+        }
+        Ok(phonemes)
+    }
+}
@@ -0,0 +1,294 @@
+//! Loudness measurement and normalization.
+//!
+//! Integrated loudness follows ITU-R BS.1770-4 / EBU R128 (K-weighting, 400 ms blocks with
+//! 75% overlap, absolute and relative gating). Normalization applies a static gain towards
+//! the target and then a lookahead true-peak limiter working on a 4x oversampled peak estimate.
+
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use crate::resample::resample;
+
+/// True-peak ceiling used when only a loudness target is set
+const DEFAULT_TRUE_PEAK_DB: f32 = -1.0;
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+const LIMITER_LOOKAHEAD_MS: f32 = 2.0;
+const LIMITER_RELEASE_MS: f32 = 80.0;
+
+/// Loudness normalization settings (per request, or per voice in map.json).
+/// `None` fields fall back to the voice default; with neither set the audio is only measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessTarget {
+    /// Integrated loudness to normalize to (LUFS), e.g. -16.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_lufs: Option<f32>,
+    /// True-peak ceiling (dBTP) for the limiter, e.g. -1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub true_peak_db: Option<f32>,
+}
+
+impl LoudnessTarget {
+    /// Fill unset fields from `defaults`
+    pub fn or(self, defaults: LoudnessTarget) -> Self {
+        Self {
+            target_lufs: self.target_lufs.or(defaults.target_lufs),
+            true_peak_db: self.true_peak_db.or(defaults.true_peak_db),
+        }
+    }
+
+    /// Read per-voice defaults from a map.json voice object
+    pub(crate) fn from_json(obj: &serde_json::Map<String, serde_json::Value>) -> Self {
+        let field = |key: &str| obj.get(key).and_then(|x| x.as_f64()).map(|x| x as f32);
+        Self {
+            target_lufs: field("target_lufs"),
+            true_peak_db: field("true_peak_db"),
+        }
+    }
+}
+
+// f32 has no Hash impl; hash the bit patterns so targets can be part of cache keys
+impl Hash for LoudnessTarget {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.target_lufs.map(f32::to_bits).hash(state);
+        self.true_peak_db.map(f32::to_bits).hash(state);
+    }
+}
+
+/// Loudness before and after processing. `None` for silence (everything gated out).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessReport {
+    pub measured_lufs: Option<f32>,
+    pub output_lufs: Option<f32>,
+    /// Static gain applied towards the target (limiting not included)
+    pub gain_db: f32,
+}
+
+/// Measure, normalize and limit in place according to `target`
+pub fn normalize(samples: &mut [f32], sample_rate: u32, target: &LoudnessTarget) -> LoudnessReport {
+    let measured = integrated_loudness(samples, sample_rate);
+    let mut report = LoudnessReport {
+        measured_lufs: measured,
+        output_lufs: measured,
+        gain_db: 0.0,
+    };
+
+    if let (Some(target_lufs), Some(measured)) = (target.target_lufs, measured) {
+        report.gain_db = target_lufs - measured;
+        let gain = db_to_linear(report.gain_db);
+        samples.iter_mut().for_each(|s| *s *= gain);
+    }
+
+    let ceiling = target
+        .true_peak_db
+        .or(target.target_lufs.map(|_| DEFAULT_TRUE_PEAK_DB));
+    if let Some(ceiling_db) = ceiling {
+        limit_true_peak(samples, sample_rate, ceiling_db);
+    }
+
+    if report.gain_db != 0.0 || ceiling.is_some() {
+        report.output_lufs = integrated_loudness(samples, sample_rate);
+    }
+    report
+}
+
+/// Integrated loudness in LUFS (None when the signal is silent or gated out)
+pub fn integrated_loudness(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    if samples.is_empty() || sample_rate == 0 {
+        return None;
+    }
+
+    let weighted = k_weight(samples, sample_rate);
+    let block = (0.4 * sample_rate as f64) as usize;
+    let step = (0.1 * sample_rate as f64) as usize;
+
+    // Mean square of each gating block (a short signal is one block)
+    let powers: Vec<f64> = if weighted.len() <= block {
+        vec![mean_square(&weighted)]
+    } else {
+        (0..=(weighted.len() - block) / step)
+            .map(|i| mean_square(&weighted[i * step..i * step + block]))
+            .collect()
+    };
+
+    let above_absolute: Vec<f64> = powers
+        .into_iter()
+        .filter(|&p| power_to_lufs(p) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if above_absolute.is_empty() {
+        return None;
+    }
+
+    let relative_gate = power_to_lufs(mean(&above_absolute)) + RELATIVE_GATE_LU;
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|&p| power_to_lufs(p) > relative_gate)
+        .collect();
+    if gated.is_empty() {
+        return None;
+    }
+    Some(power_to_lufs(mean(&gated)) as f32)
+}
+
+/// Lookahead limiter keeping the (4x oversampled) peak level below `ceiling_db`
+pub fn limit_true_peak(samples: &mut [f32], sample_rate: u32, ceiling_db: f32) {
+    if samples.is_empty() {
+        return;
+    }
+    let ceiling = db_to_linear(ceiling_db);
+    let oversampled = resample(samples, sample_rate, sample_rate * 4);
+
+    // Gain each sample needs on its own, from the inter-sample peaks around it
+    let needed: Vec<f32> = (0..samples.len())
+        .map(|i| {
+            let around = &oversampled[(i * 4).saturating_sub(2)..(i * 4 + 3).min(oversampled.len())];
+            let peak = around.iter().fold(samples[i].abs(), |m, s| m.max(s.abs()));
+            if peak > ceiling { ceiling / peak } else { 1.0 }
+        })
+        .collect();
+    if needed.iter().all(|&g| g >= 1.0) {
+        return;
+    }
+
+    let lookahead = ((LIMITER_LOOKAHEAD_MS / 1000.0 * sample_rate as f32) as usize).max(1);
+    let release_step = 1.0 / (LIMITER_RELEASE_MS / 1000.0 * sample_rate as f32);
+
+    // Minimum over the lookahead window, then a slow release back towards unity
+    let mut envelope = Vec::with_capacity(needed.len());
+    let mut previous = 1.0f32;
+    for i in 0..needed.len() {
+        let window_min = needed[i..(i + lookahead + 1).min(needed.len())]
+            .iter()
+            .fold(1.0f32, |m, &g| m.min(g));
+        previous = window_min.min(previous + release_step);
+        envelope.push(previous);
+    }
+
+    // Averaging over the lookahead turns gain steps into ramps that finish before each peak
+    let mut sum = 0.0f32;
+    for i in 0..samples.len() {
+        sum += envelope[i];
+        if i > lookahead {
+            sum -= envelope[i - lookahead - 1];
+        }
+        let count = (i + 1).min(lookahead + 1) as f32;
+        let gain = (sum / count).min(needed[i]);
+        samples[i] *= gain;
+    }
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn power_to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.max(1e-20).log10()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn mean_square(samples: &[f64]) -> f64 {
+    samples.iter().map(|s| s * s).sum::<f64>() / samples.len().max(1) as f64
+}
+
+/// BS.1770 K-weighting (high shelf + high pass), coefficients derived for any sample rate
+fn k_weight(samples: &[f32], sample_rate: u32) -> Vec<f64> {
+    let fs = sample_rate as f64;
+
+    // Stage 1: high shelf (+4 dB above ~1.7 kHz)
+    let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+    let k = (std::f64::consts::PI * f0 / fs).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [(vh + vb * k / q + k * k) / a0, 2.0 * (k * k - vh) / a0, (vh - vb * k / q + k * k) / a0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    // Stage 2: high pass (~38 Hz)
+    let (f0, q) = (38.13547087602444, 0.5003270373238773);
+    let k = (std::f64::consts::PI * f0 / fs).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    let stage1 = shelf.process(samples.iter().map(|&s| s as f64));
+    high_pass.process(stage1.into_iter())
+}
+
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    /// Direct form I
+    fn process(&self, input: impl Iterator<Item = f64>) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        input
+            .map(|x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2 - self.a[0] * y1 - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, amplitude: f32, sample_rate: u32, seconds: f32) -> Vec<f32> {
+        (0..(sample_rate as f32 * seconds) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate as f32).sin() * amplitude)
+            .collect()
+    }
+
+    #[test]
+    fn test_reference_tone_loudness() {
+        // A 997 Hz full-scale sine measures -3.01 LUFS; at -20 dBFS it is -23.01 LUFS
+        for sample_rate in [16000, 22050, 48000] {
+            let lufs = integrated_loudness(&sine(997.0, 0.1, sample_rate, 3.0), sample_rate).unwrap();
+            assert!((lufs + 23.01).abs() < 0.1, "{sample_rate} Hz: {lufs} LUFS");
+        }
+    }
+
+    #[test]
+    fn test_silence_is_gated() {
+        assert_eq!(integrated_loudness(&vec![0.0; 22050], 22050), None);
+    }
+
+    #[test]
+    fn test_normalize_reaches_target() {
+        let mut samples = sine(440.0, 0.02, 22050, 2.0);
+        let report = normalize(
+            &mut samples,
+            22050,
+            &LoudnessTarget { target_lufs: Some(-16.0), true_peak_db: None },
+        );
+        assert!(report.gain_db > 10.0);
+        assert!((report.output_lufs.unwrap() + 16.0).abs() < 0.2, "{report:?}");
+    }
+
+    #[test]
+    fn test_limiter_respects_ceiling() {
+        let mut samples = sine(440.0, 0.25, 22050, 1.0);
+        // A loud burst in the middle
+        for s in &mut samples[10000..10500] {
+            *s *= 3.9;
+        }
+        limit_true_peak(&mut samples, 22050, -1.0);
+        let ceiling = db_to_linear(-1.0);
+        let peak = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak <= ceiling * 1.001, "peak {peak}");
+        // Quiet parts far from the burst are untouched
+        assert!((samples[2000] - sine(440.0, 0.25, 22050, 1.0)[2000]).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,130 @@
+//! Deterministic stand-in for a voice model.
+//!
+//! Produces a sine tone per sentence whose length follows the text (60 ms per character) and
+//! whose pitch follows the text and speaker, so the same request always gives the same
+//! samples. Needs no model file; the sample rate and speaker count come from the
+//! voice config when it is readable.
+
+use std::{f32::consts::TAU, path::Path};
+
+use crate::{engine::TtsEngine, Prosody, TtsManager};
+
+const DEFAULT_SAMPLE_RATE: u32 = 22050;
+const MS_PER_CHAR: u32 = 60;
+const MIN_MS: u32 = 120;
+const AMPLITUDE: f32 = 0.3;
+const FADE_MS: u32 = 5;
+
+/// Tone generator implementing `TtsEngine`
+#[derive(Debug, Clone)]
+pub struct MockEngine {
+    sample_rate: u32,
+    num_speakers: u32,
+}
+
+impl MockEngine {
+    pub fn new(sample_rate: u32, num_speakers: u32) -> Self {
+        Self { sample_rate, num_speakers: num_speakers.max(1) }
+    }
+
+    /// Sample rate and speakers from a voice config, with defaults when it cannot be read
+    /// (for example a Git LFS pointer)
+    pub(crate) fn for_config(cfg_path: &Path) -> Self {
+        Self::new(
+            TtsManager::read_sample_rate(cfg_path).unwrap_or(DEFAULT_SAMPLE_RATE),
+            TtsManager::read_num_speakers(cfg_path).unwrap_or(1),
+        )
+    }
+
+    fn tone(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> Vec<f32> {
+        let chars = text.chars().filter(|c| !c.is_whitespace()).count() as u32;
+        if chars == 0 {
+            return Vec::new();
+        }
+        let length_scale = prosody.length_scale.unwrap_or(1.0).max(0.1);
+        let duration_ms = (MS_PER_CHAR * chars).max(MIN_MS) as f32 * length_scale;
+        let len = (self.sample_rate as f32 * duration_ms / 1000.0) as usize;
+
+        // FNV-1a: stable across builds and platforms
+        let hash = text.bytes().fold(0x811c_9dc5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x0100_0193));
+        let frequency = 180.0 + (hash % 240) as f32 + 40.0 * speaker.unwrap_or(0) as f32;
+        let fade = (self.sample_rate * FADE_MS / 1000) as usize;
+
+        (0..len)
+            .map(|i| {
+                let envelope = (i.min(len - 1 - i) as f32 / fade.max(1) as f32).min(1.0);
+                AMPLITUDE * envelope * (TAU * frequency * i as f32 / self.sample_rate as f32).sin()
+            })
+            .collect()
+    }
+}
+
+impl TtsEngine for MockEngine {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn num_speakers(&self) -> u32 {
+        self.num_speakers
+    }
+
+    fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+        let mut samples = Vec::new();
+        for sentence in self.phonemize(text)? {
+            samples.extend(self.tone(&sentence, speaker, prosody));
+        }
+        Ok(samples)
+    }
+
+    /// Sentences of the text, lowercased (there is no phonemizer)
+    fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>> {
+        Ok(text
+            .split_inclusive(['.', '!', '?'])
+            .map(|sentence| sentence.trim().to_lowercase())
+            .filter(|sentence| !sentence.is_empty())
+            .collect())
+    }
+
+    fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+        Ok(self.tone(phonemes, speaker, prosody))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_tones() {
+        let engine = MockEngine::new(16000, 2);
+        let short = engine.synthesize("Hallo.", None, Prosody::default()).unwrap();
+        assert_eq!(short, engine.synthesize("Hallo.", None, Prosody::default()).unwrap());
+        assert!(short.iter().all(|s| s.abs() <= AMPLITUDE));
+
+        // Length follows the text and the length scale
+        let long = engine.synthesize("Hallo. Wie geht es dir?", None, Prosody::default()).unwrap();
+        assert!(long.len() > short.len());
+        let slow = Prosody { length_scale: Some(2.0), ..Prosody::default() };
+        assert_eq!(engine.synthesize("Hallo.", None, slow).unwrap().len(), short.len() * 2);
+
+        // Speakers differ, empty text is silent
+        assert_ne!(engine.synthesize("Hallo.", Some(1), Prosody::default()).unwrap(), short);
+        assert!(engine.synthesize("  ", None, Prosody::default()).unwrap().is_empty());
+        assert_eq!(engine.phonemize("Eins. Zwei!").unwrap(), vec!["eins.", "zwei!"]);
+    }
+
+    #[test]
+    fn test_manager_runs_without_models() {
+        let map = [("de_DE".to_string(), ("/nonexistent/voice.onnx.json".to_string(), None))].into();
+        let tts = TtsManager::new(map).with_engine(crate::EngineKind::Mock);
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        let options = crate::SynthesisOptions::default();
+        let audio = runtime.block_on(tts.synthesize_with_cache("Guten Tag.", Some("de_DE"), None, &options)).unwrap();
+        assert_eq!(audio.sample_rate, DEFAULT_SAMPLE_RATE);
+        assert!(audio.duration_ms > 0 && !audio.cache_hit);
+        let again = runtime.block_on(tts.synthesize_with_cache("Guten Tag.", Some("de_DE"), None, &options)).unwrap();
+        assert!(again.cache_hit);
+        assert_eq!(again.audio, audio.audio);
+    }
+}
@@ -0,0 +1,123 @@
+//! Loaded-model bookkeeping.
+//!
+//! A Piper voice is an ONNX session whose weights dominate its memory, so each loaded model is
+//! accounted with the size of its .onnx file. Least recently used models are evicted to stay
+//! under a byte budget and a model count, and models idle longer than the TTL are unloaded.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+/// Limits of the loaded-model cache
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelCacheConfig {
+    pub max_models: usize,
+    /// Approximate memory budget for all loaded models
+    pub max_bytes: u64,
+    /// Unload models not used for this long (`None` keeps them until evicted)
+    pub idle_ttl: Option<Duration>,
+}
+
+impl Default for ModelCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_models: 15,
+            max_bytes: 2 * 1024 * 1024 * 1024,
+            idle_ttl: None,
+        }
+    }
+}
+
+/// A model currently held in memory
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadedModel {
+    pub config: String,
+    /// Approximate memory (size of the model file)
+    pub memory_bytes: u64,
+    pub sample_rate: u32,
+    pub num_speakers: u32,
+    /// Seconds since the model was last used
+    pub idle_secs: u64,
+}
+
+/// Model file of a voice config (`voice.onnx.json` -> `voice.onnx`)
+pub(crate) fn model_path(config_path: &Path) -> PathBuf {
+    config_path.with_extension("")
+}
+
+/// Approximate memory a voice takes once loaded
+pub(crate) fn estimate_memory(config_path: &Path) -> u64 {
+    fs::metadata(model_path(config_path)).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Keys of the models to unload, given the loaded models as (key, memory, last access). Idle
+/// models go first, then least recently used ones until the count and byte budget leave room
+/// for an `incoming` model of that size. A model larger than the whole budget still loads,
+/// alone.
+pub(crate) fn evictions(
+    mut loaded: Vec<(String, u64, Instant)>,
+    config: &ModelCacheConfig,
+    incoming: Option<u64>,
+    now: Instant,
+) -> Vec<String> {
+    loaded.sort_by_key(|(_, _, last_accessed)| *last_accessed);
+    let (incoming_models, incoming_bytes) = incoming.map_or((0, 0), |bytes| (1, bytes));
+    let mut models = loaded.len();
+    let mut bytes: u64 = loaded.iter().map(|(_, size, _)| size).sum();
+
+    let mut evicted = Vec::new();
+    for (key, size, last_accessed) in loaded {
+        let idle = config.idle_ttl.is_some_and(|ttl| now.duration_since(last_accessed) >= ttl);
+        let over_budget = models + incoming_models > config.max_models.max(1)
+            || bytes + incoming_bytes > config.max_bytes;
+        if !idle && !over_budget {
+            // Sorted by last access: later models are neither idle nor needed for room
+            break;
+        }
+        models -= 1;
+        bytes -= size;
+        evicted.push(key);
+    }
+    evicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn loaded(now: Instant, models: &[(&str, u64, u64)]) -> Vec<(String, u64, Instant)> {
+        models
+            .iter()
+            .map(|&(key, size, idle_secs)| (key.to_string(), size * MB, now - Duration::from_secs(idle_secs)))
+            .collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_for_the_budget() {
+        let now = Instant::now();
+        let config = ModelCacheConfig { max_models: 10, max_bytes: 200 * MB, idle_ttl: None };
+        let models = loaded(now, &[("a", 60, 5), ("b", 60, 50), ("c", 60, 1)]);
+
+        assert_eq!(evictions(models.clone(), &config, Some(20 * MB), now), Vec::<String>::new());
+        assert_eq!(evictions(models.clone(), &config, Some(100 * MB), now), vec!["b", "a"]);
+        // Larger than the budget: everything else goes
+        assert_eq!(evictions(models.clone(), &config, Some(300 * MB), now).len(), 3);
+
+        let config = ModelCacheConfig { max_models: 3, ..config };
+        assert_eq!(evictions(models, &config, Some(MB), now), vec!["b"]);
+    }
+
+    #[test]
+    fn test_unloads_idle_models() {
+        let now = Instant::now();
+        let config = ModelCacheConfig { idle_ttl: Some(Duration::from_secs(30)), ..ModelCacheConfig::default() };
+        let models = loaded(now, &[("a", 60, 5), ("b", 60, 50), ("c", 60, 31)]);
+        assert_eq!(evictions(models, &config, None, now), vec!["b", "c"]);
+    }
+}
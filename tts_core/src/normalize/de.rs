@@ -0,0 +1,215 @@
+//! German number verbalization.
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 20] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn",
+    "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn", "neunzehn",
+];
+const TENS: [&str; 10] = ["", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig"];
+/// Scales above a thousand are separate (feminine) nouns: "zwei Millionen"
+const SCALES: [(u64, &str, &str); 3] = [
+    (1_000_000_000_000, "Billion", "Billionen"),
+    (1_000_000_000, "Milliarde", "Milliarden"),
+    (1_000_000, "Million", "Millionen"),
+];
+const MONTHS: [&str; 12] = [
+    "Januar", "Februar", "März", "April", "Mai", "Juni",
+    "Juli", "August", "September", "Oktober", "November", "Dezember",
+];
+/// Words after which ordinals take the weak "-en" ending ("am dritten Mai")
+const WEAK_EN: &[&str] = &["am", "im", "vom", "zum", "beim", "dem", "den", "des", "seit", "ab", "bis", "ihrem", "seinem"];
+
+pub struct German;
+
+/// 1-999 as one word; `1` stays "eins" only at the very end ("hunderteins")
+fn below_thousand(n: u64) -> String {
+    let mut word = String::new();
+    if n >= 100 {
+        let hundreds = n / 100;
+        word.push_str(if hundreds == 1 { "ein" } else { ONES[hundreds as usize] });
+        word.push_str("hundert");
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        let unit = rest % 10;
+        if unit > 0 {
+            word.push_str(if unit == 1 { "ein" } else { ONES[unit as usize] });
+            word.push_str("und");
+        }
+        word.push_str(TENS[(rest / 10) as usize]);
+    } else if rest > 0 || word.is_empty() {
+        word.push_str(ONES[rest as usize]);
+    }
+    word
+}
+
+impl Verbalizer for German {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        for (scale, singular, plural) in SCALES {
+            if rest >= scale {
+                let count = rest / scale;
+                words.push(if count == 1 {
+                    format!("eine {singular}")
+                } else {
+                    format!("{} {}", self.cardinal(count), plural)
+                });
+                rest %= scale;
+            }
+        }
+        if rest > 0 || words.is_empty() {
+            let mut word = String::new();
+            if rest >= 1000 {
+                let thousands = rest / 1000;
+                word.push_str(&if thousands == 1 { "ein".to_string() } else { below_thousand(thousands) });
+                word.push_str("tausend");
+                rest %= 1000;
+            }
+            if rest > 0 || word.is_empty() {
+                word.push_str(&below_thousand(rest));
+            }
+            words.push(word);
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, previous_word: Option<&str>) -> String {
+        let cardinal = self.cardinal(n);
+        let stem = match n % 100 {
+            1 if cardinal.ends_with("eins") => format!("{}erst", &cardinal[..cardinal.len() - 4]),
+            3 => format!("{}dritt", &cardinal[..cardinal.len() - 4]),
+            7 => format!("{}siebt", &cardinal[..cardinal.len() - 6]),
+            8 => cardinal,
+            2..=19 => format!("{cardinal}t"),
+            _ => format!("{cardinal}st"),
+        };
+        let ending = if previous_word.is_some_and(|w| WEAK_EN.contains(&w)) { "en" } else { "e" };
+        format!("{stem}{ending}")
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, previous_word: Option<&str>) -> String {
+        let date = format!("{} {}", self.ordinal(day as u64, previous_word), self.month_name(month));
+        match year {
+            Some(year) => format!("{} {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, _marker: Option<&str>) -> String {
+        let hour = if hour == 1 { "ein".to_string() } else { self.cardinal(hour as u64) };
+        if minute == 0 {
+            format!("{hour} Uhr")
+        } else {
+            format!("{} Uhr {}", hour, self.cardinal(minute as u64))
+        }
+    }
+
+    fn year(&self, n: u64) -> String {
+        match n {
+            1100..=1999 if n.is_multiple_of(100) => format!("{}hundert", self.cardinal(n / 100)),
+            1100..=1999 => format!("{}hundert{}", self.cardinal(n / 100), self.cardinal(n % 100)),
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn count(&self, n: u64, feminine: bool) -> String {
+        match (n, feminine) {
+            (1, true) => "eine".to_string(),
+            (1, false) => "ein".to_string(),
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        let (major, minor) = match currency {
+            Currency::Euro => (("Euro", "Euro"), ("Cent", "Cent")),
+            Currency::Dollar => (("Dollar", "Dollar"), ("Cent", "Cent")),
+            Currency::Pound => (("Pfund", "Pfund"), ("Penny", "Pence")),
+        };
+        CurrencyNames { major, minor, feminine: false }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        match unit {
+            Unit::KilometersPerHour => UnitName::new("Kilometer pro Stunde", "Kilometer pro Stunde", false),
+            Unit::MilesPerHour => UnitName::new("Meile pro Stunde", "Meilen pro Stunde", true),
+            Unit::Kilometer => UnitName::new("Kilometer", "Kilometer", false),
+            Unit::Meter => UnitName::new("Meter", "Meter", false),
+            Unit::Centimeter => UnitName::new("Zentimeter", "Zentimeter", false),
+            Unit::Millimeter => UnitName::new("Millimeter", "Millimeter", false),
+            Unit::Kilogram => UnitName::new("Kilogramm", "Kilogramm", false),
+            Unit::Gram => UnitName::new("Gramm", "Gramm", false),
+            Unit::Milligram => UnitName::new("Milligramm", "Milligramm", false),
+            Unit::Liter => UnitName::new("Liter", "Liter", false),
+            Unit::Milliliter => UnitName::new("Milliliter", "Milliliter", false),
+            Unit::Celsius => UnitName::new("Grad Celsius", "Grad Celsius", false),
+            Unit::Fahrenheit => UnitName::new("Grad Fahrenheit", "Grad Fahrenheit", false),
+            Unit::KilowattHour => UnitName::new("Kilowattstunde", "Kilowattstunden", true),
+            Unit::Kilowatt => UnitName::new("Kilowatt", "Kilowatt", false),
+            Unit::Watt => UnitName::new("Watt", "Watt", false),
+            Unit::Gigabyte => UnitName::new("Gigabyte", "Gigabyte", false),
+            Unit::Megabyte => UnitName::new("Megabyte", "Megabyte", false),
+            Unit::Hour => UnitName::new("Stunde", "Stunden", true),
+            Unit::Minute => UnitName::new("Minute", "Minuten", true),
+            Unit::Second => UnitName::new("Sekunde", "Sekunden", true),
+        }
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "minus"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "Komma"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "Prozent"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "und"
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &['.', '\u{a0}', '\u{202f}']
+    }
+
+    fn dot_ordinals(&self) -> bool {
+        true
+    }
+
+    fn ordinal_determiners(&self) -> &'static [&'static str] {
+        &["der", "die", "das", "den", "dem", "des", "am", "im", "vom", "zum", "zur", "beim", "ihr", "sein", "ihre", "seine"]
+    }
+
+    fn time_markers(&self) -> &'static [&'static str] {
+        &["Uhr"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_ordinals() {
+        assert_eq!(German.cardinal(101), "einhunderteins");
+        assert_eq!(German.cardinal(71), "einundsiebzig");
+        assert_eq!(German.cardinal(2_500_000), "zwei Millionen fünfhunderttausend");
+        assert_eq!(German.ordinal(1, Some("der")), "erste");
+        assert_eq!(German.ordinal(3, Some("am")), "dritten");
+        assert_eq!(German.ordinal(7, None), "siebte");
+        assert_eq!(German.ordinal(20, None), "zwanzigste");
+    }
+}
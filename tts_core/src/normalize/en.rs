@@ -0,0 +1,206 @@
+//! English (US) number verbalization.
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const SCALES: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+pub struct English;
+
+fn below_thousand(n: u64) -> String {
+    let mut words = Vec::new();
+    if n >= 100 {
+        words.push(format!("{} hundred", ONES[(n / 100) as usize]));
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        let unit = rest % 10;
+        words.push(if unit == 0 {
+            TENS[(rest / 10) as usize].to_string()
+        } else {
+            format!("{}-{}", TENS[(rest / 10) as usize], ONES[unit as usize])
+        });
+    } else if rest > 0 || words.is_empty() {
+        words.push(ONES[rest as usize].to_string());
+    }
+    words.join(" ")
+}
+
+impl Verbalizer for English {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        for (scale, name) in SCALES {
+            if rest >= scale {
+                words.push(format!("{} {}", below_thousand(rest / scale), name));
+                rest %= scale;
+            }
+        }
+        if rest > 0 || words.is_empty() {
+            words.push(below_thousand(rest));
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, _previous_word: Option<&str>) -> String {
+        let cardinal = self.cardinal(n);
+        // Only the last word changes: "twenty-one" -> "twenty-first"
+        let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+        let (head, last) = cardinal.split_at(split);
+        let last = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            w if w.ends_with('y') => format!("{}ieth", &w[..w.len() - 1]),
+            w => format!("{w}th"),
+        };
+        format!("{head}{last}")
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, _previous_word: Option<&str>) -> String {
+        let date = format!("{} {}", self.month_name(month), self.ordinal(day as u64, None));
+        match year {
+            Some(year) => format!("{}, {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, marker: Option<&str>) -> String {
+        let Some(marker) = marker else {
+            return match minute {
+                0 => format!("{} o'clock", self.cardinal(hour as u64)),
+                1..=9 => format!("{} oh {}", self.cardinal(hour as u64), self.cardinal(minute as u64)),
+                _ => format!("{} {}", self.cardinal(hour as u64), self.cardinal(minute as u64)),
+            };
+        };
+        let period = if marker.starts_with(['a', 'A']) { "a m" } else { "p m" };
+        let hour = match hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        match minute {
+            0 => format!("{} {}", self.cardinal(hour as u64), period),
+            1..=9 => format!("{} oh {} {}", self.cardinal(hour as u64), self.cardinal(minute as u64), period),
+            _ => format!("{} {} {}", self.cardinal(hour as u64), self.cardinal(minute as u64), period),
+        }
+    }
+
+    fn year(&self, n: u64) -> String {
+        match n {
+            2000..=2009 | 1000..=1009 => self.cardinal(n),
+            _ if n.is_multiple_of(100) => format!("{} hundred", self.cardinal(n / 100)),
+            _ if n % 100 < 10 => format!("{} oh {}", self.cardinal(n / 100), self.cardinal(n % 100)),
+            _ => format!("{} {}", self.cardinal(n / 100), self.cardinal(n % 100)),
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        let (major, minor) = match currency {
+            Currency::Euro => (("euro", "euros"), ("cent", "cents")),
+            Currency::Dollar => (("dollar", "dollars"), ("cent", "cents")),
+            Currency::Pound => (("pound", "pounds"), ("penny", "pence")),
+        };
+        CurrencyNames { major, minor, feminine: false }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        let (singular, plural) = match unit {
+            Unit::KilometersPerHour => ("kilometer per hour", "kilometers per hour"),
+            Unit::MilesPerHour => ("mile per hour", "miles per hour"),
+            Unit::Kilometer => ("kilometer", "kilometers"),
+            Unit::Meter => ("meter", "meters"),
+            Unit::Centimeter => ("centimeter", "centimeters"),
+            Unit::Millimeter => ("millimeter", "millimeters"),
+            Unit::Kilogram => ("kilogram", "kilograms"),
+            Unit::Gram => ("gram", "grams"),
+            Unit::Milligram => ("milligram", "milligrams"),
+            Unit::Liter => ("liter", "liters"),
+            Unit::Milliliter => ("milliliter", "milliliters"),
+            Unit::Celsius => ("degree Celsius", "degrees Celsius"),
+            Unit::Fahrenheit => ("degree Fahrenheit", "degrees Fahrenheit"),
+            Unit::KilowattHour => ("kilowatt hour", "kilowatt hours"),
+            Unit::Kilowatt => ("kilowatt", "kilowatts"),
+            Unit::Watt => ("watt", "watts"),
+            Unit::Gigabyte => ("gigabyte", "gigabytes"),
+            Unit::Megabyte => ("megabyte", "megabytes"),
+            Unit::Hour => ("hour", "hours"),
+            Unit::Minute => ("minute", "minutes"),
+            Unit::Second => ("second", "seconds"),
+        };
+        UnitName::new(singular, plural, false)
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "minus"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "point"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "percent"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "and"
+    }
+
+    fn decimal_separator(&self) -> char {
+        '.'
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &[',']
+    }
+
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &["st", "nd", "rd", "th"]
+    }
+
+    fn time_markers(&self) -> &'static [&'static str] {
+        &["a.m.", "p.m.", "A.M.", "P.M.", "am", "pm", "AM", "PM"]
+    }
+
+    fn month_first(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_ordinals() {
+        assert_eq!(English.cardinal(0), "zero");
+        assert_eq!(English.cardinal(115), "one hundred fifteen");
+        assert_eq!(English.cardinal(2_000_042), "two million forty-two");
+        assert_eq!(English.ordinal(21, None), "twenty-first");
+        assert_eq!(English.ordinal(40, None), "fortieth");
+        assert_eq!(English.ordinal(112, None), "one hundred twelfth");
+        assert_eq!(English.year(1905), "nineteen oh five");
+        assert_eq!(English.year(2000), "two thousand");
+    }
+}
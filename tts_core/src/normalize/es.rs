@@ -0,0 +1,214 @@
+//! Spanish number verbalization.
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 30] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez",
+    "once", "doce", "trece", "catorce", "quince", "dieciséis", "diecisiete", "dieciocho", "diecinueve",
+    "veinte", "veintiuno", "veintidós", "veintitrés", "veinticuatro", "veinticinco", "veintiséis",
+    "veintisiete", "veintiocho", "veintinueve",
+];
+const TENS: [&str; 10] = ["", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa"];
+const HUNDREDS: [&str; 10] = [
+    "", "ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos",
+    "seiscientos", "setecientos", "ochocientos", "novecientos",
+];
+const ORDINALS: [&str; 11] = [
+    "", "primero", "segundo", "tercero", "cuarto", "quinto", "sexto", "séptimo", "octavo", "noveno", "décimo",
+];
+const MONTHS: [&str; 12] = [
+    "enero", "febrero", "marzo", "abril", "mayo", "junio",
+    "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre",
+];
+
+pub struct Spanish;
+
+fn below_thousand(n: u64) -> String {
+    if n == 100 {
+        return "cien".to_string();
+    }
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut words = Vec::new();
+    if hundreds > 0 {
+        words.push(HUNDREDS[hundreds as usize].to_string());
+    }
+    if rest > 0 || words.is_empty() {
+        words.push(match rest {
+            0..=29 => ONES[rest as usize].to_string(),
+            _ if rest % 10 == 0 => TENS[(rest / 10) as usize].to_string(),
+            _ => format!("{} y {}", TENS[(rest / 10) as usize], ONES[(rest % 10) as usize]),
+        });
+    }
+    words.join(" ")
+}
+
+/// Shortened "uno" before a noun or a scale word: "veintiún mil", "un millón"
+fn apocope(words: &str) -> String {
+    if let Some(stem) = words.strip_suffix("veintiuno") {
+        format!("{stem}veintiún")
+    } else if let Some(stem) = words.strip_suffix("uno") {
+        format!("{stem}un")
+    } else {
+        words.to_string()
+    }
+}
+
+impl Verbalizer for Spanish {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        if rest >= 1_000_000 {
+            let millions = rest / 1_000_000;
+            words.push(if millions == 1 {
+                "un millón".to_string()
+            } else {
+                format!("{} millones", apocope(&self.cardinal(millions)))
+            });
+            rest %= 1_000_000;
+        }
+        if rest >= 1000 {
+            let thousands = rest / 1000;
+            words.push(if thousands == 1 {
+                "mil".to_string()
+            } else {
+                format!("{} mil", apocope(&below_thousand(thousands)))
+            });
+            rest %= 1000;
+        }
+        if rest > 0 || words.is_empty() {
+            words.push(below_thousand(rest));
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, previous_word: Option<&str>) -> String {
+        let feminine = previous_word.is_some_and(|w| matches!(w, "la" | "las" | "una"));
+        // Ordinals above ten are usually read as cardinals ("el piso veinte")
+        if !(1..=10).contains(&n) {
+            return self.cardinal(n);
+        }
+        let ordinal = ORDINALS[n as usize];
+        if feminine {
+            format!("{}a", &ordinal[..ordinal.len() - 1])
+        } else {
+            ordinal.to_string()
+        }
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, _previous_word: Option<&str>) -> String {
+        let day = if day == 1 { "primero".to_string() } else { self.cardinal(day as u64) };
+        let date = format!("{} de {}", day, self.month_name(month));
+        match year {
+            Some(year) => format!("{} de {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, _marker: Option<&str>) -> String {
+        let hour_words = self.count(hour as u64, true);
+        match minute {
+            0 => format!("{hour_words} en punto"),
+            _ => format!("{} y {}", hour_words, self.cardinal(minute as u64)),
+        }
+    }
+
+    fn count(&self, n: u64, feminine: bool) -> String {
+        let cardinal = self.cardinal(n);
+        if feminine {
+            // "veintiuna horas", "doscientas libras"
+            let cardinal = cardinal.replace("ientos", "ientas");
+            if let Some(stem) = cardinal.strip_suffix("uno") {
+                return format!("{stem}una");
+            }
+            cardinal
+        } else {
+            apocope(&cardinal)
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        match currency {
+            Currency::Euro => CurrencyNames { major: ("euro", "euros"), minor: ("céntimo", "céntimos"), feminine: false },
+            Currency::Dollar => CurrencyNames { major: ("dólar", "dólares"), minor: ("centavo", "centavos"), feminine: false },
+            Currency::Pound => CurrencyNames { major: ("libra", "libras"), minor: ("penique", "peniques"), feminine: true },
+        }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        match unit {
+            Unit::KilometersPerHour => UnitName::new("kilómetro por hora", "kilómetros por hora", false),
+            Unit::MilesPerHour => UnitName::new("milla por hora", "millas por hora", true),
+            Unit::Kilometer => UnitName::new("kilómetro", "kilómetros", false),
+            Unit::Meter => UnitName::new("metro", "metros", false),
+            Unit::Centimeter => UnitName::new("centímetro", "centímetros", false),
+            Unit::Millimeter => UnitName::new("milímetro", "milímetros", false),
+            Unit::Kilogram => UnitName::new("kilogramo", "kilogramos", false),
+            Unit::Gram => UnitName::new("gramo", "gramos", false),
+            Unit::Milligram => UnitName::new("miligramo", "miligramos", false),
+            Unit::Liter => UnitName::new("litro", "litros", false),
+            Unit::Milliliter => UnitName::new("mililitro", "mililitros", false),
+            Unit::Celsius => UnitName::new("grado Celsius", "grados Celsius", false),
+            Unit::Fahrenheit => UnitName::new("grado Fahrenheit", "grados Fahrenheit", false),
+            Unit::KilowattHour => UnitName::new("kilovatio hora", "kilovatios hora", false),
+            Unit::Kilowatt => UnitName::new("kilovatio", "kilovatios", false),
+            Unit::Watt => UnitName::new("vatio", "vatios", false),
+            Unit::Gigabyte => UnitName::new("gigabyte", "gigabytes", false),
+            Unit::Megabyte => UnitName::new("megabyte", "megabytes", false),
+            Unit::Hour => UnitName::new("hora", "horas", true),
+            Unit::Minute => UnitName::new("minuto", "minutos", false),
+            Unit::Second => UnitName::new("segundo", "segundos", false),
+        }
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "menos"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "coma"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "por ciento"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "con"
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &['.', ' ', '\u{a0}', '\u{202f}']
+    }
+
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &[".º", ".ª", "º", "ª", ".er", "er"]
+    }
+
+    fn fraction_digit_by_digit(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_counts() {
+        assert_eq!(Spanish.cardinal(100), "cien");
+        assert_eq!(Spanish.cardinal(145), "ciento cuarenta y cinco");
+        assert_eq!(Spanish.cardinal(21_000), "veintiún mil");
+        assert_eq!(Spanish.cardinal(2_000_001), "dos millones uno");
+        assert_eq!(Spanish.count(31, false), "treinta y un");
+        assert_eq!(Spanish.count(200, true), "doscientas");
+        assert_eq!(Spanish.ordinal(3, Some("la")), "tercera");
+    }
+}
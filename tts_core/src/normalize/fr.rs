@@ -0,0 +1,222 @@
+//! French number verbalization (traditional hyphenation: "vingt et un", "quatre-vingt-dix").
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 20] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix",
+    "onze", "douze", "treize", "quatorze", "quinze", "seize", "dix-sept", "dix-huit", "dix-neuf",
+];
+const TENS: [&str; 7] = ["", "", "vingt", "trente", "quarante", "cinquante", "soixante"];
+const SCALES: [(u64, &str, &str); 3] = [
+    (1_000_000_000_000, "billion", "billions"),
+    (1_000_000_000, "milliard", "milliards"),
+    (1_000_000, "million", "millions"),
+];
+const MONTHS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+
+pub struct French;
+
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..=19 => ONES[n as usize].to_string(),
+        // 70-79 and 90-99 count on from 60 and 80: "soixante-douze", "quatre-vingt-dix-neuf"
+        70..=79 if n == 71 => "soixante et onze".to_string(),
+        70..=79 => format!("soixante-{}", ONES[(n - 60) as usize]),
+        80 => "quatre-vingts".to_string(),
+        81..=99 => format!("quatre-vingt-{}", ONES[(n - 80) as usize]),
+        _ => {
+            let (tens, unit) = (TENS[(n / 10) as usize], n % 10);
+            match unit {
+                0 => tens.to_string(),
+                1 => format!("{tens} et un"),
+                _ => format!("{}-{}", tens, ONES[unit as usize]),
+            }
+        }
+    }
+}
+
+/// `final_plural`: "cents"/"quatre-vingts" keep their s only at the end of the number
+fn below_thousand(n: u64, final_plural: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut words = Vec::new();
+    match hundreds {
+        0 => {}
+        1 => words.push("cent".to_string()),
+        h if rest == 0 && final_plural => words.push(format!("{} cents", ONES[h as usize])),
+        h => words.push(format!("{} cent", ONES[h as usize])),
+    }
+    if rest > 0 || words.is_empty() {
+        let mut word = below_hundred(rest);
+        if rest == 80 && !final_plural {
+            word.pop();
+        }
+        words.push(word);
+    }
+    words.join(" ")
+}
+
+impl Verbalizer for French {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        for (scale, singular, plural) in SCALES {
+            if rest >= scale {
+                let count = rest / scale;
+                words.push(format!("{} {}", self.cardinal(count), if count == 1 { singular } else { plural }));
+                rest %= scale;
+            }
+        }
+        if rest >= 1000 {
+            let thousands = rest / 1000;
+            words.push(if thousands == 1 {
+                "mille".to_string()
+            } else {
+                format!("{} mille", below_thousand(thousands, false))
+            });
+            rest %= 1000;
+        }
+        if rest > 0 || words.is_empty() {
+            words.push(below_thousand(rest, true));
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, previous_word: Option<&str>) -> String {
+        let feminine = previous_word.is_some_and(|w| matches!(w, "la" | "une" | "les"));
+        if n == 1 {
+            return if feminine { "première" } else { "premier" }.to_string();
+        }
+        let cardinal = self.cardinal(n);
+        let stem = if let Some(stem) = cardinal.strip_suffix("cinq") {
+            format!("{stem}cinqu")
+        } else if let Some(stem) = cardinal.strip_suffix("neuf") {
+            format!("{stem}neuv")
+        } else if cardinal.ends_with("cents") || cardinal.ends_with("vingts") {
+            cardinal[..cardinal.len() - 1].to_string()
+        } else {
+            cardinal.strip_suffix('e').unwrap_or(&cardinal).to_string()
+        };
+        format!("{stem}ième")
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, _previous_word: Option<&str>) -> String {
+        let day = if day == 1 { "premier".to_string() } else { self.cardinal(day as u64) };
+        let date = format!("{} {}", day, self.month_name(month));
+        match year {
+            Some(year) => format!("{} {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, _marker: Option<&str>) -> String {
+        let hours = format!(
+            "{} {}",
+            self.count(hour as u64, true),
+            if hour > 1 { "heures" } else { "heure" }
+        );
+        if minute == 0 {
+            hours
+        } else {
+            format!("{} {}", hours, self.cardinal(minute as u64))
+        }
+    }
+
+    fn count(&self, n: u64, feminine: bool) -> String {
+        let cardinal = self.cardinal(n);
+        if feminine && cardinal.ends_with("un") {
+            format!("{cardinal}e")
+        } else {
+            cardinal
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        match currency {
+            Currency::Euro => CurrencyNames { major: ("euro", "euros"), minor: ("centime", "centimes"), feminine: false },
+            Currency::Dollar => CurrencyNames { major: ("dollar", "dollars"), minor: ("cent", "cents"), feminine: false },
+            Currency::Pound => CurrencyNames { major: ("livre", "livres"), minor: ("penny", "pence"), feminine: true },
+        }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        match unit {
+            Unit::KilometersPerHour => UnitName::new("kilomètre par heure", "kilomètres par heure", false),
+            Unit::MilesPerHour => UnitName::new("mile par heure", "miles par heure", false),
+            Unit::Kilometer => UnitName::new("kilomètre", "kilomètres", false),
+            Unit::Meter => UnitName::new("mètre", "mètres", false),
+            Unit::Centimeter => UnitName::new("centimètre", "centimètres", false),
+            Unit::Millimeter => UnitName::new("millimètre", "millimètres", false),
+            Unit::Kilogram => UnitName::new("kilogramme", "kilogrammes", false),
+            Unit::Gram => UnitName::new("gramme", "grammes", false),
+            Unit::Milligram => UnitName::new("milligramme", "milligrammes", false),
+            Unit::Liter => UnitName::new("litre", "litres", false),
+            Unit::Milliliter => UnitName::new("millilitre", "millilitres", false),
+            Unit::Celsius => UnitName::new("degré Celsius", "degrés Celsius", false),
+            Unit::Fahrenheit => UnitName::new("degré Fahrenheit", "degrés Fahrenheit", false),
+            Unit::KilowattHour => UnitName::new("kilowattheure", "kilowattheures", false),
+            Unit::Kilowatt => UnitName::new("kilowatt", "kilowatts", false),
+            Unit::Watt => UnitName::new("watt", "watts", false),
+            Unit::Gigabyte => UnitName::new("gigaoctet", "gigaoctets", false),
+            Unit::Megabyte => UnitName::new("mégaoctet", "mégaoctets", false),
+            Unit::Hour => UnitName::new("heure", "heures", true),
+            Unit::Minute => UnitName::new("minute", "minutes", true),
+            Unit::Second => UnitName::new("seconde", "secondes", true),
+        }
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "moins"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "virgule"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "pour cent"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "et"
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &[' ', '\u{a0}', '\u{202f}', '.']
+    }
+
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &["ème", "ère", "er", "re", "e"]
+    }
+
+    fn fraction_digit_by_digit(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_ordinals() {
+        assert_eq!(French.cardinal(80), "quatre-vingts");
+        assert_eq!(French.cardinal(97), "quatre-vingt-dix-sept");
+        assert_eq!(French.cardinal(200), "deux cents");
+        assert_eq!(French.cardinal(280_000), "deux cent quatre-vingt mille");
+        assert_eq!(French.ordinal(3, None), "troisième");
+        assert_eq!(French.ordinal(5, None), "cinquième");
+        assert_eq!(French.ordinal(21, None), "vingt et unième");
+        assert_eq!(French.ordinal(1, Some("la")), "première");
+    }
+}
@@ -0,0 +1,216 @@
+//! Italian number verbalization.
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 20] = [
+    "zero", "uno", "due", "tre", "quattro", "cinque", "sei", "sette", "otto", "nove", "dieci",
+    "undici", "dodici", "tredici", "quattordici", "quindici", "sedici", "diciassette", "diciotto", "diciannove",
+];
+const TENS: [&str; 10] = ["", "", "venti", "trenta", "quaranta", "cinquanta", "sessanta", "settanta", "ottanta", "novanta"];
+const ORDINALS: [&str; 11] = [
+    "", "primo", "secondo", "terzo", "quarto", "quinto", "sesto", "settimo", "ottavo", "nono", "decimo",
+];
+const MONTHS: [&str; 12] = [
+    "gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno",
+    "luglio", "agosto", "settembre", "ottobre", "novembre", "dicembre",
+];
+
+pub struct Italian;
+
+/// 1-999 as one word: "centoventitré"
+fn below_thousand(n: u64) -> String {
+    let mut word = String::new();
+    if n >= 100 {
+        if n / 100 > 1 {
+            word.push_str(ONES[(n / 100) as usize]);
+        }
+        word.push_str("cento");
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        let (tens, unit) = (TENS[(rest / 10) as usize], rest % 10);
+        // The tens drop their vowel before "uno" and "otto": "ventuno", "trentotto"
+        if unit == 1 || unit == 8 {
+            word.push_str(&tens[..tens.len() - 1]);
+        } else {
+            word.push_str(tens);
+        }
+        match unit {
+            0 => {}
+            3 => word.push_str("tré"),
+            u => word.push_str(ONES[u as usize]),
+        }
+    } else if rest > 0 || word.is_empty() {
+        word.push_str(ONES[rest as usize]);
+    }
+    word
+}
+
+impl Verbalizer for Italian {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        for (scale, singular, plural) in [(1_000_000_000, "miliardo", "miliardi"), (1_000_000, "milione", "milioni")] {
+            if rest >= scale {
+                let count = rest / scale;
+                words.push(if count == 1 {
+                    format!("un {singular}")
+                } else {
+                    format!("{} {}", self.cardinal(count), plural)
+                });
+                rest %= scale;
+            }
+        }
+        if rest > 0 || words.is_empty() {
+            let mut word = String::new();
+            if rest >= 1000 {
+                let thousands = rest / 1000;
+                if thousands == 1 {
+                    word.push_str("mille");
+                } else {
+                    word.push_str(&below_thousand(thousands));
+                    word.push_str("mila");
+                }
+                rest %= 1000;
+            }
+            if rest > 0 || word.is_empty() {
+                word.push_str(&below_thousand(rest));
+            }
+            words.push(word);
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, previous_word: Option<&str>) -> String {
+        let feminine = previous_word.is_some_and(|w| matches!(w, "la" | "le" | "una"));
+        let masculine = match n {
+            1..=10 => ORDINALS[n as usize].to_string(),
+            // "undicesimo", "ventitreesimo", "ventiseiesimo"
+            _ => {
+                let cardinal = self.cardinal(n).replace("tré", "tre");
+                if cardinal.ends_with("tre") || cardinal.ends_with("sei") {
+                    format!("{cardinal}esimo")
+                } else {
+                    format!("{}esimo", &cardinal[..cardinal.len() - 1])
+                }
+            }
+        };
+        if feminine {
+            format!("{}a", &masculine[..masculine.len() - 1])
+        } else {
+            masculine
+        }
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, _previous_word: Option<&str>) -> String {
+        let day = if day == 1 { "primo".to_string() } else { self.cardinal(day as u64) };
+        let date = format!("{} {}", day, self.month_name(month));
+        match year {
+            Some(year) => format!("{} {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, _marker: Option<&str>) -> String {
+        let hour_words = self.count(hour as u64, true);
+        if minute == 0 {
+            format!("{hour_words} in punto")
+        } else {
+            format!("{} e {}", hour_words, self.cardinal(minute as u64))
+        }
+    }
+
+    fn count(&self, n: u64, feminine: bool) -> String {
+        match (n, feminine) {
+            (1, true) => "una".to_string(),
+            (1, false) => "un".to_string(),
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        match currency {
+            Currency::Euro => CurrencyNames { major: ("euro", "euro"), minor: ("centesimo", "centesimi"), feminine: false },
+            Currency::Dollar => CurrencyNames { major: ("dollaro", "dollari"), minor: ("centesimo", "centesimi"), feminine: false },
+            Currency::Pound => CurrencyNames { major: ("sterlina", "sterline"), minor: ("penny", "pence"), feminine: true },
+        }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        match unit {
+            Unit::KilometersPerHour => UnitName::new("chilometro orario", "chilometri orari", false),
+            Unit::MilesPerHour => UnitName::new("miglio orario", "miglia orarie", false),
+            Unit::Kilometer => UnitName::new("chilometro", "chilometri", false),
+            Unit::Meter => UnitName::new("metro", "metri", false),
+            Unit::Centimeter => UnitName::new("centimetro", "centimetri", false),
+            Unit::Millimeter => UnitName::new("millimetro", "millimetri", false),
+            Unit::Kilogram => UnitName::new("chilogrammo", "chilogrammi", false),
+            Unit::Gram => UnitName::new("grammo", "grammi", false),
+            Unit::Milligram => UnitName::new("milligrammo", "milligrammi", false),
+            Unit::Liter => UnitName::new("litro", "litri", false),
+            Unit::Milliliter => UnitName::new("millilitro", "millilitri", false),
+            Unit::Celsius => UnitName::new("grado Celsius", "gradi Celsius", false),
+            Unit::Fahrenheit => UnitName::new("grado Fahrenheit", "gradi Fahrenheit", false),
+            Unit::KilowattHour => UnitName::new("chilowattora", "chilowattora", false),
+            Unit::Kilowatt => UnitName::new("chilowatt", "chilowatt", false),
+            Unit::Watt => UnitName::new("watt", "watt", false),
+            Unit::Gigabyte => UnitName::new("gigabyte", "gigabyte", false),
+            Unit::Megabyte => UnitName::new("megabyte", "megabyte", false),
+            Unit::Hour => UnitName::new("ora", "ore", true),
+            Unit::Minute => UnitName::new("minuto", "minuti", false),
+            Unit::Second => UnitName::new("secondo", "secondi", false),
+        }
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "meno"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "virgola"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "per cento"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "e"
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &['.', '\u{a0}', '\u{202f}']
+    }
+
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &["º", "ª"]
+    }
+
+    fn fraction_digit_by_digit(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_ordinals() {
+        assert_eq!(Italian.cardinal(28), "ventotto");
+        assert_eq!(Italian.cardinal(123), "centoventitré");
+        assert_eq!(Italian.cardinal(2025), "duemilaventicinque");
+        assert_eq!(Italian.cardinal(1_000_000), "un milione");
+        assert_eq!(Italian.ordinal(11, None), "undicesimo");
+        assert_eq!(Italian.ordinal(23, None), "ventitreesimo");
+        assert_eq!(Italian.ordinal(2, Some("la")), "seconda");
+    }
+}
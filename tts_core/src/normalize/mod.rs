@@ -0,0 +1,648 @@
+//! Language-aware text normalization.
+//!
+//! Rewrites numbers, dates, times, currency amounts, percentages and units into words before
+//! the text is split and synthesized. Each supported language provides a `Verbalizer`; text in
+//! other languages is returned unchanged.
+
+mod de;
+mod en;
+mod es;
+mod fr;
+mod it;
+mod nl;
+
+/// Currencies recognized by symbol (`€`, `$`, `£`) or ISO code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Euro,
+    Dollar,
+    Pound,
+}
+
+/// Units recognized after a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    KilometersPerHour,
+    MilesPerHour,
+    Kilometer,
+    Meter,
+    Centimeter,
+    Millimeter,
+    Kilogram,
+    Gram,
+    Milligram,
+    Liter,
+    Milliliter,
+    Celsius,
+    Fahrenheit,
+    KilowattHour,
+    Kilowatt,
+    Watt,
+    Gigabyte,
+    Megabyte,
+    Hour,
+    Minute,
+    Second,
+}
+
+/// Unit symbols, longest first so `km/h` wins over `km` and `ml` over `m`.
+/// Single-letter symbols only match after a space (`5 m`, but not `90s`).
+const UNIT_SYMBOLS: &[(&str, Unit)] = &[
+    ("km/h", Unit::KilometersPerHour),
+    ("kWh", Unit::KilowattHour),
+    ("mph", Unit::MilesPerHour),
+    ("min", Unit::Minute),
+    ("°C", Unit::Celsius),
+    ("°F", Unit::Fahrenheit),
+    ("km", Unit::Kilometer),
+    ("cm", Unit::Centimeter),
+    ("mm", Unit::Millimeter),
+    ("kg", Unit::Kilogram),
+    ("mg", Unit::Milligram),
+    ("ml", Unit::Milliliter),
+    ("kW", Unit::Kilowatt),
+    ("GB", Unit::Gigabyte),
+    ("MB", Unit::Megabyte),
+    ("m", Unit::Meter),
+    ("g", Unit::Gram),
+    ("l", Unit::Liter),
+    ("L", Unit::Liter),
+    ("W", Unit::Watt),
+    ("h", Unit::Hour),
+    ("s", Unit::Second),
+];
+
+const CURRENCY_SYMBOLS: &[(&str, Currency)] = &[
+    ("€", Currency::Euro),
+    ("$", Currency::Dollar),
+    ("£", Currency::Pound),
+    ("EUR", Currency::Euro),
+    ("USD", Currency::Dollar),
+    ("GBP", Currency::Pound),
+];
+
+/// Names of a currency and its subunit: (singular, plural), and whether the main unit is
+/// grammatically feminine
+pub struct CurrencyNames {
+    pub major: (&'static str, &'static str),
+    pub minor: (&'static str, &'static str),
+    pub feminine: bool,
+}
+
+/// Name of a unit: (singular, plural), and whether it is grammatically feminine
+pub struct UnitName {
+    pub singular: &'static str,
+    pub plural: &'static str,
+    pub feminine: bool,
+}
+
+impl UnitName {
+    pub const fn new(singular: &'static str, plural: &'static str, feminine: bool) -> Self {
+        Self { singular, plural, feminine }
+    }
+}
+
+/// Spells out numbers and number-like expressions for one language
+pub trait Verbalizer: Send + Sync {
+    fn cardinal(&self, n: u64) -> String;
+    /// Ordinal number; `previous_word` lets languages with case endings pick the right form
+    fn ordinal(&self, n: u64, previous_word: Option<&str>) -> String;
+    fn month_name(&self, month: u32) -> &'static str;
+    fn date(&self, day: u32, month: u32, year: Option<u64>, previous_word: Option<&str>) -> String;
+    /// Clock time; `marker` is the written suffix that followed it, if any ("pm", "Uhr")
+    fn time(&self, hour: u32, minute: u32, marker: Option<&str>) -> String;
+    fn currency_names(&self, currency: Currency) -> CurrencyNames;
+    fn unit_name(&self, unit: Unit) -> UnitName;
+
+    fn minus_word(&self) -> &'static str;
+    fn decimal_word(&self) -> &'static str;
+    fn percent_word(&self) -> &'static str;
+    /// Joins the main and subunit amount ("and", "und", "et", ...)
+    fn and_word(&self) -> &'static str;
+    fn decimal_separator(&self) -> char;
+    fn group_separators(&self) -> &'static [char];
+    /// Suffixes that turn a number into an ordinal ("1st", "2e", "3º")
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// True if "12. " followed by a lowercase word or month reads as an ordinal (German)
+    fn dot_ordinals(&self) -> bool {
+        false
+    }
+    /// Words after which "12. " is an ordinal even before a capitalized noun ("der 2. Platz")
+    fn ordinal_determiners(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Words that may follow a clock time and are absorbed into it ("pm", "Uhr")
+    fn time_markers(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Month/day order of slash dates
+    fn month_first(&self) -> bool {
+        false
+    }
+    /// Read the digits after the decimal separator one by one ("three point one four")
+    fn fraction_digit_by_digit(&self) -> bool {
+        true
+    }
+
+    /// Numbers that look like years (1000-2099)
+    fn year(&self, n: u64) -> String {
+        self.cardinal(n)
+    }
+
+    /// Number counting a noun ("ein Euro", "una hora"); most languages use the cardinal
+    fn count(&self, n: u64, _feminine: bool) -> String {
+        self.cardinal(n)
+    }
+
+    fn decimal(&self, integer: u64, fraction: &str) -> String {
+        let fraction_words = if self.fraction_digit_by_digit() || fraction.starts_with('0') || fraction.len() > 2 {
+            fraction
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .map(|d| self.cardinal(d as u64))
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            self.cardinal(fraction.parse().unwrap_or(0))
+        };
+        format!("{} {} {}", self.cardinal(integer), self.decimal_word(), fraction_words)
+    }
+
+    fn currency(&self, currency: Currency, major: u64, minor: u32) -> String {
+        let names = self.currency_names(currency);
+        let major_text = format!(
+            "{} {}",
+            self.count(major, names.feminine),
+            if major == 1 { names.major.0 } else { names.major.1 }
+        );
+        if minor == 0 {
+            return major_text;
+        }
+        let minor_text = format!(
+            "{} {}",
+            self.count(minor as u64, false),
+            if minor == 1 { names.minor.0 } else { names.minor.1 }
+        );
+        if major == 0 {
+            minor_text
+        } else {
+            format!("{} {} {}", major_text, self.and_word(), minor_text)
+        }
+    }
+}
+
+/// Verbalizer for a language key (`de_DE`) or code (`de`)
+pub fn verbalizer_for(language: &str) -> Option<&'static dyn Verbalizer> {
+    let code = language.split(['_', '-']).next().unwrap_or(language);
+    Some(match code {
+        "en" => &en::English,
+        "de" => &de::German,
+        "fr" => &fr::French,
+        "es" => &es::Spanish,
+        "it" => &it::Italian,
+        "nl" => &nl::Dutch,
+        _ => return None,
+    })
+}
+
+/// Verbalize numbers, dates, times, currency, percentages and units for `language`
+pub fn normalize_text(text: &str, language: &str) -> String {
+    match verbalizer_for(language) {
+        Some(verbalizer) => Normalizer::new(text, verbalizer).run(),
+        None => text.to_string(),
+    }
+}
+
+/// A parsed number: integer part and optional fraction digits
+#[derive(Debug, Clone)]
+struct Number {
+    integer: u64,
+    fraction: Option<String>,
+    /// Digits of the integer part as written (without group separators)
+    digits: usize,
+    grouped: bool,
+    negative: bool,
+}
+
+struct Normalizer<'a> {
+    chars: Vec<char>,
+    v: &'a dyn Verbalizer,
+    out: String,
+}
+
+impl<'a> Normalizer<'a> {
+    fn new(text: &str, v: &'a dyn Verbalizer) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            v,
+            out: String::with_capacity(text.len() * 2),
+        }
+    }
+
+    fn run(mut self) -> String {
+        let mut i = 0;
+        while i < self.chars.len() {
+            let c = self.chars[i];
+
+            // Leading currency symbol: "€5", "$ 3.50"
+            if let Some((currency, len)) = self.currency_at(i).filter(|_| !self.preceded_by_word(i)) {
+                let start = self.skip_space(i + len);
+                if self.at_number_start(start) {
+                    if let Some((number, end)) = self.parse_number(start) {
+                        let text = self.currency_text(currency, &number);
+                        self.emit(&text);
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+
+            if c.is_ascii_digit() && !self.preceded_by_word(i) {
+                if let Some(end) = self.expression_at(i) {
+                    i = end;
+                    continue;
+                }
+            }
+
+            // Negative numbers: "-5" at a word start
+            if (c == '-' || c == '−') && i + 1 < self.chars.len() && self.chars[i + 1].is_ascii_digit()
+                && (i == 0 || self.chars[i - 1].is_whitespace() || self.chars[i - 1] == '(')
+            {
+                if let Some((mut number, end)) = self.parse_number(i + 1) {
+                    number.negative = true;
+                    let words = self.number_text(&number);
+                    i = self.finish_quantity(i, &words, &number, end);
+                    continue;
+                }
+            }
+
+            self.out.push(c);
+            i += 1;
+        }
+        self.out
+    }
+
+    /// Recognize an expression starting with a digit at `i`; returns the end index
+    fn expression_at(&mut self, i: usize) -> Option<usize> {
+        if let Some(end) = self.iso_date(i).or_else(|| self.dotted_date(i)).or_else(|| self.slash_date(i)) {
+            return Some(end);
+        }
+        if let Some(end) = self.time(i) {
+            return Some(end);
+        }
+
+        let (number, end) = self.parse_number(i)?;
+
+        // Ordinals: "1st", "2e", "3º", German "3. Mai"
+        if number.fraction.is_none() && !number.grouped {
+            if let Some(end) = self.ordinal(&number, end) {
+                return Some(end);
+            }
+        }
+
+        let words = if number.fraction.is_none() && !number.grouped && number.digits == 4 && (1000..=2099).contains(&number.integer) {
+            self.v.year(number.integer)
+        } else {
+            self.number_text(&number)
+        };
+        Some(self.finish_quantity(i, &words, &number, end))
+    }
+
+    /// Emit a number followed by `%`, a currency or a unit if one follows; else the number alone.
+    /// Numbers glued to other letters ("1990s", "4x") are left as written.
+    fn finish_quantity(&mut self, start: usize, words: &str, number: &Number, end: usize) -> usize {
+        let after = self.skip_space(end);
+
+        if self.chars.get(after) == Some(&'%') {
+            let text = format!("{} {}", self.number_text(number), self.v.percent_word());
+            self.emit(&text);
+            return after + 1;
+        }
+
+        if let Some((currency, len)) = self.currency_at(after) {
+            if self.boundary_at(after + len) {
+                let text = self.currency_text(currency, number);
+                self.emit(&text);
+                return after + len;
+            }
+        }
+
+        let spaced = after > end;
+        for (symbol, unit) in UNIT_SYMBOLS {
+            let len = symbol.chars().count();
+            if (len > 1 || spaced) && self.matches_at(after, symbol) && self.boundary_at(after + len) {
+                let name = self.v.unit_name(*unit);
+                let one = number.integer == 1 && number.fraction.is_none();
+                let amount = if number.fraction.is_some() {
+                    self.number_text(number)
+                } else {
+                    self.signed(number, self.v.count(number.integer, name.feminine))
+                };
+                let text = format!("{} {}", amount, if one { name.singular } else { name.plural });
+                self.emit(&text);
+                return after + len;
+            }
+        }
+
+        if self.boundary_at(end) {
+            self.emit(words);
+        } else {
+            let written: String = self.chars[start..end].iter().collect();
+            self.emit(&written);
+        }
+        end
+    }
+
+    fn number_text(&self, number: &Number) -> String {
+        let words = match &number.fraction {
+            Some(fraction) => self.v.decimal(number.integer, fraction),
+            None => self.v.cardinal(number.integer),
+        };
+        self.signed(number, words)
+    }
+
+    fn signed(&self, number: &Number, words: String) -> String {
+        if number.negative {
+            format!("{} {}", self.v.minus_word(), words)
+        } else {
+            words
+        }
+    }
+
+    fn currency_text(&self, currency: Currency, number: &Number) -> String {
+        let minor = number
+            .fraction
+            .as_deref()
+            .map(|f| {
+                let mut digits: String = f.chars().take(2).collect();
+                while digits.len() < 2 {
+                    digits.push('0');
+                }
+                digits.parse().unwrap_or(0)
+            })
+            .unwrap_or(0);
+        self.signed(number, self.v.currency(currency, number.integer, minor))
+    }
+
+    fn ordinal(&mut self, number: &Number, end: usize) -> Option<usize> {
+        for suffix in self.v.ordinal_suffixes() {
+            let len = suffix.chars().count();
+            if self.matches_at(end, suffix) && self.boundary_at(end + len) {
+                let words = self.v.ordinal(number.integer, self.previous_word().as_deref());
+                self.emit(&words);
+                return Some(end + len);
+            }
+        }
+
+        // "am 3. Mai", "der 2. Platz" - a dot followed by a lowercase word, a month name, or
+        // after an article or preposition
+        if self.v.dot_ordinals() && self.chars.get(end) == Some(&'.') && self.chars.get(end + 1) == Some(&' ') {
+            let next = self.chars.get(end + 2).copied()?;
+            let previous = self.previous_word();
+            let after_determiner = previous
+                .as_deref()
+                .is_some_and(|w| self.v.ordinal_determiners().contains(&w));
+            if next.is_lowercase() || after_determiner || self.month_at(end + 2).is_some() {
+                let words = self.v.ordinal(number.integer, previous.as_deref());
+                self.emit(&words);
+                return Some(end + 1);
+            }
+        }
+        None
+    }
+
+    /// `2025-05-12`
+    fn iso_date(&mut self, i: usize) -> Option<usize> {
+        let (year, p) = self.digits(i, 4, 4)?;
+        let p = self.expect(p, '-')?;
+        let (month, p) = self.digits(p, 1, 2)?;
+        let p = self.expect(p, '-')?;
+        let (day, p) = self.digits(p, 1, 2)?;
+        self.emit_date(day as u32, month as u32, Some(year), p)
+    }
+
+    /// `12.05.2025`, and `12.05.` where day-first dates are the norm
+    fn dotted_date(&mut self, i: usize) -> Option<usize> {
+        let (day, p) = self.digits(i, 1, 2)?;
+        let p = self.expect(p, '.')?;
+        let (month, p) = self.digits(p, 1, 2)?;
+        let p = self.expect(p, '.')?;
+        match self.digits(p, 2, 4) {
+            Some((year, end)) if end - p != 3 => self.emit_date(day as u32, month as u32, Some(year), end),
+            _ if !self.v.month_first() && self.boundary_at(p) => self.emit_date(day as u32, month as u32, None, p),
+            _ => None,
+        }
+    }
+
+    /// `05/12/2025` (month first in English, day first elsewhere)
+    fn slash_date(&mut self, i: usize) -> Option<usize> {
+        let (first, p) = self.digits(i, 1, 2)?;
+        let p = self.expect(p, '/')?;
+        let (second, p) = self.digits(p, 1, 2)?;
+        let p = self.expect(p, '/')?;
+        let (year, end) = self.digits(p, 2, 4)?;
+        if end - p == 3 {
+            return None;
+        }
+        let (day, month) = if self.v.month_first() { (second, first) } else { (first, second) };
+        self.emit_date(day as u32, month as u32, Some(year), end)
+    }
+
+    fn emit_date(&mut self, day: u32, month: u32, year: Option<u64>, end: usize) -> Option<usize> {
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || !self.boundary_at(end) {
+            return None;
+        }
+        let words = self.v.date(day, month, year, self.previous_word().as_deref());
+        self.emit(&words);
+        Some(end)
+    }
+
+    /// `14:30`
+    fn time(&mut self, i: usize) -> Option<usize> {
+        let (hour, p) = self.digits(i, 1, 2)?;
+        let p = self.expect(p, ':')?;
+        let (minute, end) = self.digits(p, 2, 2)?;
+        if hour > 23 || minute > 59 || !self.boundary_at(end) || self.chars.get(end) == Some(&':') {
+            return None;
+        }
+
+        // "2:30 pm", "14:30 Uhr"
+        let after = self.skip_space(end);
+        let marker = self.v.time_markers().iter().copied().find(|m| {
+            let len = m.chars().count();
+            self.matches_at(after, m) && (m.ends_with('.') || self.boundary_at(after + len))
+        });
+        let mut end = end;
+        if let Some(marker) = marker {
+            end = after + marker.chars().count();
+            // Keep an abbreviation dot that also ends the sentence
+            if marker.ends_with('.') && !self.chars.get(end + 1).is_some_and(|c| c.is_lowercase()) {
+                end -= 1;
+            }
+        }
+
+        let words = self.v.time(hour as u32, minute as u32, marker);
+        self.emit(&words);
+        Some(end)
+    }
+
+    /// Parse a number with the language's group and decimal separators
+    fn parse_number(&self, i: usize) -> Option<(Number, usize)> {
+        let (first, mut p) = self.digit_run(i)?;
+        let mut integer_digits = first;
+        let mut grouped = false;
+
+        // Group separators must be followed by exactly three digits
+        if integer_digits.len() <= 3 {
+            while let Some(&sep) = self.chars.get(p) {
+                if !self.v.group_separators().contains(&sep) {
+                    break;
+                }
+                match self.digit_run(p + 1) {
+                    Some((group, end)) if group.len() == 3 => {
+                        integer_digits.push_str(&group);
+                        grouped = true;
+                        p = end;
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        let mut fraction = None;
+        if self.chars.get(p) == Some(&self.v.decimal_separator()) {
+            if let Some((digits, end)) = self.digit_run(p + 1) {
+                fraction = Some(digits);
+                p = end;
+            }
+        }
+
+        // Too long to read as a number (IDs, phone numbers): leave as is
+        if integer_digits.len() > 15 {
+            return None;
+        }
+        Some((
+            Number {
+                integer: integer_digits.parse().ok()?,
+                fraction,
+                digits: integer_digits.len(),
+                grouped,
+                negative: false,
+            },
+            p,
+        ))
+    }
+
+    fn digit_run(&self, i: usize) -> Option<(String, usize)> {
+        let mut p = i;
+        while p < self.chars.len() && self.chars[p].is_ascii_digit() {
+            p += 1;
+        }
+        (p > i).then(|| (self.chars[i..p].iter().collect(), p))
+    }
+
+    /// Between `min` and `max` digits, not followed by another digit
+    fn digits(&self, i: usize, min: usize, max: usize) -> Option<(u64, usize)> {
+        let (run, end) = self.digit_run(i)?;
+        (run.len() >= min && run.len() <= max).then(|| (run.parse().unwrap_or(0), end))
+    }
+
+    fn expect(&self, i: usize, c: char) -> Option<usize> {
+        (self.chars.get(i) == Some(&c)).then_some(i + 1)
+    }
+
+    fn skip_space(&self, i: usize) -> usize {
+        match self.chars.get(i) {
+            Some(' ') | Some('\u{a0}') | Some('\u{202f}') => i + 1,
+            _ => i,
+        }
+    }
+
+    fn matches_at(&self, i: usize, s: &str) -> bool {
+        s.chars().enumerate().all(|(k, c)| self.chars.get(i + k) == Some(&c))
+    }
+
+    /// True if position `i` is not inside a word
+    fn boundary_at(&self, i: usize) -> bool {
+        self.chars.get(i).is_none_or(|c| !c.is_alphanumeric())
+    }
+
+    fn preceded_by_word(&self, i: usize) -> bool {
+        i > 0 && (self.chars[i - 1].is_alphanumeric() || self.chars[i - 1] == '_')
+    }
+
+    fn at_number_start(&self, i: usize) -> bool {
+        self.chars.get(i).is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn currency_at(&self, i: usize) -> Option<(Currency, usize)> {
+        CURRENCY_SYMBOLS
+            .iter()
+            .find(|(symbol, _)| self.matches_at(i, symbol))
+            .map(|(symbol, currency)| (*currency, symbol.chars().count()))
+    }
+
+    fn month_at(&self, i: usize) -> Option<u32> {
+        let word: String = self.chars[i..].iter().take_while(|c| c.is_alphabetic()).collect();
+        (1..=12).find(|&m| self.v.month_name(m).eq_ignore_ascii_case(&word))
+    }
+
+    fn previous_word(&self) -> Option<String> {
+        self.out
+            .split_whitespace()
+            .next_back()
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+    }
+
+    fn emit(&mut self, words: &str) {
+        self.out.push_str(words);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_language_is_unchanged() {
+        assert_eq!(normalize_text("Це 5 км", "uk_UA"), "Це 5 км");
+    }
+
+    #[test]
+    fn test_english() {
+        let n = |t| normalize_text(t, "en_US");
+        assert_eq!(n("I have 21 apples."), "I have twenty-one apples.");
+        assert_eq!(n("It costs $3.50 today"), "It costs three dollars and fifty cents today");
+        assert_eq!(n("Pi is 3.14."), "Pi is three point one four.");
+        assert_eq!(n("In 1999 it was 10 km/h"), "In nineteen ninety-nine it was ten kilometers per hour");
+        assert_eq!(n("On 05/12/2025 at 14:30"), "On May twelfth, twenty twenty-five at fourteen thirty");
+        assert_eq!(n("the 3rd time, 15% off"), "the third time, fifteen percent off");
+        assert_eq!(n("1,000,000 people"), "one million people");
+        assert_eq!(n("-5 °C"), "minus five degrees Celsius");
+        assert_eq!(n("mp3 and A4 in the 1990s"), "mp3 and A4 in the 1990s");
+        assert_eq!(n("at 5:30 p.m. sharp"), "at five thirty p m sharp");
+    }
+
+    #[test]
+    fn test_german() {
+        let n = |t| normalize_text(t, "de_DE");
+        assert_eq!(n("Das kostet 3,14 €."), "Das kostet drei Euro und vierzehn Cent.");
+        assert_eq!(n("Am 12.05.2025 um 10:00 Uhr"), "Am zwölften Mai zweitausendfünfundzwanzig um zehn Uhr");
+        assert_eq!(n("Mit 10 km/h in die 90er"), "Mit zehn Kilometer pro Stunde in die 90er");
+        assert_eq!(n("Er wurde 1999 geboren"), "Er wurde neunzehnhundertneunundneunzig geboren");
+        assert_eq!(n("Der 2. Platz, 1.000 Leute, 25 %"), "Der zweite Platz, eintausend Leute, fünfundzwanzig Prozent");
+        assert_eq!(n("1 kg"), "ein Kilogramm");
+    }
+
+    #[test]
+    fn test_romance_and_dutch() {
+        assert_eq!(normalize_text("Il coûte 3,50 €", "fr_FR"), "Il coûte trois euros et cinquante centimes");
+        assert_eq!(normalize_text("Le 1er mai, 71 %", "fr_FR"), "Le premier mai, soixante et onze pour cent");
+        assert_eq!(normalize_text("Cuesta 21 €", "es_ES"), "Cuesta veintiún euros");
+        assert_eq!(normalize_text("El 12/05/2025", "es_ES"), "El doce de mayo de dos mil veinticinco");
+        assert_eq!(normalize_text("Sono 23 km", "it_IT"), "Sono ventitré chilometri");
+        assert_eq!(normalize_text("Het is 22,5 °C", "nl_NL"), "Het is tweeëntwintig komma vijf graden Celsius");
+    }
+}
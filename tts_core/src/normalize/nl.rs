@@ -0,0 +1,194 @@
+//! Dutch number verbalization.
+
+use super::{Currency, CurrencyNames, Unit, UnitName, Verbalizer};
+
+const ONES: [&str; 20] = [
+    "nul", "een", "twee", "drie", "vier", "vijf", "zes", "zeven", "acht", "negen", "tien",
+    "elf", "twaalf", "dertien", "veertien", "vijftien", "zestien", "zeventien", "achttien", "negentien",
+];
+const TENS: [&str; 10] = ["", "", "twintig", "dertig", "veertig", "vijftig", "zestig", "zeventig", "tachtig", "negentig"];
+const MONTHS: [&str; 12] = [
+    "januari", "februari", "maart", "april", "mei", "juni",
+    "juli", "augustus", "september", "oktober", "november", "december",
+];
+
+pub struct Dutch;
+
+/// 1-999 as one word: "honderdtweeëntwintig"
+fn below_thousand(n: u64) -> String {
+    let mut word = String::new();
+    if n >= 100 {
+        if n / 100 > 1 {
+            word.push_str(ONES[(n / 100) as usize]);
+        }
+        word.push_str("honderd");
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        let unit = rest % 10;
+        if unit > 0 {
+            let unit_word = ONES[unit as usize];
+            word.push_str(unit_word);
+            // Diaeresis where "en" would run into a preceding e: "tweeëntwintig"
+            word.push_str(if unit_word.ends_with('e') { "ën" } else { "en" });
+        }
+        word.push_str(TENS[(rest / 10) as usize]);
+    } else if rest > 0 || word.is_empty() {
+        word.push_str(ONES[rest as usize]);
+    }
+    word
+}
+
+impl Verbalizer for Dutch {
+    fn cardinal(&self, n: u64) -> String {
+        let mut words = Vec::new();
+        let mut rest = n;
+        for (scale, name) in [(1_000_000_000, "miljard"), (1_000_000, "miljoen")] {
+            if rest >= scale {
+                words.push(format!("{} {}", self.cardinal(rest / scale), name));
+                rest %= scale;
+            }
+        }
+        // "duizend" is joined to what precedes it and written apart from what follows
+        if rest >= 1000 {
+            let thousands = rest / 1000;
+            words.push(if thousands == 1 {
+                "duizend".to_string()
+            } else {
+                format!("{}duizend", below_thousand(thousands))
+            });
+            rest %= 1000;
+        }
+        if rest > 0 || words.is_empty() {
+            words.push(below_thousand(rest));
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: u64, _previous_word: Option<&str>) -> String {
+        let cardinal = self.cardinal(n);
+        match n % 100 {
+            1 => format!("{}eerste", &cardinal[..cardinal.len() - 3]),
+            3 => format!("{}derde", &cardinal[..cardinal.len() - 4]),
+            8 => format!("{cardinal}ste"),
+            2..=19 => format!("{cardinal}de"),
+            _ => format!("{cardinal}ste"),
+        }
+    }
+
+    fn month_name(&self, month: u32) -> &'static str {
+        MONTHS[(month as usize).clamp(1, 12) - 1]
+    }
+
+    fn date(&self, day: u32, month: u32, year: Option<u64>, _previous_word: Option<&str>) -> String {
+        let date = format!("{} {}", self.cardinal(day as u64), self.month_name(month));
+        match year {
+            Some(year) => format!("{} {}", date, self.year(year)),
+            None => date,
+        }
+    }
+
+    fn time(&self, hour: u32, minute: u32, _marker: Option<&str>) -> String {
+        if minute == 0 {
+            format!("{} uur", self.cardinal(hour as u64))
+        } else {
+            format!("{} uur {}", self.cardinal(hour as u64), self.cardinal(minute as u64))
+        }
+    }
+
+    fn year(&self, n: u64) -> String {
+        match n {
+            1100..=1999 if n.is_multiple_of(100) => format!("{}honderd", self.cardinal(n / 100)),
+            1100..=1999 => format!("{}honderd{}", self.cardinal(n / 100), self.cardinal(n % 100)),
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn currency_names(&self, currency: Currency) -> CurrencyNames {
+        let (major, minor) = match currency {
+            Currency::Euro => (("euro", "euro"), ("cent", "cent")),
+            Currency::Dollar => (("dollar", "dollar"), ("cent", "cent")),
+            Currency::Pound => (("pond", "pond"), ("penny", "pence")),
+        };
+        CurrencyNames { major, minor, feminine: false }
+    }
+
+    fn unit_name(&self, unit: Unit) -> UnitName {
+        let (singular, plural) = match unit {
+            Unit::KilometersPerHour => ("kilometer per uur", "kilometer per uur"),
+            Unit::MilesPerHour => ("mijl per uur", "mijl per uur"),
+            Unit::Kilometer => ("kilometer", "kilometer"),
+            Unit::Meter => ("meter", "meter"),
+            Unit::Centimeter => ("centimeter", "centimeter"),
+            Unit::Millimeter => ("millimeter", "millimeter"),
+            Unit::Kilogram => ("kilogram", "kilogram"),
+            Unit::Gram => ("gram", "gram"),
+            Unit::Milligram => ("milligram", "milligram"),
+            Unit::Liter => ("liter", "liter"),
+            Unit::Milliliter => ("milliliter", "milliliter"),
+            Unit::Celsius => ("graad Celsius", "graden Celsius"),
+            Unit::Fahrenheit => ("graad Fahrenheit", "graden Fahrenheit"),
+            Unit::KilowattHour => ("kilowattuur", "kilowattuur"),
+            Unit::Kilowatt => ("kilowatt", "kilowatt"),
+            Unit::Watt => ("watt", "watt"),
+            Unit::Gigabyte => ("gigabyte", "gigabyte"),
+            Unit::Megabyte => ("megabyte", "megabyte"),
+            Unit::Hour => ("uur", "uur"),
+            Unit::Minute => ("minuut", "minuten"),
+            Unit::Second => ("seconde", "seconden"),
+        };
+        UnitName::new(singular, plural, false)
+    }
+
+    fn minus_word(&self) -> &'static str {
+        "min"
+    }
+
+    fn decimal_word(&self) -> &'static str {
+        "komma"
+    }
+
+    fn percent_word(&self) -> &'static str {
+        "procent"
+    }
+
+    fn and_word(&self) -> &'static str {
+        "en"
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separators(&self) -> &'static [char] {
+        &['.', '\u{a0}', '\u{202f}']
+    }
+
+    fn ordinal_suffixes(&self) -> &'static [&'static str] {
+        &["ste", "de", "e"]
+    }
+
+    fn time_markers(&self) -> &'static [&'static str] {
+        &["uur"]
+    }
+
+    fn fraction_digit_by_digit(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals_and_ordinals() {
+        assert_eq!(Dutch.cardinal(83), "drieëntachtig");
+        assert_eq!(Dutch.cardinal(2025), "tweeduizend vijfentwintig");
+        assert_eq!(Dutch.ordinal(1, None), "eerste");
+        assert_eq!(Dutch.ordinal(8, None), "achtste");
+        assert_eq!(Dutch.ordinal(12, None), "twaalfde");
+        assert_eq!(Dutch.ordinal(21, None), "eenentwintigste");
+        assert_eq!(Dutch.ordinal(103, None), "honderdderde");
+    }
+}
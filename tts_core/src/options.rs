@@ -0,0 +1,45 @@
+//! Per-request synthesis options.
+
+use crate::{AudioFormat, LoudnessTarget, Prosody, SilenceOptions};
+
+/// Everything besides text, language and voice that changes the synthesized audio.
+/// All fields but `parallelism` are part of the response cache key, the format only by its
+/// resolution (see `for_cache_key`).
+#[derive(Debug, Clone, Default, PartialEq, Hash)]
+pub struct SynthesisOptions {
+    /// Speaker ID for multi-speaker voices (defaults to the voice's speaker from map.json)
+    pub speaker: Option<i64>,
+    /// Piper inference settings (unset fields fall back to the voice defaults)
+    pub prosody: Prosody,
+    /// Apply per-sentence emotion hints (rate via length scale, pitch via DSP)
+    pub expressive: bool,
+    /// Output encoding for cached responses
+    pub format: AudioFormat,
+    /// Resample to this rate instead of the voice's native rate
+    pub sample_rate: Option<u32>,
+    /// Loudness normalization (unset fields fall back to the voice defaults)
+    pub loudness: LoudnessTarget,
+    /// Return word and chunk timings with the audio (plain text input only)
+    pub alignment: bool,
+    /// Trim leading and trailing silence and shorten long pauses (timings follow)
+    pub silence: SilenceOptions,
+    /// Text chunks synthesized at once (0 or 1: sequentially); does not change the output
+    pub parallelism: usize,
+}
+
+impl SynthesisOptions {
+    /// Options as they identify a cached response. Responses are cached as PCM and encoded on
+    /// the way out, so formats of the same resolution share entries. Parallel and sequential
+    /// synthesis give the same audio.
+    pub(crate) fn for_cache_key(&self) -> Self {
+        let format = if self.format.is_high_resolution() { AudioFormat::WavF32 } else { AudioFormat::Wav };
+        Self { format, parallelism: 0, ..self.clone() }
+    }
+
+    pub fn with_speaker(speaker: Option<i64>) -> Self {
+        Self {
+            speaker,
+            ..Self::default()
+        }
+    }
+}
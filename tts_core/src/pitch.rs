@@ -0,0 +1,150 @@
+//! Duration-preserving pitch shift.
+//!
+//! The signal is time-stretched with WSOLA (waveform-similarity overlap-add) by the pitch
+//! factor and then resampled back to its original length, which scales the pitch by the
+//! same factor while keeping the duration.
+
+/// Analysis/synthesis frame length
+const FRAME_MS: f32 = 30.0;
+/// Maximum offset searched for the best-matching frame
+const TOLERANCE_MS: f32 = 8.0;
+
+/// Shift pitch by `factor` (1.1 = 10% higher) without changing the duration
+pub fn pitch_shift(samples: &[f32], sample_rate: u32, factor: f32) -> Vec<f32> {
+    if samples.is_empty() || !factor.is_finite() || factor <= 0.0 || (factor - 1.0).abs() < 1e-3 {
+        return samples.to_vec();
+    }
+
+    let stretched = time_stretch(samples, sample_rate, factor);
+    resample_to_len(&stretched, samples.len())
+}
+
+/// WSOLA time stretch: output is `stretch` times as long as the input
+fn time_stretch(samples: &[f32], sample_rate: u32, stretch: f32) -> Vec<f32> {
+    let frame_len = ((FRAME_MS / 1000.0 * sample_rate as f32) as usize).max(16);
+    let synthesis_hop = frame_len / 2;
+    let tolerance = (TOLERANCE_MS / 1000.0 * sample_rate as f32) as isize;
+    let out_len = (samples.len() as f32 * stretch) as usize;
+
+    // Periodic Hann window: overlapping at 50% sums to a constant
+    let window: Vec<f32> = (0..frame_len)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame_len as f32).cos())
+        .collect();
+
+    let input_at = |pos: isize| -> f32 {
+        if pos >= 0 && (pos as usize) < samples.len() {
+            samples[pos as usize]
+        } else {
+            0.0
+        }
+    };
+
+    let mut output = vec![0.0f32; out_len + frame_len];
+    let mut norm = vec![0.0f32; out_len + frame_len];
+    let mut prev_pos: isize = 0;
+    let mut out_pos = 0usize;
+    let mut first = true;
+
+    while out_pos < out_len {
+        let nominal = (out_pos as f32 / stretch) as isize;
+
+        let best = if first {
+            nominal
+        } else {
+            // Pick the frame near the nominal position that best continues the previous one
+            let target = prev_pos + synthesis_hop as isize;
+            let mut best = nominal;
+            let mut best_score = f32::NEG_INFINITY;
+            for delta in -tolerance..=tolerance {
+                let candidate = nominal + delta;
+                if candidate < 0 {
+                    continue;
+                }
+                let score: f32 = (0..synthesis_hop)
+                    .map(|i| input_at(candidate + i as isize) * input_at(target + i as isize))
+                    .sum();
+                if score > best_score {
+                    best_score = score;
+                    best = candidate;
+                }
+            }
+            best
+        };
+
+        for i in 0..frame_len {
+            output[out_pos + i] += input_at(best + i as isize) * window[i];
+            norm[out_pos + i] += window[i];
+        }
+
+        prev_pos = best;
+        out_pos += synthesis_hop;
+        first = false;
+    }
+
+    output.truncate(out_len);
+    for (sample, weight) in output.iter_mut().zip(norm.iter()) {
+        if *weight > 1e-3 {
+            *sample /= weight;
+        }
+    }
+    output
+}
+
+/// Linear-interpolation resampling to an exact output length
+fn resample_to_len(samples: &[f32], len: usize) -> Vec<f32> {
+    if samples.is_empty() || len == 0 {
+        return vec![0.0; len];
+    }
+    if len == 1 || samples.len() == 1 {
+        return vec![samples[0]; len];
+    }
+
+    let step = (samples.len() - 1) as f64 / (len - 1) as f64;
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = samples[idx];
+            let b = samples[(idx + 1).min(samples.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    fn zero_crossings(samples: &[f32]) -> usize {
+        samples.windows(2).filter(|w| (w[0] < 0.0) != (w[1] < 0.0)).count()
+    }
+
+    #[test]
+    fn test_pitch_shift_keeps_length() {
+        let input = sine(220.0, 22050, 22050);
+        for factor in [0.9, 0.95, 1.05, 1.1] {
+            assert_eq!(pitch_shift(&input, 22050, factor).len(), input.len());
+        }
+    }
+
+    #[test]
+    fn test_pitch_shift_neutral_is_identity() {
+        let input = sine(220.0, 22050, 1000);
+        assert_eq!(pitch_shift(&input, 22050, 1.0), input);
+    }
+
+    #[test]
+    fn test_pitch_shift_scales_frequency() {
+        let input = sine(200.0, 16000, 16000);
+        let shifted = pitch_shift(&input, 16000, 1.2);
+        let ratio = zero_crossings(&shifted) as f32 / zero_crossings(&input) as f32;
+        assert!((ratio - 1.2).abs() < 0.05, "frequency ratio was {ratio}");
+    }
+}
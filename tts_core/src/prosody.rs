@@ -0,0 +1,63 @@
+//! Prosody controls passed through to Piper's inference settings.
+
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+/// Per-request (or per-voice default) Piper inference settings.
+/// `None` fields fall back to the voice default and then to the model config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Prosody {
+    /// Phoneme duration multiplier: > 1.0 speaks slower, < 1.0 faster
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length_scale: Option<f32>,
+    /// Generator noise (variation in voice quality)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noise_scale: Option<f32>,
+    /// Phoneme width noise (variation in timing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noise_w: Option<f32>,
+}
+
+impl Prosody {
+    /// Build from a speaking rate (1.0 = normal, 2.0 = twice as fast)
+    pub fn from_rate(rate: f32) -> Self {
+        Self {
+            length_scale: Some(1.0 / rate),
+            ..Self::default()
+        }
+    }
+
+    /// True when nothing overrides the model's own inference settings
+    pub fn is_default(&self) -> bool {
+        self.length_scale.is_none() && self.noise_scale.is_none() && self.noise_w.is_none()
+    }
+
+    /// Fill unset fields from `defaults`
+    pub fn or(self, defaults: Prosody) -> Self {
+        Self {
+            length_scale: self.length_scale.or(defaults.length_scale),
+            noise_scale: self.noise_scale.or(defaults.noise_scale),
+            noise_w: self.noise_w.or(defaults.noise_w),
+        }
+    }
+
+    /// Read per-voice defaults from a map.json voice object
+    pub(crate) fn from_json(obj: &serde_json::Map<String, serde_json::Value>) -> Self {
+        let field = |key: &str| obj.get(key).and_then(|x| x.as_f64()).map(|x| x as f32);
+        Self {
+            length_scale: field("length_scale"),
+            noise_scale: field("noise_scale"),
+            noise_w: field("noise_w"),
+        }
+    }
+}
+
+// f32 has no Hash impl; hash the bit patterns so prosody can be part of cache keys
+impl Hash for Prosody {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.length_scale.map(f32::to_bits).hash(state);
+        self.noise_scale.map(f32::to_bits).hash(state);
+        self.noise_w.map(f32::to_bits).hash(state);
+    }
+}
@@ -0,0 +1,164 @@
+//! Band-limited sample-rate conversion.
+//!
+//! Windowed-sinc interpolation (Kaiser window) with a precomputed polyphase filter table.
+//! When downsampling, the cutoff follows the output Nyquist frequency so no aliasing is
+//! introduced.
+
+/// Zero crossings of the sinc on each side of the kernel centre (at the input rate)
+const ZERO_CROSSINGS: usize = 24;
+/// Fraction of the Nyquist frequency kept (the rest is the transition band)
+const PASSBAND: f64 = 0.95;
+/// Kaiser window shape (≈ 90 dB stopband)
+const KAISER_BETA: f64 = 9.0;
+/// Maximum number of filter phases; rate pairs needing more use the nearest phase
+const MAX_PHASES: usize = 1024;
+
+/// Resample mono audio from `from` Hz to `to` Hz
+pub fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() || from == 0 || to == 0 {
+        return samples.to_vec();
+    }
+
+    let divisor = gcd(from, to);
+    let phases = ((to / divisor) as usize).min(MAX_PHASES);
+    let ratio = to as f64 / from as f64;
+    let cutoff = PASSBAND * ratio.min(1.0);
+    // The kernel widens as the cutoff drops
+    let half_width = (ZERO_CROSSINGS as f64 / cutoff.min(1.0)).ceil() as usize;
+    let table = FilterTable::new(phases, half_width, cutoff);
+
+    let out_len = (samples.len() as u64 * to as u64 / from as u64) as usize;
+    let step = from as f64 / to as f64;
+    let len = samples.len() as isize;
+
+    (0..out_len)
+        .map(|n| {
+            let t = n as f64 * step;
+            let mut base = t.floor() as isize;
+            let mut phase = ((t - base as f64) * phases as f64).round() as usize;
+            if phase == phases {
+                base += 1;
+                phase = 0;
+            }
+
+            let taps = table.phase(phase);
+            let start = base - half_width as isize + 1;
+            let mut acc = 0.0f32;
+            for (k, &coef) in taps.iter().enumerate() {
+                let idx = start + k as isize;
+                if idx >= 0 && idx < len {
+                    acc += samples[idx as usize] * coef;
+                }
+            }
+            acc
+        })
+        .collect()
+}
+
+/// Kernel taps for every fractional offset, `2 * half_width` taps per phase
+struct FilterTable {
+    taps: Vec<f32>,
+    width: usize,
+}
+
+impl FilterTable {
+    fn new(phases: usize, half_width: usize, cutoff: f64) -> Self {
+        let width = 2 * half_width;
+        let mut taps = Vec::with_capacity(phases * width);
+        let i0_beta = bessel_i0(KAISER_BETA);
+
+        for phase in 0..phases {
+            let frac = phase as f64 / phases as f64;
+            for k in 0..width {
+                // Distance from the interpolation point to input sample `base - half_width + 1 + k`
+                let x = k as f64 - (half_width as f64 - 1.0) - frac;
+                let sinc = if x.abs() < 1e-9 {
+                    1.0
+                } else {
+                    let arg = std::f64::consts::PI * cutoff * x;
+                    arg.sin() / arg
+                };
+                let r = x / half_width as f64;
+                let window = if r.abs() >= 1.0 {
+                    0.0
+                } else {
+                    bessel_i0(KAISER_BETA * (1.0 - r * r).sqrt()) / i0_beta
+                };
+                taps.push((cutoff * sinc * window) as f32);
+            }
+        }
+
+        Self { taps, width }
+    }
+
+    fn phase(&self, phase: usize) -> &[f32] {
+        &self.taps[phase * self.width..(phase + 1) * self.width]
+    }
+}
+
+/// Modified Bessel function of the first kind, order 0 (power series)
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half = x / 2.0;
+    for k in 1..50 {
+        term *= (half / k as f64) * (half / k as f64);
+        sum += term;
+        if term < sum * 1e-12 {
+            break;
+        }
+    }
+    sum
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_resample_length() {
+        let input = vec![0.0; 22050];
+        assert_eq!(resample(&input, 22050, 48000).len(), 48000);
+        assert_eq!(resample(&input, 22050, 8000).len(), 8000);
+        assert_eq!(resample(&input, 22050, 22050).len(), 22050);
+    }
+
+    #[test]
+    fn test_resample_preserves_tone() {
+        let input = sine(440.0, 22050, 22050);
+        let output = resample(&input, 22050, 48000);
+        let expected = sine(440.0, 48000, 48000);
+        // Compare away from the edges
+        let error: f32 = output[1000..47000]
+            .iter()
+            .zip(&expected[1000..47000])
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max);
+        assert!(error < 0.01, "max error {error}");
+    }
+
+    #[test]
+    fn test_downsampling_removes_content_above_nyquist() {
+        // 6 kHz is above the 4 kHz Nyquist frequency of 8 kHz output
+        let input = sine(6000.0, 22050, 22050);
+        let output = resample(&input, 22050, 8000);
+        assert!(rms(&output[500..7500]) < 0.005, "rms {}", rms(&output[500..7500]));
+    }
+}
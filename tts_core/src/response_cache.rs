@@ -0,0 +1,230 @@
+//! In-memory response cache.
+//!
+//! Entries hold the normalized PCM of a response instead of encoded audio, so one entry serves
+//! every output format and transport; the requested container is encoded on the way out. The
+//! cache is bounded by the memory its entries take, evicting the least recently stored first.
+
+use std::{mem::size_of, sync::Arc, time::Instant};
+
+use lru::LruCache;
+use serde::Serialize;
+
+use crate::{
+    encoder::to_i16, Alignment, AudioFormat, CacheSource, ChunkSpan, EncodedAudio, LoudnessReport, WordTiming,
+};
+
+/// Size and budget of a response cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CacheUsage {
+    pub entries: usize,
+    pub bytes: u64,
+    pub max_bytes: u64,
+}
+
+/// Normalized samples of a response: 16-bit unless the output format has a higher resolution
+/// (24-bit and float WAV)
+#[derive(Debug, Clone)]
+pub(crate) enum CachedPcm {
+    I16(Arc<[i16]>),
+    F32(Arc<[f32]>),
+}
+
+impl CachedPcm {
+    pub(crate) fn new(samples: &[f32], format: AudioFormat) -> Self {
+        if format.is_high_resolution() {
+            Self::F32(samples.into())
+        } else {
+            Self::I16(samples.iter().map(|&s| to_i16(s)).collect())
+        }
+    }
+
+    pub(crate) fn is_float(&self) -> bool {
+        matches!(self, Self::F32(_))
+    }
+
+    pub(crate) fn byte_len(&self) -> usize {
+        match self {
+            Self::I16(samples) => samples.len() * size_of::<i16>(),
+            Self::F32(samples) => samples.len() * size_of::<f32>(),
+        }
+    }
+
+    /// Samples in [-1.0, 1.0]; 16-bit samples convert back to the exact same 16-bit values
+    pub(crate) fn to_f32(&self) -> Vec<f32> {
+        match self {
+            Self::I16(samples) => samples.iter().map(|&s| s as f32 / i16::MAX as f32).collect(),
+            Self::F32(samples) => samples.to_vec(),
+        }
+    }
+
+    /// Encode in the requested format (blocking: FLAC, Opus and MP3 take a while)
+    pub(crate) fn encode(&self, sample_rate: u32, format: AudioFormat) -> anyhow::Result<EncodedAudio> {
+        format.encode(&self.to_f32(), sample_rate)
+    }
+
+    /// Little-endian sample bytes
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::I16(samples) => samples.iter().flat_map(|s| s.to_le_bytes()).collect(),
+            Self::F32(samples) => samples.iter().flat_map(|s| s.to_le_bytes()).collect(),
+        }
+    }
+
+    /// Inverse of `to_bytes`
+    pub(crate) fn from_bytes(bytes: &[u8], float: bool) -> Option<Self> {
+        if float {
+            bytes.chunks_exact(4).remainder().is_empty().then(|| {
+                Self::F32(bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect())
+            })
+        } else {
+            bytes.chunks_exact(2).remainder().is_empty().then(|| Self::I16(bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect()))
+        }
+    }
+}
+
+/// Cached audio response
+#[derive(Clone)]
+pub(crate) struct CachedResponse {
+    pub(crate) pcm: CachedPcm,
+    pub(crate) sample_rate: u32, // of the PCM; encoders may resample
+    pub(crate) duration_ms: u64,
+    pub(crate) loudness: LoudnessReport,
+    pub(crate) alignment: Option<Alignment>,
+    pub(crate) cached_at: Instant,
+    pub(crate) source: CacheSource,
+}
+
+impl CachedResponse {
+    /// Approximate memory held by the entry
+    fn size(&self) -> usize {
+        let alignment = self.alignment.as_ref().map_or(0, |alignment| {
+            alignment.words.iter().map(|w| size_of::<WordTiming>() + w.word.len()).sum::<usize>()
+                + alignment.chunks.len() * size_of::<ChunkSpan>()
+        });
+        size_of::<Self>() + self.pcm.byte_len() + self.source.text.len() + self.source.spoken.len() + alignment
+    }
+}
+
+pub(crate) struct ResponseCache {
+    entries: LruCache<u64, CachedResponse>,
+    bytes: usize,
+    max_bytes: usize,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache").field("usage", &self.usage()).finish()
+    }
+}
+
+impl ResponseCache {
+    pub(crate) fn new(max_bytes: usize) -> Self {
+        Self { entries: LruCache::unbounded(), bytes: 0, max_bytes }
+    }
+
+    pub(crate) fn peek(&self, key: &u64) -> Option<&CachedResponse> {
+        self.entries.peek(key)
+    }
+
+    /// Store a response, evicting the oldest entries to stay within the budget. Responses
+    /// larger than the whole budget are not cached.
+    pub(crate) fn put(&mut self, key: u64, response: CachedResponse) {
+        let size = response.size();
+        if size > self.max_bytes {
+            self.pop(&key);
+            return;
+        }
+        if let Some(previous) = self.entries.put(key, response) {
+            self.bytes -= previous.size();
+        }
+        self.bytes += size;
+        while self.bytes > self.max_bytes {
+            match self.entries.pop_lru() {
+                Some((_, evicted)) => self.bytes -= evicted.size(),
+                None => break,
+            }
+        }
+    }
+
+    pub(crate) fn pop(&mut self, key: &u64) -> Option<CachedResponse> {
+        let removed = self.entries.pop(key)?;
+        self.bytes -= removed.size();
+        Some(removed)
+    }
+
+    /// Remove all entries; returns how many there were
+    pub(crate) fn clear(&mut self) -> usize {
+        let entries = self.entries.len();
+        self.entries.clear();
+        self.bytes = 0;
+        entries
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&u64, &CachedResponse)> {
+        self.entries.iter()
+    }
+
+    pub(crate) fn usage(&self) -> CacheUsage {
+        CacheUsage {
+            entries: self.entries.len(),
+            bytes: self.bytes as u64,
+            max_bytes: self.max_bytes as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputKind;
+
+    fn response(samples: usize) -> CachedResponse {
+        CachedResponse {
+            pcm: CachedPcm::new(&vec![0.25; samples], AudioFormat::Wav),
+            sample_rate: 22050,
+            duration_ms: 0,
+            loudness: LoudnessReport::default(),
+            alignment: None,
+            cached_at: Instant::now(),
+            source: CacheSource { language: "de_DE".to_string(), voice: None, text: String::new(), spoken: String::new(), kind: InputKind::Text },
+        }
+    }
+
+    #[test]
+    fn test_bounded_by_bytes() {
+        let entry_size = response(1000).size();
+        let mut cache = ResponseCache::new(entry_size * 2);
+        cache.put(1, response(1000));
+        cache.put(2, response(1000));
+        cache.put(3, response(1000));
+
+        assert!(cache.peek(&1).is_none());
+        assert_eq!(cache.usage().entries, 2);
+        assert_eq!(cache.usage().bytes, entry_size as u64 * 2);
+
+        // Too large for the budget: not cached, and the older entries stay
+        cache.put(4, response(10_000));
+        assert!(cache.peek(&4).is_none());
+        assert_eq!(cache.usage().entries, 2);
+        cache.pop(&2);
+        assert_eq!(cache.usage().bytes, entry_size as u64);
+    }
+
+    #[test]
+    fn test_pcm_round_trips() {
+        let samples = [0.0, 0.5, -0.25, 1.0, -1.0, 0.123_456];
+        let pcm = CachedPcm::new(&samples, AudioFormat::Wav);
+        assert_eq!(pcm.byte_len(), 12);
+        // Encoding from cached 16-bit PCM gives the same bytes as encoding the original
+        assert_eq!(
+            pcm.encode(16000, AudioFormat::Pcm).unwrap().bytes,
+            AudioFormat::Pcm.encode(&samples, 16000).unwrap().bytes
+        );
+        let restored = CachedPcm::from_bytes(&pcm.to_bytes(), pcm.is_float()).unwrap();
+        assert_eq!(restored.to_f32(), pcm.to_f32());
+
+        let float = CachedPcm::new(&samples, AudioFormat::WavF32);
+        assert_eq!(float.to_f32(), samples);
+        assert!(CachedPcm::from_bytes(&[0, 0, 0], true).is_none());
+    }
+}
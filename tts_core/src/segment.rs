@@ -0,0 +1,277 @@
+//! Sentence and clause segmentation.
+//!
+//! Splits text at sentence endings, semicolons/colons and commas while skipping the periods of
+//! known abbreviations ("z.B.", "Dr.", "M."), decimal points and thousands separators. The
+//! abbreviation lists live in `data/abbreviations/<language>.txt`; an entry may carry a spoken
+//! expansion ("bzw. = beziehungsweise") that `expand_abbreviations` substitutes before synthesis.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const ABBREVIATION_FILES: &[(&str, &str)] = &[
+    ("en", include_str!("../data/abbreviations/en.txt")),
+    ("de", include_str!("../data/abbreviations/de.txt")),
+    ("fr", include_str!("../data/abbreviations/fr.txt")),
+    ("es", include_str!("../data/abbreviations/es.txt")),
+    ("it", include_str!("../data/abbreviations/it.txt")),
+    ("nl", include_str!("../data/abbreviations/nl.txt")),
+];
+
+/// Closing characters that stay with the sentence they end
+const CLOSERS: &[char] = &['"', '\'', ')', ']', '»', '«', '”', '’'];
+
+/// One entry of an abbreviation list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Abbreviation {
+    /// Written form, including its periods ("z.B.")
+    pub text: String,
+    /// Spoken form, if it should be expanded before synthesis
+    pub expansion: Option<String>,
+}
+
+/// Kind of break that ends a segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// `.`, `!`, `?`, `…`
+    Sentence,
+    /// `;` and `:`
+    Clause,
+    /// `,`
+    Comma,
+}
+
+/// A piece of text up to and including its closing punctuation and one following space
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    /// `None` for trailing text without closing punctuation
+    pub boundary: Option<Boundary>,
+}
+
+/// Abbreviations for a language key (`de_DE`) or code (`de`), longest first.
+/// Languages without a list use the English one.
+pub fn abbreviations_for(language: &str) -> &'static [Abbreviation] {
+    own_abbreviations(language)
+        .or_else(|| own_abbreviations("en"))
+        .unwrap_or(&[])
+}
+
+fn own_abbreviations(language: &str) -> Option<&'static [Abbreviation]> {
+    static LISTS: OnceLock<HashMap<&'static str, Vec<Abbreviation>>> = OnceLock::new();
+    let lists = LISTS.get_or_init(|| {
+        ABBREVIATION_FILES
+            .iter()
+            .map(|(code, data)| (*code, parse_abbreviations(data)))
+            .collect()
+    });
+    let code = language.split(['_', '-']).next().unwrap_or(language);
+    lists.get(code).map(|list| list.as_slice())
+}
+
+/// Parse an abbreviation file: one `abbreviation` or `abbreviation = expansion` per line,
+/// `#` starts a comment line
+fn parse_abbreviations(data: &str) -> Vec<Abbreviation> {
+    let mut list: Vec<Abbreviation> = data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once(" = ") {
+            Some((text, expansion)) => Abbreviation {
+                text: text.trim().to_string(),
+                expansion: Some(expansion.trim().to_string()),
+            },
+            None => Abbreviation { text: line.to_string(), expansion: None },
+        })
+        .collect();
+    list.sort_by_key(|a| std::cmp::Reverse(a.text.chars().count()));
+    list
+}
+
+/// Split `text` into segments at sentence, clause and comma boundaries
+pub fn segment_text(text: &str, language: &str) -> Vec<Segment> {
+    let chars: Vec<char> = text.chars().collect();
+    let abbreviations = abbreviations_for(language);
+    let mut segments = Vec::new();
+    let mut current = String::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        current.push(c);
+
+        let boundary = match c {
+            '.' | '!' | '?' | '…' => (c != '.' || is_sentence_period(&chars, i, abbreviations)).then_some(Boundary::Sentence),
+            ';' => Some(Boundary::Clause),
+            ':' => (!between_digits(&chars, i)).then_some(Boundary::Clause),
+            ',' => (!is_number_comma(&chars, i)).then_some(Boundary::Comma),
+            _ => None,
+        };
+
+        if let Some(boundary) = boundary {
+            // Keep "?!", "..." and closing quotes/brackets with the segment
+            while let Some(&next) = chars.get(i + 1) {
+                let trailing = match boundary {
+                    Boundary::Sentence => matches!(next, '.' | '!' | '?' | '…') || CLOSERS.contains(&next),
+                    _ => CLOSERS.contains(&next),
+                };
+                if !trailing {
+                    break;
+                }
+                current.push(next);
+                i += 1;
+            }
+            if chars.get(i + 1) == Some(&' ') {
+                current.push(' ');
+                i += 1;
+            }
+            segments.push(Segment { text: std::mem::take(&mut current), boundary: Some(boundary) });
+        }
+        i += 1;
+    }
+
+    if !current.trim().is_empty() {
+        segments.push(Segment { text: current, boundary: None });
+    }
+    segments
+}
+
+/// Replace abbreviations that have a spoken expansion ("z.B." -> "zum Beispiel").
+/// Only languages with their own list are expanded.
+pub fn expand_abbreviations(text: &str, language: &str) -> String {
+    let Some(abbreviations) = own_abbreviations(language) else {
+        return text.to_string();
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+
+    let mut i = 0;
+    while i < chars.len() {
+        let found = abbreviations
+            .iter()
+            .filter(|a| a.expansion.is_some())
+            .find_map(|a| match_at(&chars, i, &a.text).map(|len| (a, len)));
+        match found {
+            Some((abbreviation, len)) => {
+                let expansion = abbreviation.expansion.as_deref().unwrap_or_default();
+                // Keep the capital at the start of a sentence ("Z.B. ..." -> "Zum Beispiel ...")
+                if chars[i].is_uppercase() && !abbreviation.text.starts_with(char::is_uppercase) {
+                    let mut first = expansion.chars();
+                    out.extend(first.next().into_iter().flat_map(char::to_uppercase));
+                    out.push_str(first.as_str());
+                } else {
+                    out.push_str(expansion);
+                }
+                // An abbreviation at the very end still closes the sentence
+                if abbreviation.text.ends_with('.') && chars[i + len..].iter().all(|c| c.is_whitespace()) {
+                    out.push('.');
+                }
+                i += len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// True if the period at `i` ends a sentence
+fn is_sentence_period(chars: &[char], i: usize, abbreviations: &[Abbreviation]) -> bool {
+    if between_digits(chars, i) {
+        return false;
+    }
+    // Periods inside a word ("example.com") are not boundaries; "end.Next" is
+    if let Some(&next) = chars.get(i + 1) {
+        if next.is_alphanumeric() {
+            let prev_lower = i > 0 && chars[i - 1].is_lowercase();
+            if !(prev_lower && next.is_uppercase()) {
+                return false;
+            }
+        }
+    }
+    !abbreviations.iter().any(|a| covers(chars, i, &a.text))
+}
+
+/// True if an occurrence of `abbreviation` includes the period at `i`
+fn covers(chars: &[char], i: usize, abbreviation: &str) -> bool {
+    abbreviation
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| c == '.')
+        .any(|(k, _)| i >= k && match_at(chars, i - k, abbreviation).is_some())
+}
+
+/// Length in chars if `abbreviation` starts at `start` as a whole word. The first letter
+/// also matches in upper case (sentence start).
+fn match_at(chars: &[char], start: usize, abbreviation: &str) -> Option<usize> {
+    if start > 0 && chars[start - 1].is_alphanumeric() {
+        return None;
+    }
+    let mut len = 0;
+    for (k, a) in abbreviation.chars().enumerate() {
+        let c = *chars.get(start + k)?;
+        let matches = c == a || (k == 0 && c.is_uppercase() && a.to_uppercase().eq(c.to_uppercase()));
+        if !matches {
+            return None;
+        }
+        len += 1;
+    }
+    // Abbreviations ending in a letter ("Mme", "Sig.ra") must end the word
+    let ends_word = !abbreviation.ends_with(char::is_alphanumeric)
+        || chars.get(start + len).is_none_or(|c| !c.is_alphanumeric());
+    ends_word.then_some(len)
+}
+
+fn between_digits(chars: &[char], i: usize) -> bool {
+    i > 0 && chars[i - 1].is_ascii_digit() && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Commas in numbers ("1,000", "3,14") and lists of numbers ("1 , 2")
+fn is_number_comma(chars: &[char], i: usize) -> bool {
+    if i == 0 || i + 1 >= chars.len() {
+        return false;
+    }
+    between_digits(chars, i) || (chars[i - 1].is_whitespace() && i > 1 && chars[i - 2].is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(segments: &[Segment]) -> Vec<&str> {
+        segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_abbreviations_do_not_end_sentences() {
+        let segments = segment_text("Das ist z.B. ein Test bzw. ein Beispiel. Nr. 5 folgt.", "de_DE");
+        assert_eq!(texts(&segments), vec!["Das ist z.B. ein Test bzw. ein Beispiel. ", "Nr. 5 folgt."]);
+
+        let segments = segment_text("M. Dupont est là. Mme. Martin aussi!", "fr_FR");
+        assert_eq!(texts(&segments), vec!["M. Dupont est là. ", "Mme. Martin aussi!"]);
+    }
+
+    #[test]
+    fn test_boundaries_and_numbers() {
+        let segments = segment_text("Wait... really?! Yes: 3.14, 1,000 and more", "en_US");
+        assert_eq!(texts(&segments), vec!["Wait... ", "really?! ", "Yes: ", "3.14, ", "1,000 and more"]);
+        assert_eq!(
+            segments.iter().map(|s| s.boundary).collect::<Vec<_>>(),
+            vec![Some(Boundary::Sentence), Some(Boundary::Sentence), Some(Boundary::Clause), Some(Boundary::Comma), None]
+        );
+    }
+
+    #[test]
+    fn test_expand_abbreviations() {
+        assert_eq!(
+            expand_abbreviations("Z.B. Obst, Gemüse usw.", "de_DE"),
+            "Zum Beispiel Obst, Gemüse und so weiter."
+        );
+        assert_eq!(expand_abbreviations("Dr. Who, etc. Fin.", "en_US"), "Doctor Who, et cetera Fin.");
+        assert_eq!(expand_abbreviations("Sr. García, etc.", "es_ES"), "Señor García, etcétera.");
+        // "Fr." is also Freitag, so it is kept as written
+        assert_eq!(expand_abbreviations("Fr. Meier kommt am Fr. 3. Mai.", "de_DE"), "Fr. Meier kommt am Fr. 3. Mai.");
+        assert_eq!(expand_abbreviations("Hr. Bz.", "uk_UA"), "Hr. Bz.");
+    }
+}
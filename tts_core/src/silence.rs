@@ -0,0 +1,140 @@
+//! Energy-based silence removal.
+//!
+//! The audio is cut into 10 ms frames; a frame is silent when its RMS level is below -50 dBFS.
+//! Trimming removes the silent frames at the start and end (keeping a short margin so that
+//! onsets and releases are not clipped); compression shortens every silence between speech to
+//! `max_silence_ms`, cutting from its middle. Synthesis removes silence from each speech chunk
+//! before it is timed and joined, so word timings describe the output as is.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+const FRAME_MS: u32 = 10;
+/// RMS level (dBFS) below which a frame is silent
+const THRESHOLD_DB: f32 = -50.0;
+/// Silence kept before the first and after the last speech frame when trimming
+const EDGE_MARGIN_MS: u32 = 20;
+
+/// Silence removal settings of a request. Off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SilenceOptions {
+    /// Trim leading and trailing silence
+    #[serde(default)]
+    pub trim_silence: bool,
+    /// Shorten silences between speech to at most this long
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_silence_ms: Option<u32>,
+}
+
+impl SilenceOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.trim_silence || self.max_silence_ms.is_some()
+    }
+
+    /// A pause between speech, shortened to `max_silence_ms`
+    pub(crate) fn cap_pause_ms(&self, pause_ms: u32) -> u32 {
+        self.max_silence_ms.map_or(pause_ms, |max| pause_ms.min(max))
+    }
+}
+
+/// Remove silence from `samples` in place. Returns the removed spans as sample ranges of the
+/// input, in ascending order.
+pub(crate) fn remove_silence(samples: &mut Vec<f32>, sample_rate: u32, options: &SilenceOptions) -> Vec<Range<usize>> {
+    if !options.is_enabled() || samples.is_empty() {
+        return Vec::new();
+    }
+    let to_samples = |ms: u32| (sample_rate as u64 * ms as u64 / 1000) as usize;
+    let frame = to_samples(FRAME_MS).max(1);
+    let threshold = 10f32.powf(THRESHOLD_DB / 20.0);
+    let speech: Vec<bool> = samples
+        .chunks(frame)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt() >= threshold)
+        .collect();
+
+    let len = samples.len();
+    let (Some(first), Some(last)) = (speech.iter().position(|&s| s), speech.iter().rposition(|&s| s)) else {
+        // No speech at all
+        if !options.trim_silence {
+            return Vec::new();
+        }
+        samples.clear();
+        return std::iter::once(0..len).collect();
+    };
+
+    let mut removed = Vec::new();
+    let margin = to_samples(EDGE_MARGIN_MS);
+    if options.trim_silence && first * frame > margin {
+        removed.push(0..first * frame - margin);
+    }
+    if let Some(max_silence) = options.max_silence_ms.map(to_samples) {
+        let mut frame_index = first;
+        while frame_index < last {
+            if speech[frame_index] {
+                frame_index += 1;
+                continue;
+            }
+            let run_start = frame_index;
+            while !speech[frame_index] {
+                frame_index += 1;
+            }
+            let (start, end) = (run_start * frame, frame_index * frame);
+            if end - start > max_silence {
+                let head = max_silence / 2;
+                removed.push(start + head..end - (max_silence - head));
+            }
+        }
+    }
+    let speech_end = ((last + 1) * frame).min(len);
+    if options.trim_silence && len - speech_end > margin {
+        removed.push(speech_end + margin..len);
+    }
+
+    let mut position = 0;
+    let mut spans = removed.iter().peekable();
+    samples.retain(|_| {
+        while spans.next_if(|span| span.end <= position).is_some() {}
+        let keep = spans.peek().is_none_or(|span| position < span.start);
+        position += 1;
+        keep
+    });
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1000 Hz: 10-sample frames
+    fn audio(parts: &[(bool, usize)]) -> Vec<f32> {
+        parts.iter().flat_map(|&(speech, len)| std::iter::repeat_n(if speech { 0.5 } else { 0.0 }, len)).collect()
+    }
+
+    #[test]
+    fn test_trims_edges() {
+        let mut samples = audio(&[(false, 300), (true, 100), (false, 50), (true, 100), (false, 500)]);
+        let options = SilenceOptions { trim_silence: true, max_silence_ms: None };
+        let removed = remove_silence(&mut samples, 1000, &options);
+        assert_eq!(removed, vec![0..280, 570..1050]);
+        assert_eq!(samples.len(), 20 + 250 + 20);
+        assert_eq!(samples[20], 0.5);
+
+        // Silence only; disabled options change nothing
+        let mut silence = vec![0.0; 100];
+        remove_silence(&mut silence, 1000, &options);
+        assert!(silence.is_empty());
+        let mut untouched = audio(&[(false, 300), (true, 100)]);
+        assert!(remove_silence(&mut untouched, 1000, &SilenceOptions::default()).is_empty());
+        assert_eq!(untouched.len(), 400);
+    }
+
+    #[test]
+    fn test_compresses_internal_silence() {
+        let mut samples = audio(&[(false, 300), (true, 100), (false, 400), (true, 100), (false, 30), (true, 100)]);
+        let options = SilenceOptions { trim_silence: false, max_silence_ms: Some(100) };
+        let removed = remove_silence(&mut samples, 1000, &options);
+        // Only the long pause is shortened, from its middle; edges stay
+        assert_eq!(removed, vec![450..750]);
+        assert_eq!(samples.len(), 1030 - 300);
+    }
+}
@@ -0,0 +1,749 @@
+//! SSML subset parser.
+//!
+//! Supported elements: `<speak>`, `<break time|strength>`, `<prosody rate>`,
+//! `<say-as interpret-as="characters|spell-out|cardinal|date" format>`, `<voice name>`,
+//! `<sub alias>`, `<p>` and `<s>`; any other element is rejected. The parsed document is a flat list of text segments (each
+//! with its voice and rate) and explicit silences, which `TtsManager::synthesize_ssml` renders
+//! segment by segment instead of splitting the text at punctuation.
+
+use crate::normalize::verbalizer_for;
+
+/// Pause after a `</s>` sentence that is followed by more text
+const SENTENCE_BREAK_MS: u32 = 400;
+/// Pause after a `</p>` paragraph that is followed by more text
+const PARAGRAPH_BREAK_MS: u32 = 700;
+/// Upper bound for a single `<break>`
+const MAX_BREAK_MS: u32 = 10_000;
+
+/// Malformed SSML, with the location of the problem in the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsmlError {
+    /// Byte offset into the input
+    pub offset: usize,
+    /// 1-based line and column (in characters)
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SsmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid SSML at line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for SsmlError {}
+
+/// One unit of an SSML document
+#[derive(Debug, Clone, PartialEq)]
+pub enum SsmlSegment {
+    /// Text to synthesize. `language`/`voice` are `None` when the request defaults apply.
+    Text {
+        text: String,
+        language: Option<String>,
+        voice: Option<String>,
+        /// Speaking rate multiplier (1.0 = normal)
+        rate: f32,
+    },
+    /// Explicit silence
+    Break { duration_ms: u32 },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SsmlDocument {
+    pub segments: Vec<SsmlSegment>,
+}
+
+/// Parse an SSML document. `default_language` is used to verbalize `say-as` dates until a
+/// `<voice name="ll_CC:voice">` switches the language.
+pub fn parse(input: &str, default_language: &str) -> Result<SsmlDocument, SsmlError> {
+    let tokens = tokenize(input)?;
+    Builder::new(input, default_language).build(tokens)
+}
+
+#[derive(Debug)]
+enum Token {
+    Text { text: String, offset: usize },
+    Start { name: String, attrs: Vec<(String, String)>, self_closing: bool, offset: usize },
+    End { name: String, offset: usize },
+}
+
+fn error_at(input: &str, offset: usize, message: impl Into<String>) -> SsmlError {
+    let before = &input[..offset.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    SsmlError {
+        offset,
+        line,
+        column,
+        message: message.into(),
+    }
+}
+
+fn decode_entities(input: &str, raw: &str, offset: usize) -> Result<String, SsmlError> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    let mut pos = offset;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let end = after
+            .find(';')
+            .ok_or_else(|| error_at(input, pos + amp, "unterminated entity"))?;
+        let entity = &after[1..end];
+        let decoded = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| error_at(input, pos + amp, format!("invalid character reference &{};", entity)))?,
+            _ if entity.starts_with('#') => entity[1..]
+                .parse::<u32>()
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| error_at(input, pos + amp, format!("invalid character reference &{};", entity)))?,
+            _ => return Err(error_at(input, pos + amp, format!("unknown entity &{};", entity))),
+        };
+        out.push(decoded);
+        rest = &after[end + 1..];
+        pos += amp + end + 1;
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, SsmlError> {
+    let mut tokens = Vec::new();
+    let mut pos = 0usize;
+
+    while pos < input.len() {
+        let rest = &input[pos..];
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text {
+                text: decode_entities(input, &rest[..end], pos)?,
+                offset: pos,
+            });
+            pos += end;
+            continue;
+        }
+
+        // Comments and XML declarations are skipped
+        if rest.starts_with("<!--") {
+            let end = rest
+                .find("-->")
+                .ok_or_else(|| error_at(input, pos, "unterminated comment"))?;
+            pos += end + 3;
+            continue;
+        }
+        if rest.starts_with("<?") {
+            let end = rest
+                .find("?>")
+                .ok_or_else(|| error_at(input, pos, "unterminated processing instruction"))?;
+            pos += end + 2;
+            continue;
+        }
+
+        let end = find_tag_end(rest).ok_or_else(|| error_at(input, pos, "unterminated tag"))?;
+        let inner = &rest[1..end];
+        let tag_offset = pos;
+        pos += end + 1;
+
+        if let Some(name) = inner.strip_prefix('/') {
+            let name = name.trim();
+            if !is_valid_name(name) {
+                return Err(error_at(input, tag_offset, format!("invalid closing tag </{}>", name)));
+            }
+            tokens.push(Token::End {
+                name: name.to_string(),
+                offset: tag_offset,
+            });
+            continue;
+        }
+
+        let (inner, self_closing) = match inner.strip_suffix('/') {
+            Some(inner) => (inner, true),
+            None => (inner, false),
+        };
+        let name_end = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        let name = &inner[..name_end];
+        if !is_valid_name(name) {
+            return Err(error_at(input, tag_offset, format!("invalid tag name '{}'", name)));
+        }
+        let attrs = parse_attributes(input, &inner[name_end..], tag_offset + 1 + name_end)?;
+        tokens.push(Token::Start {
+            name: name.to_string(),
+            attrs,
+            self_closing,
+            offset: tag_offset,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Index of the `>` closing a tag, skipping over quoted attribute values
+fn find_tag_end(rest: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in rest.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '<') => return None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
+
+fn parse_attributes(input: &str, raw: &str, offset: usize) -> Result<Vec<(String, String)>, SsmlError> {
+    let mut attrs = Vec::new();
+    let mut pos = 0usize;
+
+    loop {
+        let rest = &raw[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        if trimmed.is_empty() {
+            break;
+        }
+
+        let eq = trimmed
+            .find('=')
+            .ok_or_else(|| error_at(input, offset + pos, "attribute without value"))?;
+        let name = trimmed[..eq].trim();
+        if !is_valid_name(name) {
+            return Err(error_at(input, offset + pos, format!("invalid attribute name '{}'", name)));
+        }
+
+        let after_eq = &trimmed[eq + 1..];
+        let value_start = after_eq.trim_start();
+        let quote = value_start
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| error_at(input, offset + pos, format!("attribute '{}' must be quoted", name)))?;
+        let value_end = value_start[1..]
+            .find(quote)
+            .ok_or_else(|| error_at(input, offset + pos, format!("unterminated value for attribute '{}'", name)))?;
+        let value = decode_entities(input, &value_start[1..1 + value_end], offset + pos)?;
+
+        attrs.push((name.to_string(), value));
+        let consumed = trimmed.len() - value_start.len() + value_end + 2;
+        pos += consumed;
+    }
+
+    Ok(attrs)
+}
+
+/// Inherited state for the current element
+#[derive(Debug, Clone)]
+struct Context {
+    language: Option<String>,
+    voice: Option<String>,
+    rate: f32,
+}
+
+/// Element whose text content is rewritten before it is spoken
+#[derive(Debug)]
+enum Capture {
+    SayAs { interpret_as: String, format: Option<String>, text: String },
+    Sub { alias: String },
+}
+
+struct Builder<'a> {
+    input: &'a str,
+    default_language: &'a str,
+    segments: Vec<SsmlSegment>,
+    /// Open elements and the context active inside them
+    stack: Vec<(String, Context)>,
+    capture: Option<(Capture, usize)>,
+    pending_boundary: Option<u32>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(input: &'a str, default_language: &'a str) -> Self {
+        Self {
+            input,
+            default_language,
+            segments: Vec::new(),
+            stack: Vec::new(),
+            capture: None,
+            pending_boundary: None,
+        }
+    }
+
+    fn context(&self) -> Context {
+        self.stack
+            .last()
+            .map(|(_, ctx)| ctx.clone())
+            .unwrap_or(Context { language: None, voice: None, rate: 1.0 })
+    }
+
+    fn attr<'t>(attrs: &'t [(String, String)], name: &str) -> Option<&'t str> {
+        attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    fn build(mut self, tokens: Vec<Token>) -> Result<SsmlDocument, SsmlError> {
+        let mut saw_root = false;
+
+        for token in tokens {
+            match token {
+                Token::Text { text, offset } => {
+                    if self.stack.is_empty() {
+                        if text.trim().is_empty() {
+                            continue;
+                        }
+                        return Err(error_at(self.input, offset, "text outside of <speak>"));
+                    }
+                    match &mut self.capture {
+                        Some((Capture::SayAs { text: captured, .. }, _)) => captured.push_str(&text),
+                        Some((Capture::Sub { .. }, _)) => {}
+                        None => self.push_text(&text),
+                    }
+                }
+                Token::Start { name, attrs, self_closing, offset } => {
+                    if self.stack.is_empty() {
+                        if saw_root {
+                            return Err(error_at(self.input, offset, "content after </speak>"));
+                        }
+                        if name != "speak" {
+                            return Err(error_at(self.input, offset, format!("expected <speak> root element, found <{}>", name)));
+                        }
+                        saw_root = true;
+                    } else if name == "speak" {
+                        return Err(error_at(self.input, offset, "nested <speak> element"));
+                    }
+                    if self.capture.is_some() {
+                        return Err(error_at(self.input, offset, format!("<{}> is not allowed inside <say-as> or <sub>", name)));
+                    }
+                    self.start_element(&name, &attrs, self_closing, offset)?;
+                }
+                Token::End { name, offset } => {
+                    let (open, _) = self
+                        .stack
+                        .pop()
+                        .ok_or_else(|| error_at(self.input, offset, format!("unexpected closing tag </{}>", name)))?;
+                    if open != name {
+                        return Err(error_at(self.input, offset, format!("expected </{}>, found </{}>", open, name)));
+                    }
+                    self.end_element(&name)?;
+                }
+            }
+        }
+
+        if let Some((name, _)) = self.stack.last() {
+            return Err(error_at(self.input, self.input.len(), format!("unclosed <{}> element", name)));
+        }
+        if !saw_root {
+            return Err(error_at(self.input, 0, "expected <speak> root element"));
+        }
+
+        Ok(SsmlDocument { segments: self.segments })
+    }
+
+    fn start_element(
+        &mut self,
+        name: &str,
+        attrs: &[(String, String)],
+        self_closing: bool,
+        offset: usize,
+    ) -> Result<(), SsmlError> {
+        let mut ctx = self.context();
+
+        match name {
+            "speak" | "p" | "s" => {}
+            "break" => {
+                let duration_ms = match (Self::attr(attrs, "time"), Self::attr(attrs, "strength")) {
+                    (Some(time), _) => parse_time(time)
+                        .ok_or_else(|| error_at(self.input, offset, format!("invalid break time '{}'", time)))?,
+                    (None, Some(strength)) => strength_ms(strength)
+                        .ok_or_else(|| error_at(self.input, offset, format!("invalid break strength '{}'", strength)))?,
+                    (None, None) => strength_ms("medium").unwrap_or(0),
+                };
+                self.pending_boundary = None;
+                if duration_ms > 0 {
+                    self.segments.push(SsmlSegment::Break {
+                        duration_ms: duration_ms.min(MAX_BREAK_MS),
+                    });
+                }
+            }
+            "prosody" => {
+                if let Some(rate) = Self::attr(attrs, "rate") {
+                    let factor = parse_rate(rate)
+                        .ok_or_else(|| error_at(self.input, offset, format!("invalid prosody rate '{}'", rate)))?;
+                    ctx.rate *= factor;
+                }
+            }
+            "voice" => {
+                let voice_name = Self::attr(attrs, "name")
+                    .ok_or_else(|| error_at(self.input, offset, "<voice> requires a name attribute"))?;
+                match voice_name.split_once(':') {
+                    Some((language, voice)) => {
+                        ctx.language = Some(language.to_string());
+                        ctx.voice = Some(voice.to_string());
+                    }
+                    None => ctx.voice = Some(voice_name.to_string()),
+                }
+            }
+            "say-as" => {
+                let interpret_as = Self::attr(attrs, "interpret-as")
+                    .ok_or_else(|| error_at(self.input, offset, "<say-as> requires an interpret-as attribute"))?;
+                if !matches!(interpret_as, "characters" | "spell-out" | "cardinal" | "number" | "date") {
+                    return Err(error_at(self.input, offset, format!("unsupported interpret-as '{}'", interpret_as)));
+                }
+                self.capture = Some((
+                    Capture::SayAs {
+                        interpret_as: interpret_as.to_string(),
+                        format: Self::attr(attrs, "format").map(|s| s.to_string()),
+                        text: String::new(),
+                    },
+                    offset,
+                ));
+            }
+            "sub" => {
+                let alias = Self::attr(attrs, "alias")
+                    .ok_or_else(|| error_at(self.input, offset, "<sub> requires an alias attribute"))?;
+                self.capture = Some((Capture::Sub { alias: alias.to_string() }, offset));
+            }
+            _ => return Err(error_at(self.input, offset, format!("unsupported element <{}>", name))),
+        }
+
+        if self_closing {
+            // Nothing to capture for an empty element
+            if let Some((capture, offset)) = self.capture.take() {
+                self.stack.push((name.to_string(), ctx));
+                self.finish_capture(capture, offset)?;
+                self.stack.pop();
+            }
+        } else {
+            self.stack.push((name.to_string(), ctx));
+        }
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> Result<(), SsmlError> {
+        match name {
+            "s" => self.boundary(SENTENCE_BREAK_MS),
+            "p" => self.boundary(PARAGRAPH_BREAK_MS),
+            "say-as" | "sub" => {
+                if let Some((capture, offset)) = self.capture.take() {
+                    // Push the context back temporarily so the rewritten text inherits it
+                    let ctx = self.context();
+                    self.stack.push((name.to_string(), ctx));
+                    self.finish_capture(capture, offset)?;
+                    self.stack.pop();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn finish_capture(&mut self, capture: Capture, _offset: usize) -> Result<(), SsmlError> {
+        let text = match capture {
+            Capture::Sub { alias } => alias,
+            Capture::SayAs { interpret_as, format, text } => {
+                let language = self
+                    .context()
+                    .language
+                    .unwrap_or_else(|| self.default_language.to_string());
+                say_as(&interpret_as, format.as_deref(), text.trim(), &language)
+            }
+        };
+        // Surround with spaces so the rewritten text doesn't merge with neighbours
+        self.push_text(&format!(" {} ", text));
+        Ok(())
+    }
+
+    fn boundary(&mut self, duration_ms: u32) {
+        let has_text = self.segments.iter().any(|s| matches!(s, SsmlSegment::Text { .. }));
+        if has_text {
+            self.pending_boundary = Some(self.pending_boundary.unwrap_or(0).max(duration_ms));
+        }
+    }
+
+    fn push_text(&mut self, raw: &str) {
+        // Collapse whitespace like an XML renderer would
+        let mut text = String::with_capacity(raw.len());
+        let mut last_space = false;
+        for c in raw.chars() {
+            if c.is_whitespace() {
+                if !last_space {
+                    text.push(' ');
+                }
+                last_space = true;
+            } else {
+                text.push(c);
+                last_space = false;
+            }
+        }
+        if text.trim().is_empty() {
+            // Keep word separation between adjacent text runs
+            if let Some(SsmlSegment::Text { text: prev, .. }) = self.segments.last_mut() {
+                if !text.is_empty() && !prev.ends_with(' ') {
+                    prev.push(' ');
+                }
+            }
+            return;
+        }
+
+        if let Some(duration_ms) = self.pending_boundary.take() {
+            self.segments.push(SsmlSegment::Break { duration_ms });
+        }
+
+        let ctx = self.context();
+        if let Some(SsmlSegment::Text { text: prev, language, voice, rate }) = self.segments.last_mut() {
+            if *language == ctx.language && *voice == ctx.voice && *rate == ctx.rate {
+                if prev.ends_with(' ') && text.starts_with(' ') {
+                    prev.push_str(&text[1..]);
+                } else {
+                    prev.push_str(&text);
+                }
+                return;
+            }
+        }
+        self.segments.push(SsmlSegment::Text {
+            text,
+            language: ctx.language,
+            voice: ctx.voice,
+            rate: ctx.rate,
+        });
+    }
+}
+
+/// Parse `500ms`, `1.5s` or a bare millisecond count
+fn parse_time(time: &str) -> Option<u32> {
+    let time = time.trim();
+    let ms = if let Some(ms) = time.strip_suffix("ms") {
+        ms.trim().parse::<f32>().ok()?
+    } else if let Some(s) = time.strip_suffix('s') {
+        s.trim().parse::<f32>().ok()? * 1000.0
+    } else {
+        time.parse::<f32>().ok()?
+    };
+    (ms.is_finite() && ms >= 0.0).then_some(ms as u32)
+}
+
+fn strength_ms(strength: &str) -> Option<u32> {
+    Some(match strength {
+        "none" => 0,
+        "x-weak" => 100,
+        "weak" => 200,
+        "medium" => 400,
+        "strong" => 700,
+        "x-strong" => 1000,
+        _ => return None,
+    })
+}
+
+/// Parse a prosody rate: keyword, percentage (`80%`) or multiplier (`1.2`)
+fn parse_rate(rate: &str) -> Option<f32> {
+    let rate = rate.trim();
+    let factor = match rate {
+        "x-slow" => 0.6,
+        "slow" => 0.8,
+        "medium" | "default" => 1.0,
+        "fast" => 1.25,
+        "x-fast" => 1.5,
+        _ => match rate.strip_suffix('%') {
+            Some(pct) => pct.trim().parse::<f32>().ok()? / 100.0,
+            None => rate.parse::<f32>().ok()?,
+        },
+    };
+    (factor.is_finite() && (0.25..=4.0).contains(&factor)).then_some(factor)
+}
+
+/// Rewrite `say-as` content into speakable text
+fn say_as(interpret_as: &str, format: Option<&str>, text: &str, language: &str) -> String {
+    match interpret_as {
+        "characters" | "spell-out" => text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        "cardinal" | "number" => say_cardinal(text, language).unwrap_or_else(|| text.to_string()),
+        "date" => say_date(text, format, language).unwrap_or_else(|| text.to_string()),
+        _ => text.to_string(),
+    }
+}
+
+/// Drop group separators so the number is read as one value, and write a decimal separator
+/// (`.` or `,`) the way the normalizer verbalizes it for `language`. `None` unless the text is
+/// a single number.
+fn say_cardinal(text: &str, language: &str) -> Option<String> {
+    let (decimal, groups): (char, &[char]) = match verbalizer_for(language) {
+        Some(v) => (v.decimal_separator(), v.group_separators()),
+        None => ('.', &[',']),
+    };
+    let chars: Vec<char> = text.trim().chars().collect();
+    let mut number = String::new();
+    let mut fraction = false;
+    for (i, &c) in chars.iter().enumerate() {
+        let digits_after = chars[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+        match c {
+            '0'..='9' => number.push(c),
+            '-' if i == 0 => number.push(c),
+            // A group separator is followed by exactly three digits
+            _ if groups.contains(&c) && !fraction && digits_after == 3 && number.ends_with(|c: char| c.is_ascii_digit()) => {}
+            '.' | ',' if !fraction && digits_after > 0 && number.ends_with(|c: char| c.is_ascii_digit()) => {
+                number.push(decimal);
+                fraction = true;
+            }
+            _ => return None,
+        }
+    }
+    number.ends_with(|c: char| c.is_ascii_digit()).then_some(number)
+}
+
+fn say_date(text: &str, format: Option<&str>, language: &str) -> Option<String> {
+    let parts: Vec<u32> = text
+        .split(['-', '.', '/'])
+        .map(|p| p.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let format = format.unwrap_or(if text.split(|c: char| !c.is_ascii_digit()).next()?.len() == 4 {
+        "ymd"
+    } else {
+        "dmy"
+    });
+    let (year, month, day) = match format {
+        "ymd" => (parts[0], parts[1], parts[2]),
+        "dmy" => (parts[2], parts[1], parts[0]),
+        "mdy" => (parts[2], parts[0], parts[1]),
+        _ => return None,
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Spelled out with numerals; text normalization turns them into words in context
+    let verbalizer = verbalizer_for(language).or_else(|| verbalizer_for("en"))?;
+    let month_name = verbalizer.month_name(month);
+    let code = language.split('_').next().unwrap_or(language);
+    Some(match code {
+        "de" => format!("{}. {} {}", day, month_name, year),
+        "es" => format!("{} de {} de {}", day, month_name, year),
+        "fr" | "it" | "nl" => format!("{} {} {}", day, month_name, year),
+        _ => format!("{} {}{}, {}", month_name, day, english_ordinal_suffix(day), year),
+    })
+}
+
+fn english_ordinal_suffix(day: u32) -> &'static str {
+    match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(t: &str) -> SsmlSegment {
+        SsmlSegment::Text { text: t.to_string(), language: None, voice: None, rate: 1.0 }
+    }
+
+    #[test]
+    fn test_parse_text_and_breaks() {
+        let doc = parse("<speak>Hello <break time=\"500ms\"/> world</speak>", "en_US").unwrap();
+        assert_eq!(
+            doc.segments,
+            vec![text("Hello "), SsmlSegment::Break { duration_ms: 500 }, text(" world")]
+        );
+    }
+
+    #[test]
+    fn test_sentences_and_paragraphs_insert_pauses_between_content() {
+        let doc = parse("<speak><p><s>One.</s><s>Two.</s></p><p>Three.</p></speak>", "en_US").unwrap();
+        assert_eq!(
+            doc.segments,
+            vec![
+                text("One."),
+                SsmlSegment::Break { duration_ms: SENTENCE_BREAK_MS },
+                text("Two."),
+                SsmlSegment::Break { duration_ms: PARAGRAPH_BREAK_MS },
+                text("Three."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_voice_prosody_and_rewrites() {
+        let doc = parse(
+            "<speak><voice name=\"de_DE:thorsten\"><prosody rate=\"slow\">Am <say-as interpret-as=\"date\">2025-05-12</say-as></prosody></voice> <sub alias=\"World Wide Web\">WWW</sub> <say-as interpret-as=\"characters\">ABC</say-as></speak>",
+            "en_US",
+        )
+        .unwrap();
+        assert_eq!(
+            doc.segments,
+            vec![
+                SsmlSegment::Text {
+                    text: "Am 12. Mai 2025 ".to_string(),
+                    language: Some("de_DE".to_string()),
+                    voice: Some("thorsten".to_string()),
+                    rate: 0.8,
+                },
+                text(" World Wide Web A B C "),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cardinal_keeps_decimals() {
+        let cardinal = |number: &str, language: &str| say_as("cardinal", None, number, language);
+        assert_eq!(cardinal("1.234.567", "de_DE"), "1234567");
+        assert_eq!(cardinal("1,234", "en_US"), "1234");
+        assert_eq!(cardinal("-42", "en_US"), "-42");
+        // Decimals keep their separator, in the form the language's normalizer reads
+        assert_eq!(cardinal("3.5", "en_US"), "3.5");
+        assert_eq!(cardinal("3.5", "de_DE"), "3,5");
+        assert_eq!(cardinal("1.000,25", "de_DE"), "1000,25");
+        assert_eq!(crate::normalize_text(&cardinal("3.5", "de_DE"), "de_DE"), "drei Komma fünf");
+        // Anything but a single number is left as written
+        assert_eq!(cardinal("3 bis 5", "de_DE"), "3 bis 5");
+        assert_eq!(cardinal("1.2.3", "en_US"), "1.2.3");
+
+        let doc = parse("<speak>Pi ist etwa <say-as interpret-as=\"cardinal\">3.14</say-as></speak>", "de_DE").unwrap();
+        assert_eq!(doc.segments, vec![text("Pi ist etwa 3,14 ")]);
+    }
+
+    #[test]
+    fn test_errors_report_position() {
+        let err = parse("<speak>\n  Hello <break time=\"soon\"/></speak>", "en_US").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+        assert!(err.message.contains("break time"));
+
+        let err = parse("<speak><s>Hi</p></speak>", "en_US").unwrap_err();
+        assert_eq!(err.offset, 12);
+
+        assert!(parse("Hello", "en_US").is_err());
+        assert!(parse("<speak>Hello", "en_US").is_err());
+        assert!(parse("<speak><audio src=\"x\"/></speak>", "en_US").is_err());
+        let err = parse("<speak><emphasis>Hi</emphasis></speak>", "en_US").unwrap_err();
+        assert!(err.message.contains("unsupported element <emphasis>"));
+        assert!(parse("<speak>Fish &chips;</speak>", "en_US").is_err());
+    }
+}
@@ -0,0 +1,741 @@
+//! Incremental synthesis API.
+//!
+//! `SpeechChunks` is a blocking iterator that synthesizes one text chunk (as produced by
+//! `TtsManager::split_text_with_pauses`) per step and yields the inserted pauses as their own
+//! chunks. Speech chunks are trimmed and faded (see `join`) so that they can be played back to
+//! back with the pauses. With `parallel`, worker threads synthesize the upcoming text chunks
+//! ahead of the consumer and the results are handed out in order, so the output is the same
+//! as in sequential mode. `SynthesisStream` drives that iterator on a blocking thread and
+//! exposes the results as a `tokio_stream::Stream`; dropping the stream stops synthesis after
+//! the current chunk.
+
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    task::{Context, Poll},
+};
+
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+
+use crate::{alignment::WordMap, join::ChunkJoiner, lexicon::Lexicon, pitch::pitch_shift, resample::resample, silence, EmotionDetector, PauseConfig, Prosody, SilenceOptions, TtsEngine, VoiceHandle, WordTiming};
+
+/// What a streamed chunk contains
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChunkKind {
+    /// Synthesized speech for one sentence or clause
+    Speech { text: String },
+    /// Silence inserted after the preceding speech chunk
+    Pause { duration_ms: u32 },
+}
+
+/// One piece of audio produced by the streaming API
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    /// Position of this chunk in the output, starting at 0
+    pub sequence: u64,
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub kind: ChunkKind,
+    /// Word timings relative to the start of this chunk (only with alignment enabled)
+    pub words: Vec<WordTiming>,
+}
+
+impl AudioChunk {
+    /// Duration of this chunk in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        (self.samples.len() as f32 / self.sample_rate as f32 * 1000.0) as u64
+    }
+}
+
+/// Blocking iterator over synthesized chunks and pauses.
+/// Each call to `next` synthesizes at most one text chunk.
+pub struct SpeechChunks {
+    synth: ChunkSynth,
+    // Maps chunk words back to the input text for word timings
+    alignment: Option<WordMap>,
+    // Char offset of each chunk in the text the chunks were split from
+    offsets: Vec<usize>,
+    // Pause lengths and chunk joining of the voice
+    pauses: PauseConfig,
+    joiner: ChunkJoiner,
+    // Silence trimming and compression of each chunk
+    silence: SilenceOptions,
+    chunks: Vec<String>,
+    // Text chunks synthesized at once (1: one after another)
+    parallelism: usize,
+    // Parallel mode: started with the first chunk
+    lookahead: Option<Lookahead>,
+    // Next chunk to hand to the look-ahead, and chunks handed to it but not yet taken
+    next_job: usize,
+    in_flight: usize,
+    // Chunks synthesized ahead, by chunk index
+    ready: HashMap<usize, Option<anyhow::Result<Vec<f32>>>>,
+    index: usize,
+    sequence: u64,
+    pending_pause: Option<u32>,
+}
+
+/// Synthesis of one text chunk; shared with the look-ahead threads in parallel mode
+#[derive(Clone)]
+struct ChunkSynth {
+    voice: VoiceHandle,
+    speaker: Option<i64>,
+    prosody: Prosody,
+    // Expressive mode: per-sentence rate/pitch hints
+    detector: Option<Arc<dyn EmotionDetector>>,
+    // Pronunciation overrides applied to each chunk
+    lexicon: Lexicon,
+    // Output rate (the voice's native rate unless `resample_to` was called)
+    sample_rate: u32,
+}
+
+impl ChunkSynth {
+    fn synthesize(&self, chunk: &str) -> anyhow::Result<Vec<f32>> {
+        self.synthesize_with(&*self.voice.pool.acquire()?, chunk)
+    }
+
+    /// `synthesize` on an instance already taken from the voice's pool
+    fn synthesize_with(&self, engine: &dyn TtsEngine, chunk: &str) -> anyhow::Result<Vec<f32>> {
+        let samples = self.synthesize_native(engine, chunk)?;
+        Ok(resample(&samples, self.voice.sample_rate, self.sample_rate))
+    }
+
+    /// Synthesize at the voice's native rate
+    fn synthesize_native(&self, engine: &dyn TtsEngine, chunk: &str) -> anyhow::Result<Vec<f32>> {
+        let pieces = self.lexicon.apply(chunk);
+        let Some(detector) = &self.detector else {
+            return VoiceHandle::synthesize_pieces_with(engine, &pieces, self.speaker, self.prosody);
+        };
+
+        let hint = detector.detect(chunk);
+        if hint.is_neutral() {
+            return VoiceHandle::synthesize_pieces_with(engine, &pieces, self.speaker, self.prosody);
+        }
+
+        let prosody = Prosody {
+            length_scale: Some(self.prosody.length_scale.unwrap_or(self.voice.length_scale) / hint.rate),
+            ..self.prosody
+        };
+        let samples = VoiceHandle::synthesize_pieces_with(engine, &pieces, self.speaker, prosody)?;
+        Ok(pitch_shift(&samples, self.voice.sample_rate, hint.pitch))
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads that run the look-ahead of every stream in parallel mode, one per CPU, so the
+/// thread count does not grow with the number of requests
+fn lookahead_threads() -> &'static std::sync::mpsc::Sender<Job> {
+    static JOBS: OnceLock<std::sync::mpsc::Sender<Job>> = OnceLock::new();
+    JOBS.get_or_init(|| {
+        let (jobs, queue) = std::sync::mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        let count = std::thread::available_parallelism().map_or(4, |n| n.get());
+        for _ in 0..count {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || loop {
+                let job = queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            });
+        }
+        jobs
+    })
+}
+
+// Samples of a chunk synthesized ahead, or `None` if the voice had no free slot for it
+type ChunkResult = (usize, Option<anyhow::Result<Vec<f32>>>);
+
+/// Upcoming chunks of one stream handed to the look-ahead threads. A chunk is synthesized by
+/// whoever claims it first: a look-ahead thread, or the consumer once it needs the chunk.
+/// Dropping this cancels the chunks no thread has started yet.
+struct Lookahead {
+    synth: Arc<ChunkSynth>,
+    claims: HashMap<usize, Arc<AtomicBool>>,
+    sender: std::sync::mpsc::Sender<ChunkResult>,
+    results: std::sync::mpsc::Receiver<ChunkResult>,
+}
+
+impl Lookahead {
+    fn new(synth: &ChunkSynth) -> Self {
+        let (sender, results) = std::sync::mpsc::channel();
+        Self { synth: Arc::new(synth.clone()), claims: HashMap::new(), sender, results }
+    }
+
+    fn submit(&mut self, index: usize, chunk: String) -> anyhow::Result<()> {
+        let claimed = Arc::new(AtomicBool::new(false));
+        self.claims.insert(index, Arc::clone(&claimed));
+        let (synth, sender) = (Arc::clone(&self.synth), self.sender.clone());
+        let job: Job = Box::new(move || {
+            if claimed.swap(true, Ordering::AcqRel) {
+                return;
+            }
+            // Only a free slot: waiting in the voice's queue would hold back other requests
+            let samples = synth.voice.pool.try_acquire().map(|engine| {
+                std::panic::catch_unwind(AssertUnwindSafe(|| synth.synthesize_with(&*engine, &chunk)))
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Chunk synthesis panicked")))
+            });
+            let _ = sender.send((index, samples));
+        });
+        lookahead_threads()
+            .send(job)
+            .map_err(|_| anyhow::anyhow!("Look-ahead threads stopped"))
+    }
+
+    /// Claim a chunk for the consumer; false if a look-ahead thread has started it
+    fn claim(&mut self, index: usize) -> bool {
+        self.claims.remove(&index).is_some_and(|claimed| !claimed.swap(true, Ordering::AcqRel))
+    }
+}
+
+impl Drop for Lookahead {
+    fn drop(&mut self) {
+        for claimed in self.claims.values() {
+            claimed.store(true, Ordering::Release);
+        }
+    }
+}
+
+impl SpeechChunks {
+    pub(crate) fn new(
+        voice: VoiceHandle,
+        speaker: Option<i64>,
+        prosody: Prosody,
+        chunks: Vec<String>,
+    ) -> Self {
+        Self {
+            pauses: PauseConfig::default(),
+            joiner: ChunkJoiner::new(PauseConfig::default(), voice.sample_rate),
+            silence: SilenceOptions::default(),
+            synth: ChunkSynth {
+                sample_rate: voice.sample_rate,
+                voice,
+                speaker,
+                prosody,
+                detector: None,
+                lexicon: Lexicon::default(),
+            },
+            alignment: None,
+            offsets: chunks
+                .iter()
+                .scan(0, |offset, chunk| {
+                    let start = *offset;
+                    *offset += chunk.chars().count();
+                    Some(start)
+                })
+                .collect(),
+            chunks,
+            parallelism: 1,
+            lookahead: None,
+            next_job: 0,
+            in_flight: 0,
+            ready: HashMap::new(),
+            index: 0,
+            sequence: 0,
+            pending_pause: None,
+        }
+    }
+
+    /// Apply the detector's prosody hint to each sentence: rate through the length
+    /// scale, pitch through a pitch shift of the synthesized samples
+    pub fn expressive(mut self, detector: Arc<dyn EmotionDetector>) -> Self {
+        self.synth.detector = Some(detector);
+        self
+    }
+
+    /// Apply lexicon respellings and phonemes to each chunk
+    pub(crate) fn with_lexicon(mut self, lexicon: Lexicon) -> Self {
+        self.synth.lexicon = lexicon;
+        self
+    }
+
+    /// Attach word timings to each speech chunk
+    pub(crate) fn with_alignment(mut self, alignment: WordMap) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Pause lengths, trimming and fades between chunks
+    pub fn with_pauses(mut self, pauses: PauseConfig) -> Self {
+        self.pauses = pauses;
+        self.joiner = ChunkJoiner::new(pauses, self.synth.sample_rate);
+        self
+    }
+
+    /// Trim the silence around each speech chunk and shorten silences within it and the
+    /// pauses between chunks to `max_silence_ms`
+    pub fn with_silence(mut self, silence: SilenceOptions) -> Self {
+        self.silence = silence;
+        self
+    }
+
+    /// Resample every chunk from the voice's native rate to `sample_rate`
+    pub fn resample_to(mut self, sample_rate: u32) -> Self {
+        self.synth.sample_rate = sample_rate;
+        self.joiner = ChunkJoiner::new(self.pauses, sample_rate);
+        self
+    }
+
+    /// Synthesize up to `workers` upcoming text chunks at once. Chunks are still yielded in
+    /// order, each as soon as it and the chunks before it are done. The chunks ahead run on
+    /// threads shared by all streams and only on free slots of the voice's pool, so they never
+    /// queue behind other requests; a chunk that got no slot is synthesized once it is reached.
+    pub fn parallel(mut self, workers: usize) -> Self {
+        self.parallelism = workers.max(1);
+        self
+    }
+
+    /// Sample rate of every chunk produced by this iterator
+    pub fn sample_rate(&self) -> u32 {
+        self.synth.sample_rate
+    }
+
+    fn next_sequence(&mut self) -> u64 {
+        let seq = self.sequence;
+        self.sequence += 1;
+        seq
+    }
+
+    /// Samples of the text chunk at `index`. In parallel mode the chunks that follow it are
+    /// handed to the look-ahead, at most `parallelism` chunks ahead of the consumer.
+    fn take_or_synthesize(&mut self, index: usize, chunk: &str) -> anyhow::Result<Vec<f32>> {
+        if self.parallelism <= 1 {
+            return self.synth.synthesize(chunk);
+        }
+
+        let lookahead = self.lookahead.get_or_insert_with(|| Lookahead::new(&self.synth));
+        while self.in_flight < self.parallelism && self.next_job < self.chunks.len() {
+            let job = self.next_job;
+            self.next_job += 1;
+            let text = self.chunks[job].trim();
+            if text.is_empty() {
+                continue;
+            }
+            lookahead.submit(job, text.to_string())?;
+            self.in_flight += 1;
+        }
+
+        self.in_flight -= 1;
+        if lookahead.claim(index) {
+            return self.synth.synthesize(chunk);
+        }
+        loop {
+            if let Some(samples) = self.ready.remove(&index) {
+                return samples.unwrap_or_else(|| self.synth.synthesize(chunk));
+            }
+            let (done, samples) = lookahead
+                .results
+                .recv()
+                .map_err(|_| anyhow::anyhow!("Look-ahead threads stopped"))?;
+            self.ready.insert(done, samples);
+        }
+    }
+}
+
+impl Iterator for SpeechChunks {
+    type Item = anyhow::Result<AudioChunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Emit the pause that follows the previous speech chunk first
+        if let Some(duration_ms) = self.pending_pause.take() {
+            let pause_samples = (duration_ms as f32 / 1000.0 * self.synth.sample_rate as f32) as usize;
+            return Some(Ok(AudioChunk {
+                sequence: self.next_sequence(),
+                samples: vec![0.0; pause_samples],
+                sample_rate: self.synth.sample_rate,
+                kind: ChunkKind::Pause { duration_ms },
+                words: Vec::new(),
+            }));
+        }
+
+        while self.index < self.chunks.len() {
+            let i = self.index;
+            self.index += 1;
+
+            let chunk = self.chunks[i].trim().to_string();
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let mut samples = match self.take_or_synthesize(i, &chunk) {
+                Ok(samples) => self.joiner.trim(samples),
+                Err(e) => {
+                    // Stop after an error; the remaining chunks are not synthesized
+                    self.index = self.chunks.len();
+                    self.lookahead = None;
+                    self.ready.clear();
+                    return Some(Err(e));
+                }
+            };
+            silence::remove_silence(&mut samples, self.synth.sample_rate, &self.silence);
+
+            let mut words = match &self.alignment {
+                Some(alignment) => {
+                    let leading = self.chunks[i].chars().take_while(|c| c.is_whitespace()).count();
+                    alignment.time_chunk(&chunk, self.offsets[i] + leading, &samples, self.synth.sample_rate)
+                }
+                None => Vec::new(),
+            };
+
+            // Queue a pause after this chunk unless no speech follows (only blank chunks);
+            // chunks without a pause are crossfaded into the next one
+            let more_speech = self.chunks[i + 1..].iter().any(|chunk| !chunk.trim().is_empty());
+            let pause_ms = more_speech.then(|| self.pauses.pause_ms(&self.chunks[i]));
+            let samples = self.joiner.join(samples, pause_ms);
+            // Word times start where the joined chunk starts, but a crossfaded end is held back
+            // and played over the start of the next chunk: keep them within this chunk
+            let duration_ms = samples.len() as u64 * 1000 / self.synth.sample_rate as u64;
+            for word in &mut words {
+                word.start_ms = word.start_ms.min(duration_ms);
+                word.end_ms = word.end_ms.min(duration_ms);
+            }
+            self.pending_pause = pause_ms
+                .map(|ms| self.silence.cap_pause_ms(ms))
+                .filter(|&ms| ms > 0);
+
+            return Some(Ok(AudioChunk {
+                sequence: self.next_sequence(),
+                samples,
+                sample_rate: self.synth.sample_rate,
+                kind: ChunkKind::Speech { text: chunk },
+                words,
+            }));
+        }
+
+        None
+    }
+}
+
+/// Async stream of synthesized chunks.
+/// Synthesis runs on a blocking thread; dropping the stream cancels the remaining work.
+pub struct SynthesisStream {
+    inner: ReceiverStream<anyhow::Result<AudioChunk>>,
+}
+
+impl SynthesisStream {
+    /// Number of chunks buffered ahead of the consumer
+    const BUFFER: usize = 4;
+
+    /// Spawn a blocking producer that builds the chunk iterator and forwards its items.
+    /// Must be called from within a Tokio runtime.
+    pub(crate) fn spawn<F>(make_chunks: F) -> Self
+    where
+        F: FnOnce() -> anyhow::Result<SpeechChunks> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(Self::BUFFER);
+
+        tokio::task::spawn_blocking(move || {
+            let chunks = match make_chunks() {
+                Ok(chunks) => chunks,
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(e));
+                    return;
+                }
+            };
+
+            for item in chunks {
+                if tx.blocking_send(item).is_err() {
+                    break; // Stream dropped - stop synthesizing
+                }
+            }
+        });
+
+        Self {
+            inner: ReceiverStream::new(rx),
+        }
+    }
+}
+
+impl Stream for SynthesisStream {
+    type Item = anyhow::Result<AudioChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_stream::StreamExt;
+
+    use crate::{synth_pool::SynthPool, EngineKind, MockEngine, PoolConfig, SynthesisOptions, TtsEngine, TtsManager};
+
+    use super::*;
+
+    const RATE: u32 = 16000;
+    const PADDING_MS: u32 = 200;
+
+    /// Mock tones with silence before and after every sentence, as Piper pads them
+    struct PaddedEngine(MockEngine);
+
+    impl PaddedEngine {
+        fn pad(samples: Vec<f32>) -> Vec<f32> {
+            let padding = vec![0.0; (RATE * PADDING_MS / 1000) as usize];
+            [padding.clone(), samples, padding].concat()
+        }
+    }
+
+    impl TtsEngine for PaddedEngine {
+        fn sample_rate(&self) -> u32 {
+            self.0.sample_rate()
+        }
+
+        fn num_speakers(&self) -> u32 {
+            self.0.num_speakers()
+        }
+
+        fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            self.0.synthesize(text, speaker, prosody).map(Self::pad)
+        }
+
+        fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>> {
+            self.0.phonemize(text)
+        }
+
+        fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            self.0.speak_phonemes(phonemes, speaker, prosody).map(Self::pad)
+        }
+    }
+
+    fn padded_chunks(chunks: &[&str]) -> SpeechChunks {
+        let engine: Arc<dyn TtsEngine> = Arc::new(PaddedEngine(MockEngine::new(RATE, 1)));
+        let pool = SynthPool::new("padded".to_string(), vec![engine], PoolConfig::default());
+        let voice = VoiceHandle { pool: Arc::new(pool), num_speakers: 1, sample_rate: RATE, length_scale: 1.0 };
+        SpeechChunks::new(voice, None, Prosody::default(), chunks.iter().map(|chunk| chunk.to_string()).collect())
+    }
+
+    /// Mock tones, counting the chunks synthesized
+    struct CountingEngine {
+        inner: MockEngine,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl TtsEngine for CountingEngine {
+        fn sample_rate(&self) -> u32 {
+            self.inner.sample_rate()
+        }
+
+        fn num_speakers(&self) -> u32 {
+            self.inner.num_speakers()
+        }
+
+        fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.synthesize(text, speaker, prosody)
+        }
+
+        fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>> {
+            self.inner.phonemize(text)
+        }
+
+        fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            self.inner.speak_phonemes(phonemes, speaker, prosody)
+        }
+    }
+
+    /// `count` sentences on a voice that counts its synthesis calls
+    fn counted_chunks(count: usize) -> (SpeechChunks, Arc<std::sync::atomic::AtomicUsize>) {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let engine: Arc<dyn TtsEngine> = Arc::new(CountingEngine { inner: MockEngine::new(RATE, 1), calls: Arc::clone(&calls) });
+        let pool = SynthPool::new("counted".to_string(), vec![engine], PoolConfig::default());
+        let voice = VoiceHandle { pool: Arc::new(pool), num_speakers: 1, sample_rate: RATE, length_scale: 1.0 };
+        let chunks = (0..count).map(|i| format!("Satz Nummer {i}. ")).collect();
+        (SpeechChunks::new(voice, None, Prosody::default(), chunks), calls)
+    }
+
+    fn leading_silence_ms(samples: &[f32]) -> u32 {
+        let silent = samples.iter().take_while(|s| s.abs() < 0.001).count();
+        (silent as u64 * 1000 / RATE as u64) as u32
+    }
+
+    #[test]
+    fn test_chunks_in_order_ending_with_speech() {
+        let texts = ["Erster Satz. ", "Zweiter Teil, ", "  ", "Dritter Satz."];
+        for parallelism in [1, 3] {
+            let mut chunks = padded_chunks(&texts).parallel(parallelism);
+            let items: Vec<AudioChunk> = chunks.by_ref().map(|chunk| chunk.unwrap()).collect();
+            // Blank chunks are skipped; every speech chunk but the last is followed by its pause
+            let kinds: Vec<ChunkKind> = items.iter().map(|chunk| chunk.kind.clone()).collect();
+            let speech = |text: &str| ChunkKind::Speech { text: text.to_string() };
+            assert_eq!(
+                kinds,
+                vec![
+                    speech("Erster Satz."),
+                    ChunkKind::Pause { duration_ms: 400 },
+                    speech("Zweiter Teil,"),
+                    ChunkKind::Pause { duration_ms: 150 },
+                    speech("Dritter Satz."),
+                ]
+            );
+            assert!(items.iter().map(|chunk| chunk.sequence).eq(0..5));
+            assert!(items.iter().all(|chunk| chunk.sample_rate == RATE && !chunk.samples.is_empty()));
+            // The iterator stays finished
+            assert!(chunks.next().is_none());
+            assert!(chunks.next().is_none());
+        }
+
+        // Trailing blank chunks add no pause after the last speech
+        let kinds: Vec<ChunkKind> = padded_chunks(&["Nur ein Satz. ", " ", "\n"]).map(|chunk| chunk.unwrap().kind).collect();
+        assert_eq!(kinds, vec![ChunkKind::Speech { text: "Nur ein Satz.".to_string() }]);
+
+        // The stream yields the same chunks, then ends
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let streamed = runtime.block_on(async {
+            let mut stream = SynthesisStream::spawn(move || Ok(padded_chunks(&texts)));
+            let mut kinds = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                kinds.push(chunk.unwrap().kind);
+            }
+            assert!(stream.next().await.is_none());
+            kinds
+        });
+        assert_eq!(streamed.len(), 5);
+        assert!(matches!(streamed.last(), Some(ChunkKind::Speech { .. })));
+    }
+
+    #[test]
+    fn test_dropping_stops_synthesis() {
+        // Dropping the stream after the first chunk stops the producer once the buffer is full
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let (chunks, calls) = counted_chunks(30);
+        runtime.block_on(async {
+            let mut stream = SynthesisStream::spawn(move || Ok(chunks));
+            assert_eq!(stream.next().await.unwrap().unwrap().sequence, 0);
+        });
+        // Waits for the blocking producer to finish
+        drop(runtime);
+        let synthesized = calls.load(Ordering::SeqCst);
+        assert!(synthesized < 10, "{synthesized} chunks synthesized");
+
+        // The look-ahead only gets the chunks within the window ahead of the consumer
+        let (mut chunks, calls) = counted_chunks(30);
+        chunks = chunks.parallel(4);
+        assert!(matches!(chunks.next().unwrap().unwrap().kind, ChunkKind::Speech { .. }));
+        drop(chunks);
+        assert!(calls.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn test_stream_removes_silence() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        // Keep the engine padding through the joiner so that only silence removal cuts it
+        let untrimmed = PauseConfig { trim: Some(false), sentence_ms: Some(600), ..PauseConfig::default() };
+        let collect = |silence: SilenceOptions| {
+            runtime.block_on(async {
+                SynthesisStream::spawn(move || {
+                    Ok(padded_chunks(&["Erster Satz. ", "Zweiter Satz."]).with_pauses(untrimmed).with_silence(silence))
+                })
+                .map(|chunk| chunk.unwrap())
+                .collect::<Vec<_>>()
+                .await
+            })
+        };
+
+        let padded = collect(SilenceOptions::default());
+        assert!(leading_silence_ms(&padded[0].samples) >= PADDING_MS);
+        assert_eq!(padded[1].kind, ChunkKind::Pause { duration_ms: 600 });
+
+        let options = SilenceOptions { trim_silence: true, max_silence_ms: Some(300) };
+        let trimmed = collect(options);
+        assert_eq!(trimmed.len(), padded.len());
+        for (trimmed, padded) in trimmed.iter().zip(&padded) {
+            if let ChunkKind::Speech { .. } = trimmed.kind {
+                assert!(leading_silence_ms(&trimmed.samples) <= 20);
+                // Both edges lose their padding except for a 20 ms margin
+                let cut_ms = (padded.samples.len() - trimmed.samples.len()) as u32 * 1000 / RATE;
+                assert!(cut_ms >= 2 * (PADDING_MS - 20));
+            }
+        }
+        // Pauses are shortened to the maximum silence
+        assert_eq!(trimmed[1].kind, ChunkKind::Pause { duration_ms: 300 });
+    }
+
+    /// Slows every sentence down to half the rate
+    struct Slow;
+
+    impl EmotionDetector for Slow {
+        fn detect(&self, _text: &str) -> crate::ProsodyHint {
+            crate::ProsodyHint { rate: 0.5, pitch: 1.0 }
+        }
+    }
+
+    #[test]
+    fn test_expressive_rate_scales_model_length() {
+        let speech = |chunks: SpeechChunks| chunks.map(|chunk| chunk.unwrap().samples.len()).sum::<usize>();
+        let chunks = |length_scale: f32, prosody: Prosody| {
+            let mut chunks = padded_chunks(&["Ein ganzer Satz."]);
+            chunks.synth.voice.length_scale = length_scale;
+            chunks.synth.prosody = prosody;
+            chunks
+        };
+
+        // Half the rate of a model that speaks at 2.0 by default
+        let expressive = speech(chunks(2.0, Prosody::default()).expressive(Arc::new(Slow)));
+        assert_eq!(expressive, speech(chunks(2.0, Prosody { length_scale: Some(4.0), ..Prosody::default() })));
+        // A request's own length scale replaces the model default
+        let requested = Prosody { length_scale: Some(1.0), ..Prosody::default() };
+        assert_eq!(speech(chunks(2.0, requested).expressive(Arc::new(Slow))), speech(chunks(1.0, Prosody::from_rate(0.5))));
+    }
+
+    #[test]
+    fn test_word_times_follow_joined_chunks() {
+        let text = "Eins zwei drei vier, Fünf sechs sieben acht, Neun zehn elf zwölf";
+        // The engine padding is trimmed to 10 ms and the chunks are crossfaded (no pauses)
+        let pauses = PauseConfig { comma_ms: Some(0), crossfade_ms: Some(40), ..PauseConfig::default() };
+        let chunks = padded_chunks(&["Eins zwei drei vier, ", "Fünf sechs sieben acht, ", "Neun zehn elf zwölf"])
+            .with_alignment(WordMap::new(text, text))
+            .with_pauses(pauses);
+
+        let mut output: Vec<f32> = Vec::new();
+        let mut alignment = crate::Alignment::default();
+        for chunk in chunks {
+            let chunk = chunk.unwrap();
+            assert!(matches!(chunk.kind, ChunkKind::Speech { .. }));
+            let offset_ms = output.len() as u64 * 1000 / RATE as u64;
+            alignment.push_chunk(text, chunk.words.clone(), offset_ms, chunk.duration_ms());
+            output.extend(chunk.samples);
+        }
+
+        assert_eq!(alignment.words.len(), 12);
+        assert_eq!(alignment.chunks.len(), 3);
+        for (span, first) in alignment.chunks.iter().zip([0, 4, 8]) {
+            // Each chunk's first word starts after the 10 ms kept of its padding
+            let word = &alignment.words[first];
+            assert!(word.start_ms.abs_diff(span.start_ms + 10) <= 1, "{word:?} in {span:?}");
+            // and its words end within its own audio
+            assert!(alignment.words[first..first + 4].iter().all(|word| word.end_ms <= span.end_ms));
+        }
+        assert_eq!(alignment.words[0].word, "Eins");
+        assert_eq!(alignment.words[4].word, "Fünf");
+        assert!(alignment.words.windows(2).all(|pair| pair[0].start_ms < pair[1].start_ms));
+        assert!(alignment.chunks.last().unwrap().end_ms <= output.len() as u64 * 1000 / RATE as u64);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let map = [("de_DE".to_string(), ("/nonexistent/voice.onnx.json".to_string(), None))].into();
+        let tts = TtsManager::new(map).with_engine(EngineKind::Mock);
+        let text = "Erster Satz. Zweiter Satz, mit Komma; und mehr! Wirklich? Ja: fünf Teile.";
+        let collect = |parallelism: usize| {
+            let options = SynthesisOptions { alignment: true, parallelism, ..SynthesisOptions::default() };
+            tts.synthesize_chunks(text, Some("de_DE"), None, &options)
+                .unwrap()
+                .map(|chunk| chunk.map(|chunk| (chunk.sequence, chunk.kind, chunk.samples, chunk.words)))
+                .collect::<anyhow::Result<Vec<_>>>()
+                .unwrap()
+        };
+
+        let sequential = collect(1);
+        assert!(sequential.len() > 5);
+        assert_eq!(collect(3), sequential);
+        assert_eq!(collect(16), sequential);
+    }
+}
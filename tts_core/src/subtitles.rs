@@ -0,0 +1,270 @@
+//! SRT and WebVTT subtitles.
+//!
+//! Cues follow the synthesized chunks: consecutive chunks of one sentence are merged while they
+//! fit the cue limits, and chunks that are too long on their own are split at word boundaries
+//! using the word timings. Cue times come from the chunk audio, so pauses fall between cues.
+
+use std::fmt::Write;
+
+use crate::{Alignment, WordTiming};
+
+/// Subtitle file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    /// Parse a format name: `srt`, `vtt` or `webvtt` (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" | "webvtt" => Some(Self::WebVtt),
+            _ => None,
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Srt => "application/x-subrip",
+            Self::WebVtt => "text/vtt",
+        }
+    }
+
+    /// Render cues as a subtitle file
+    pub fn render(&self, cues: &[Cue]) -> String {
+        let mut out = String::new();
+        if *self == Self::WebVtt {
+            out.push_str("WEBVTT\n\n");
+        }
+        for (i, cue) in cues.iter().enumerate() {
+            let separator = if *self == Self::Srt { ',' } else { '.' };
+            let _ = writeln!(out, "{}", i + 1);
+            let _ = writeln!(
+                out,
+                "{} --> {}",
+                timestamp(cue.start_ms, separator),
+                timestamp(cue.end_ms, separator)
+            );
+            let _ = writeln!(out, "{}\n", cue.lines.join("\n"));
+        }
+        out
+    }
+}
+
+/// `hh:mm:ss,mmm` (SRT) or `hh:mm:ss.mmm` (WebVTT)
+fn timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Cue layout limits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubtitleOptions {
+    /// Maximum characters per line
+    pub max_line_length: usize,
+    /// Maximum lines per cue
+    pub max_lines: usize,
+    /// Maximum time a cue stays on screen
+    pub max_cue_duration_ms: u64,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_length: 42,
+            max_lines: 2,
+            max_cue_duration_ms: 7000,
+        }
+    }
+}
+
+/// One subtitle cue
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub lines: Vec<String>,
+}
+
+// A piece of text with its time span, before line wrapping
+struct Span {
+    start: usize,
+    end: usize,
+    start_ms: u64,
+    end_ms: u64,
+}
+
+/// Build cues for `text` from the chunk and word timings of its synthesis
+pub fn build_cues(text: &str, alignment: &Alignment, options: &SubtitleOptions) -> Vec<Cue> {
+    let chars: Vec<char> = text.chars().collect();
+    let lines = |span: &Span| wrap(&chars[span.start..span.end].iter().collect::<String>(), options.max_line_length);
+    let fits = |span: &Span| {
+        lines(span).len() <= options.max_lines.max(1) && span.end_ms - span.start_ms <= options.max_cue_duration_ms
+    };
+
+    // Split chunks that do not fit into one cue at word boundaries
+    let mut spans: Vec<Span> = Vec::new();
+    for chunk in &alignment.chunks {
+        let span = Span { start: chunk.start, end: chunk.end.min(chars.len()), start_ms: chunk.start_ms, end_ms: chunk.end_ms };
+        if fits(&span) {
+            spans.push(span);
+            continue;
+        }
+        let words: Vec<&WordTiming> = alignment
+            .words
+            .iter()
+            .filter(|w| w.start >= span.start && w.end <= span.end)
+            .collect();
+        let mut current: Option<Span> = None;
+        for (i, word) in words.iter().enumerate() {
+            // The last word keeps the chunk's closing punctuation and end time
+            let (end, end_ms) = if i + 1 == words.len() { (span.end, span.end_ms) } else { (word.end, word.end_ms) };
+            match &mut current {
+                Some(open) if fits(&Span { start: open.start, end, start_ms: open.start_ms, end_ms }) => {
+                    open.end = end;
+                    open.end_ms = end_ms;
+                }
+                _ => {
+                    let start_ms = if i == 0 { span.start_ms } else { word.start_ms };
+                    spans.extend(current.replace(Span { start: word.start, end, start_ms, end_ms }));
+                }
+            }
+        }
+        spans.extend(current);
+    }
+
+    // Merge clauses of the same sentence while the cue limits allow
+    let mut merged: Vec<Span> = Vec::new();
+    for span in spans {
+        if let Some(previous) = merged.last_mut() {
+            let ends_sentence = chars[previous.start..previous.end]
+                .iter()
+                .rev()
+                .find(|c| !matches!(c, '"' | '\'' | ')' | ']' | '»' | '«' | '”' | '’'))
+                .is_some_and(|c| matches!(c, '.' | '!' | '?' | '…'));
+            let candidate = Span { start: previous.start, end: span.end, start_ms: previous.start_ms, end_ms: span.end_ms };
+            if !ends_sentence && fits(&candidate) {
+                *previous = candidate;
+                continue;
+            }
+        }
+        merged.push(span);
+    }
+
+    merged
+        .iter()
+        .map(|span| Cue {
+            start_ms: span.start_ms,
+            end_ms: span.end_ms,
+            lines: lines(span),
+        })
+        .collect()
+}
+
+/// Greedy word wrap; words longer than a line get a line of their own
+fn wrap(text: &str, max_line_length: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_line_length => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkSpan;
+
+    /// Chunks as (start, end, start_ms, end_ms); words are spread evenly over their chunk
+    fn alignment(text: &str, chunks: &[(usize, usize, u64, u64)]) -> Alignment {
+        let chars: Vec<char> = text.chars().collect();
+        let mut words = Vec::new();
+        for &(start, end, start_ms, end_ms) in chunks {
+            let mut ranges = Vec::new();
+            let mut i = start;
+            while i < end {
+                if chars[i].is_alphanumeric() {
+                    let word_start = i;
+                    while i < end && chars[i].is_alphanumeric() {
+                        i += 1;
+                    }
+                    ranges.push(word_start..i);
+                }
+                i += 1;
+            }
+            let step = (end_ms - start_ms) / ranges.len() as u64;
+            for (n, range) in ranges.into_iter().enumerate() {
+                words.push(WordTiming {
+                    word: chars[range.clone()].iter().collect(),
+                    start: range.start,
+                    end: range.end,
+                    start_ms: start_ms + step * n as u64,
+                    end_ms: start_ms + step * (n as u64 + 1),
+                });
+            }
+        }
+        let chunks = chunks.iter().map(|&(start, end, start_ms, end_ms)| ChunkSpan { start, end, start_ms, end_ms }).collect();
+        Alignment { words, chunks }
+    }
+
+    #[test]
+    fn test_clauses_merge_until_sentence_end() {
+        let text = "Hello there, my friend. How are you?";
+        let alignment = alignment(text, &[(0, 12, 0, 800), (13, 23, 950, 1600), (24, 36, 2000, 2900)]);
+        let cues = build_cues(text, &alignment, &SubtitleOptions::default());
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].lines, vec!["Hello there, my friend."]);
+        assert_eq!((cues[0].start_ms, cues[0].end_ms), (0, 1600));
+        assert_eq!((cues[1].start_ms, cues[1].end_ms), (2000, 2900));
+    }
+
+    #[test]
+    fn test_long_chunks_split_and_wrap() {
+        let text = "one two three four five six seven eight.";
+        let alignment = alignment(text, &[(0, 40, 0, 8000)]);
+        let options = SubtitleOptions { max_line_length: 10, max_lines: 2, max_cue_duration_ms: 7000 };
+        let cues = build_cues(text, &alignment, &options);
+
+        assert!(cues.iter().all(|c| c.lines.len() <= 2 && c.lines.iter().all(|l| l.chars().count() <= 10)));
+        assert_eq!(cues.iter().flat_map(|c| c.lines.clone()).collect::<Vec<_>>().join(" "), text);
+        assert_eq!(cues.last().unwrap().end_ms, 8000);
+    }
+
+    #[test]
+    fn test_wrapped_lines_limit_cues() {
+        // Three words of six fit 2 x 10 characters, but not two wrapped lines of ten
+        let text = "aaaaaa bbbbbb cccccc. dddddd eeeeee.";
+        let alignment = alignment(text, &[(0, 21, 0, 1500), (22, 36, 1500, 3000)]);
+        let options = SubtitleOptions { max_line_length: 10, max_lines: 2, max_cue_duration_ms: 7000 };
+        let cues = build_cues(text, &alignment, &options);
+
+        assert!(cues.iter().all(|c| c.lines.len() <= 2), "{cues:?}");
+        assert_eq!(cues[0].lines, vec!["aaaaaa", "bbbbbb"]);
+        assert_eq!(cues[1].lines, vec!["cccccc."]);
+        assert_eq!(cues[2].lines, vec!["dddddd", "eeeeee."]);
+        assert_eq!(cues.iter().flat_map(|c| c.lines.clone()).collect::<Vec<_>>().join(" "), text);
+    }
+
+    #[test]
+    fn test_render() {
+        let cues = vec![Cue { start_ms: 1500, end_ms: 3_723_004, lines: vec!["Hi".to_string(), "there".to_string()] }];
+        assert_eq!(SubtitleFormat::Srt.render(&cues), "1\n00:00:01,500 --> 01:02:03,004\nHi\nthere\n\n");
+        assert!(SubtitleFormat::WebVtt.render(&cues).starts_with("WEBVTT\n\n1\n00:00:01.500 --> "));
+        assert_eq!(SubtitleFormat::parse("VTT"), Some(SubtitleFormat::WebVtt));
+    }
+}
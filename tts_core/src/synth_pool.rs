@@ -0,0 +1,324 @@
+//! Per-voice pool of engine instances.
+//!
+//! A voice can be loaded several times (one ONNX session each) so that requests for a popular
+//! voice run in parallel instead of taking turns on one session. Each pool admits a limited
+//! number of concurrent syntheses, spread over the least busy instances; further requests wait
+//! in a bounded queue and are turned away with `VoiceBusy` when the queue is full or they have
+//! waited too long.
+
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::TtsEngine;
+
+/// Size and limits of each voice's pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolConfig {
+    /// Engine instances (ONNX sessions) per voice; each one takes the model's memory
+    pub instances: usize,
+    /// Syntheses running at once per voice
+    pub max_concurrent: usize,
+    /// Requests that may wait for a free slot; more are rejected right away
+    pub max_queue: usize,
+    /// Longest a request waits in the queue
+    pub queue_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            instances: 1,
+            max_concurrent: 2,
+            max_queue: 32,
+            queue_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A voice had no free slot for a request
+#[derive(Debug, Clone)]
+pub struct VoiceBusy {
+    pub config: String,
+    /// True if the request waited for the whole queue timeout, false if the queue was full
+    pub timed_out: bool,
+}
+
+impl fmt::Display for VoiceBusy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.timed_out {
+            write!(f, "Voice {} is busy: timed out waiting for a free synthesizer", self.config)
+        } else {
+            write!(f, "Voice {} is busy: too many requests waiting", self.config)
+        }
+    }
+}
+
+impl std::error::Error for VoiceBusy {}
+
+/// Load and wait statistics of one voice's pool
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStats {
+    pub config: String,
+    pub instances: usize,
+    pub max_concurrent: usize,
+    pub active: usize,
+    pub queued: usize,
+    /// `active / max_concurrent`
+    pub utilization: f64,
+    pub served: u64,
+    pub rejected: u64,
+    pub avg_wait_ms: f64,
+    pub max_wait_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    busy: Vec<usize>, // syntheses running on each instance
+    active: usize,
+    waiting: usize,
+    served: u64,
+    rejected: u64,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+pub(crate) struct SynthPool {
+    config_path: String,
+    engines: Vec<Arc<dyn TtsEngine>>,
+    config: PoolConfig,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+impl SynthPool {
+    /// Pool over already loaded instances of one voice (at least one)
+    pub(crate) fn new(config_path: String, engines: Vec<Arc<dyn TtsEngine>>, config: PoolConfig) -> Self {
+        let state = PoolState { busy: vec![0; engines.len()], ..PoolState::default() };
+        Self { config_path, engines, config, state: Mutex::new(state), available: Condvar::new() }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn max_concurrent(&self) -> usize {
+        self.config.max_concurrent.max(1)
+    }
+
+    /// Take a slot on the least busy instance, waiting in the queue if all slots are taken
+    /// (blocking)
+    pub(crate) fn acquire(&self) -> Result<PooledEngine<'_>, VoiceBusy> {
+        let started = Instant::now();
+        let mut state = self.lock();
+        if state.active >= self.max_concurrent() {
+            if state.waiting >= self.config.max_queue {
+                state.rejected += 1;
+                return Err(self.busy(false));
+            }
+            state.waiting += 1;
+            let deadline = started + self.config.queue_timeout;
+            while state.active >= self.max_concurrent() {
+                let now = Instant::now();
+                if now >= deadline {
+                    state.waiting -= 1;
+                    state.rejected += 1;
+                    return Err(self.busy(true));
+                }
+                state = self
+                    .available
+                    .wait_timeout(state, deadline - now)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .0;
+            }
+            state.waiting -= 1;
+        }
+
+        let index = (0..state.busy.len()).min_by_key(|&i| state.busy[i]).unwrap_or(0);
+        state.busy[index] += 1;
+        state.active += 1;
+        state.served += 1;
+        let waited = started.elapsed();
+        state.total_wait += waited;
+        state.max_wait = state.max_wait.max(waited);
+        Ok(PooledEngine { pool: self, index })
+    }
+
+    /// Take a slot on the least busy instance if one is free and no request is waiting for
+    /// it; never queues
+    pub(crate) fn try_acquire(&self) -> Option<PooledEngine<'_>> {
+        let mut state = self.lock();
+        if state.active >= self.max_concurrent() || state.waiting > 0 {
+            return None;
+        }
+        let index = (0..state.busy.len()).min_by_key(|&i| state.busy[i]).unwrap_or(0);
+        state.busy[index] += 1;
+        state.active += 1;
+        state.served += 1;
+        Some(PooledEngine { pool: self, index })
+    }
+
+    fn busy(&self, timed_out: bool) -> VoiceBusy {
+        VoiceBusy { config: self.config_path.clone(), timed_out }
+    }
+
+    fn release(&self, index: usize) {
+        let mut state = self.lock();
+        state.busy[index] -= 1;
+        state.active -= 1;
+        drop(state);
+        self.available.notify_one();
+    }
+
+    pub(crate) fn stats(&self) -> PoolStats {
+        let state = self.lock();
+        PoolStats {
+            config: self.config_path.clone(),
+            instances: self.engines.len(),
+            max_concurrent: self.max_concurrent(),
+            active: state.active,
+            queued: state.waiting,
+            utilization: state.active as f64 / self.max_concurrent() as f64,
+            served: state.served,
+            rejected: state.rejected,
+            avg_wait_ms: if state.served > 0 {
+                state.total_wait.as_secs_f64() * 1000.0 / state.served as f64
+            } else {
+                0.0
+            },
+            max_wait_ms: state.max_wait.as_millis() as u64,
+        }
+    }
+}
+
+/// An instance taken from a pool; the slot is given back on drop
+pub(crate) struct PooledEngine<'a> {
+    pool: &'a SynthPool,
+    index: usize,
+}
+
+impl Deref for PooledEngine<'_> {
+    type Target = dyn TtsEngine;
+
+    fn deref(&self) -> &Self::Target {
+        self.pool.engines[self.index].as_ref()
+    }
+}
+
+impl Drop for PooledEngine<'_> {
+    fn drop(&mut self) {
+        self.pool.release(self.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexicon::Piece, MockEngine, Prosody};
+
+    fn pool(instances: usize, config: PoolConfig) -> SynthPool {
+        let engines: Vec<Arc<dyn TtsEngine>> =
+            (0..instances).map(|_| Arc::new(MockEngine::new(16000, 1)) as Arc<dyn TtsEngine>).collect();
+        SynthPool::new("voice.onnx.json".to_string(), engines, config)
+    }
+
+    /// Keeps the last speaker set, like a Piper model
+    struct StickySpeaker {
+        inner: MockEngine,
+        current: Mutex<Option<i64>>,
+    }
+
+    impl TtsEngine for StickySpeaker {
+        fn sample_rate(&self) -> u32 {
+            self.inner.sample_rate()
+        }
+
+        fn num_speakers(&self) -> u32 {
+            self.inner.num_speakers()
+        }
+
+        fn synthesize(&self, text: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            let mut current = self.current.lock().unwrap();
+            if speaker.is_some() {
+                *current = speaker;
+            }
+            self.inner.synthesize(text, *current, prosody)
+        }
+
+        fn phonemize(&self, text: &str) -> anyhow::Result<Vec<String>> {
+            self.inner.phonemize(text)
+        }
+
+        fn speak_phonemes(&self, phonemes: &str, speaker: Option<i64>, prosody: Prosody) -> anyhow::Result<Vec<f32>> {
+            self.inner.speak_phonemes(phonemes, speaker, prosody)
+        }
+    }
+
+    #[test]
+    fn test_request_without_speaker_gets_default() {
+        let engine = StickySpeaker { inner: MockEngine::new(16000, 4), current: Mutex::new(None) };
+        let engines: Vec<Arc<dyn TtsEngine>> = vec![Arc::new(engine)];
+        let pool = SynthPool::new("voice.onnx.json".to_string(), engines, PoolConfig::default());
+        let voice = crate::VoiceHandle { pool: Arc::new(pool), num_speakers: 4, sample_rate: 16000, length_scale: 1.0 };
+        let speak = |speaker: Option<i64>| {
+            let speaker = voice.resolve_speaker(speaker).unwrap();
+            voice.synthesize_pieces(&[Piece::Text("Hallo.".to_string())], speaker, Prosody::default()).unwrap()
+        };
+
+        let third = speak(Some(3));
+        let default = speak(None);
+        assert_ne!(default, third);
+        assert_eq!(default, MockEngine::new(16000, 4).synthesize("Hallo.", Some(0), Prosody::default()).unwrap());
+        assert_eq!(voice.resolve_speaker(None).unwrap(), Some(0));
+        assert!(voice.resolve_speaker(Some(4)).is_err());
+    }
+
+    #[test]
+    fn test_spreads_load_and_limits_concurrency() {
+        let pool = pool(2, PoolConfig { instances: 2, max_concurrent: 3, max_queue: 0, ..PoolConfig::default() });
+        let first = pool.acquire().unwrap();
+        let second = pool.acquire().unwrap();
+        assert_ne!(first.index, second.index);
+        let third = pool.acquire().unwrap();
+        assert_eq!(pool.stats().active, 3);
+
+        // No queue: rejected right away
+        let busy = pool.acquire().err().unwrap();
+        assert!(!busy.timed_out);
+        assert!(pool.try_acquire().is_none());
+        drop((first, second, third));
+        let stats = pool.stats();
+        assert_eq!((stats.active, stats.served, stats.rejected), (0, 3, 1));
+    }
+
+    #[test]
+    fn test_queue_waits_and_times_out() {
+        let pool = Arc::new(pool(1, PoolConfig {
+            instances: 1,
+            max_concurrent: 1,
+            max_queue: 1,
+            queue_timeout: Duration::from_millis(200),
+        }));
+        let held = pool.acquire().unwrap();
+        assert!(pool.acquire().err().unwrap().timed_out);
+
+        // A waiter gets the slot once it is released; meanwhile the queue is full
+        let waiter = std::thread::spawn({
+            let pool = Arc::clone(&pool);
+            move || pool.acquire().map(|_| ()).is_ok()
+        });
+        while pool.stats().queued == 0 {
+            std::thread::yield_now();
+        }
+        assert!(!pool.acquire().err().unwrap().timed_out);
+        drop(held);
+        assert!(waiter.join().unwrap());
+        let stats = pool.stats();
+        assert_eq!((stats.served, stats.rejected, stats.queued), (2, 2, 0));
+    }
+}
@@ -0,0 +1,648 @@
+//! Voice configuration from models/map.json and the voice folders' metadata.json.
+//!
+//! The parsed map is replaced as a whole when map.json is reloaded, so a request always sees
+//! one consistent version of it.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{lexicon::Lexicons, EngineKind, Lexicon, LexiconScope, LoudnessTarget, PauseConfig, Prosody, TtsManager, VoiceEntry};
+
+/// Languages and voices of a map.json
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VoiceMap {
+    // For backwards compatibility: language key -> (config path, default speaker)
+    pub(crate) map: HashMap<String, (String, Option<i64>)>,
+    // New format: language -> (default_voice_id, voices_map)
+    pub(crate) voices_map: HashMap<String, (String, HashMap<String, VoiceEntry>)>,
+    // Models directory scanned for voices (discovery mode), rescanned on reload
+    pub(crate) models_dir: Option<PathBuf>,
+    // Config path -> engine, for voices whose entry names one
+    pub(crate) engines: HashMap<String, EngineKind>,
+    // Language -> pause settings of the language entry (voices can override them)
+    pub(crate) pauses: HashMap<String, PauseConfig>,
+}
+
+/// A voice folder's metadata.json. Fields not listed here are kept in `extra`, so clients get
+/// the whole file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoiceMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_range: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<String>,
+    /// Expected SHA-256 of the .onnx model and of its config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl VoiceMetadata {
+    /// metadata.json next to a voice config, if the folder has one
+    pub fn for_config<P: AsRef<Path>>(config_path: P) -> anyhow::Result<Option<Self>> {
+        let Some(dir) = config_path.as_ref().parent() else {
+            return Ok(None);
+        };
+        let path = dir.join("metadata.json");
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(&path).with_context(|| format!("Failed to load {}", path.display()))?;
+        let metadata = serde_json::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+        Ok(Some(metadata))
+    }
+}
+
+/// Outcome of a map.json reload
+#[derive(Debug, Clone, Serialize)]
+pub struct MapReload {
+    /// `language/voice` (or `language` for legacy entries) of voices that were added
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Config paths of models unloaded because no voice uses them anymore
+    pub unloaded_models: Vec<String>,
+    /// Voices kept with the broken config they already had (see `TtsManager::voice_status`)
+    pub broken: Vec<String>,
+    pub invalidated_cache_entries: usize,
+}
+
+/// A voice map that parsed but failed validation (unknown default voices, unreadable
+/// voice configs)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidVoiceMap {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for InvalidVoiceMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid voice map: {}", self.errors.join("; "))
+    }
+}
+
+impl std::error::Error for InvalidVoiceMap {}
+
+impl VoiceMap {
+    /// Load a map file and the lexicon files it references
+    pub(crate) fn load(path: &Path) -> anyhow::Result<(Self, Lexicons)> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// Parse map.json. Supports both new format (with multiple voices) and legacy format
+    pub(crate) fn parse(text: &str) -> anyhow::Result<(Self, Lexicons)> {
+        let json: serde_json::Value = serde_json::from_str(text)
+            .with_context(|| "map.json is not valid JSON")?;
+
+        let mut map: HashMap<String, (String, Option<i64>)> = HashMap::new();
+        let mut voices_map: HashMap<String, (String, HashMap<String, VoiceEntry>)> = HashMap::new();
+        let mut lexicons = Lexicons::default();
+        let mut engines: HashMap<String, EngineKind> = HashMap::new();
+        let mut pauses: HashMap<String, PauseConfig> = HashMap::new();
+
+        if let Some(obj) = json.as_object() {
+            for (lang, v) in obj {
+                // Check if this is the new format with "voices" key
+                if let serde_json::Value::Object(o) = v {
+                    if o.contains_key("voices") {
+                        // New format: { "default_voice": "...", "voices": {...} }
+                        let default_voice = o
+                            .get("default_voice")
+                            .and_then(|x| x.as_str())
+                            .ok_or_else(|| anyhow::anyhow!("missing 'default_voice' for language {}", lang))?
+                            .to_string();
+
+                        let voices_obj = o
+                            .get("voices")
+                            .and_then(|x| x.as_object())
+                            .ok_or_else(|| anyhow::anyhow!("missing 'voices' object for language {}", lang))?;
+
+                        load_lexicon(&mut lexicons, o, lang, None)?;
+                        let language_engine = engine(o)?;
+                        insert_pauses(&mut pauses, o, lang)?;
+
+                        let mut voices: HashMap<String, VoiceEntry> = HashMap::new();
+                        for (voice_id, voice_data) in voices_obj {
+                            if let serde_json::Value::Object(vo) = voice_data {
+                                let config = vo
+                                    .get("config")
+                                    .and_then(|x| x.as_str())
+                                    .ok_or_else(|| anyhow::anyhow!("missing 'config' for voice {}", voice_id))?
+                                    .to_string();
+
+                                let speaker_id = vo.get("speaker_id").and_then(|x| x.as_i64());
+                                let field = |key: &str| vo.get(key).and_then(|x| x.as_str()).map(|s| s.to_string());
+                                // metadata.json is only read in discovery mode (see `overlay`)
+                                let voice_entry = VoiceEntry {
+                                    config: config.clone(),
+                                    speaker_id,
+                                    display_name: field("display_name"),
+                                    gender: field("gender"),
+                                    quality: field("quality"),
+                                    prosody: Prosody::from_json(vo),
+                                    loudness: LoudnessTarget::from_json(vo),
+                                    pauses: PauseConfig::from_json(vo).with_context(|| format!("voice {}", voice_id))?,
+                                    sha256: field("sha256"),
+                                    config_sha256: field("config_sha256"),
+                                    metadata: None,
+                                };
+
+                                if let Some(engine) = engine(vo)?.or(language_engine) {
+                                    engines.insert(config.clone(), engine);
+                                }
+                                voices.insert(voice_id.clone(), voice_entry);
+                                load_lexicon(&mut lexicons, vo, lang, Some(voice_id))?;
+
+                                // Also populate legacy map with default voice for backwards compatibility
+                                if voice_id == &default_voice {
+                                    map.insert(lang.clone(), (config, speaker_id));
+                                }
+                            }
+                        }
+
+                        voices_map.insert(lang.clone(), (default_voice, voices));
+                        continue;
+                    }
+                }
+
+                // Legacy format handling
+                match v {
+                    serde_json::Value::String(path) => {
+                        map.insert(lang.clone(), (path.clone(), None));
+                    }
+                    serde_json::Value::Object(o) => {
+                        let config = o
+                            .get("config")
+                            .and_then(|x| x.as_str())
+                            .ok_or_else(|| anyhow::anyhow!("missing 'config' for key {}", lang))?
+                            .to_string();
+                        let spk = o.get("default_speaker").and_then(|x| x.as_i64());
+                        if let Some(engine) = engine(o)? {
+                            engines.insert(config.clone(), engine);
+                        }
+                        map.insert(lang.clone(), (config, spk));
+                        load_lexicon(&mut lexicons, o, lang, None)?;
+                        insert_pauses(&mut pauses, o, lang)?;
+                    }
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "invalid entry for key {} (expected string or object)",
+                            lang
+                        ));
+                    }
+                }
+            }
+        } else {
+            return Err(anyhow::anyhow!("map.json must be a JSON object"));
+        }
+
+        Ok((Self { map, voices_map, models_dir: None, engines, pauses }, lexicons))
+    }
+
+    /// Scan `<dir>/<language>/<voice>/` folders holding a Piper config (`*.onnx.json`) next to a
+    /// metadata.json. Language and voice ID come from the metadata, or else from the folder
+    /// names; the default voice of a language is the first by ID.
+    pub(crate) fn discover(dir: &Path) -> anyhow::Result<Self> {
+        let mut voices_map: HashMap<String, (String, HashMap<String, VoiceEntry>)> = HashMap::new();
+        for lang_dir in sorted_subdirs(dir)? {
+            for voice_dir in sorted_subdirs(&lang_dir)? {
+                if !voice_dir.join("metadata.json").is_file() {
+                    continue;
+                }
+                let Some(config) = sorted_files(&voice_dir)?
+                    .into_iter()
+                    .find(|file| file.to_string_lossy().ends_with(".onnx.json"))
+                else {
+                    continue;
+                };
+                let config = config.display().to_string();
+                let Some(entry) = VoiceEntry::from_metadata_or_warn(&config) else {
+                    continue;
+                };
+                let metadata = entry.metadata.as_ref();
+                let lang = metadata
+                    .and_then(|m| m.language.clone())
+                    .unwrap_or_else(|| file_name(&lang_dir));
+                let voice_id = metadata.and_then(|m| m.id.clone()).unwrap_or_else(|| file_name(&voice_dir));
+
+                let (default_voice, voices) = voices_map
+                    .entry(lang)
+                    .or_insert_with(|| (voice_id.clone(), HashMap::new()));
+                if voice_id < *default_voice {
+                    *default_voice = voice_id.clone();
+                }
+                voices.insert(voice_id, entry);
+            }
+        }
+
+        let mut discovered = Self {
+            map: HashMap::new(),
+            voices_map,
+            models_dir: Some(dir.to_path_buf()),
+            engines: HashMap::new(),
+            pauses: HashMap::new(),
+        };
+        discovered.set_default_map_entries();
+        Ok(discovered)
+    }
+
+    /// Discovered voices with the entries of a map.json on top. A map.json voice replaces the
+    /// discovered voice with the same language and ID, keeping the discovered values it leaves
+    /// out (or those of the metadata.json next to its config, if it was not discovered);
+    /// map.json default voices win; a legacy entry replaces the discovered voices of its
+    /// language.
+    pub(crate) fn overlay(discovered: Self, overrides: &Self) -> Self {
+        let mut voices_map = discovered.voices_map;
+        for lang in overrides.map.keys().filter(|lang| !overrides.voices_map.contains_key(*lang)) {
+            voices_map.remove(lang);
+        }
+        for (lang, (default_voice, voices)) in &overrides.voices_map {
+            let (default, merged) = voices_map
+                .entry(lang.clone())
+                .or_insert_with(|| (default_voice.clone(), HashMap::new()));
+            default.clone_from(default_voice);
+            for (voice_id, entry) in voices {
+                let found = merged.remove(voice_id);
+                let entry = entry.clone().or_else(|| found.or_else(|| VoiceEntry::from_metadata_or_warn(&entry.config)));
+                merged.insert(voice_id.clone(), entry);
+            }
+        }
+
+        let mut engines = discovered.engines;
+        engines.extend(overrides.engines.iter().map(|(config, engine)| (config.clone(), *engine)));
+        let mut merged = Self {
+            map: overrides.map.clone(),
+            voices_map,
+            models_dir: discovered.models_dir,
+            engines,
+            pauses: overrides.pauses.clone(),
+        };
+        merged.set_default_map_entries();
+        merged
+    }
+
+    // Legacy map entries for the default voice of each language (kept for backwards compatibility)
+    fn set_default_map_entries(&mut self) {
+        for (lang, (default_voice, voices)) in &self.voices_map {
+            if let Some(entry) = voices.get(default_voice) {
+                self.map.insert(lang.clone(), (entry.config.clone(), entry.speaker_id));
+            }
+        }
+    }
+
+    /// Checks beyond parsing, applied before a reload replaces the `current` map: every
+    /// default voice exists, and every new or changed Piper voice has a readable config (mock
+    /// voices need none). Voices that keep the config they have in `current` are not rejected
+    /// when it is broken, as at startup; their IDs are returned (`voice_status` has the details).
+    pub(crate) fn validate(&self, default_engine: EngineKind, current: &VoiceMap) -> Result<Vec<String>, InvalidVoiceMap> {
+        let mut errors: Vec<String> = Vec::new();
+        for (lang, (default_voice, voices)) in &self.voices_map {
+            if !voices.contains_key(default_voice) {
+                errors.push(format!("default voice '{}' of {} is not one of its voices", default_voice, lang));
+            }
+        }
+
+        let current = current.voice_configs();
+        let mut broken: Vec<String> = Vec::new();
+        let mut checked: HashMap<String, Option<String>> = HashMap::new();
+        for (voice, config) in self.voice_configs() {
+            if self.engine_for(&config, default_engine) != EngineKind::Piper {
+                continue;
+            }
+            let problem = checked
+                .entry(config.clone())
+                .or_insert_with(|| TtsManager::read_sample_rate(&config).err().map(|e| format!("{:#}", e)));
+            let Some(problem) = problem else {
+                continue;
+            };
+            if current.get(&voice) == Some(&config) {
+                broken.push(voice);
+            } else {
+                errors.push(format!("invalid voice config {} of {}: {}", config, voice, problem));
+            }
+        }
+        errors.sort();
+        if errors.is_empty() { Ok(broken) } else { Err(InvalidVoiceMap { errors }) }
+    }
+
+    pub(crate) fn engine_for(&self, config: &str, default_engine: EngineKind) -> EngineKind {
+        self.engines.get(config).copied().unwrap_or(default_engine)
+    }
+
+    /// Config paths of all voices
+    pub(crate) fn config_paths(&self) -> HashSet<String> {
+        self.map
+            .values()
+            .map(|(config, _)| config.clone())
+            .chain(self.voices_map.values().flat_map(|(_, voices)| voices.values().map(|v| v.config.clone())))
+            .collect()
+    }
+
+    /// `language/voice` for voices of the new format, `language` for legacy entries
+    pub(crate) fn voice_ids(&self) -> BTreeSet<String> {
+        self.voice_configs().into_keys().collect()
+    }
+
+    /// Config path of every voice, by `voice_ids` ID
+    fn voice_configs(&self) -> BTreeMap<String, String> {
+        let mut configs: BTreeMap<String, String> = self
+            .voices_map
+            .iter()
+            .flat_map(|(lang, (_, voices))| {
+                voices.iter().map(move |(voice, entry)| (format!("{lang}/{voice}"), entry.config.clone()))
+            })
+            .collect();
+        configs.extend(
+            self.map
+                .iter()
+                .filter(|(lang, _)| !self.voices_map.contains_key(*lang))
+                .map(|(lang, (config, _))| (lang.clone(), config.clone())),
+        );
+        configs
+    }
+
+    /// True if the language (and voice) of a lexicon scope exist
+    pub(crate) fn has_scope(&self, scope: &LexiconScope) -> bool {
+        match &scope.voice {
+            Some(voice) => self
+                .voices_map
+                .get(&scope.language)
+                .is_some_and(|(_, voices)| voices.contains_key(voice)),
+            None => self.map.contains_key(&scope.language) || self.voices_map.contains_key(&scope.language),
+        }
+    }
+}
+
+impl VoiceEntry {
+    /// Entry built from the metadata.json next to a voice config (`None` without one)
+    fn from_metadata(config: &str) -> anyhow::Result<Option<Self>> {
+        Ok(VoiceMetadata::for_config(config)?.map(|metadata| Self {
+            config: config.to_string(),
+            speaker_id: None,
+            display_name: metadata.display_name.clone(),
+            gender: metadata.gender.clone(),
+            quality: metadata.quality.clone(),
+            prosody: Prosody::default(),
+            loudness: LoudnessTarget::default(),
+            pauses: PauseConfig::default(),
+            sha256: metadata.sha256.clone(),
+            config_sha256: metadata.config_sha256.clone(),
+            metadata: Some(metadata),
+        }))
+    }
+
+    /// `from_metadata`, skipping the voice with a warning if its metadata.json is unreadable
+    fn from_metadata_or_warn(config: &str) -> Option<Self> {
+        Self::from_metadata(config).unwrap_or_else(|e| {
+            eprintln!("Warning: skipping the metadata of voice {}: {:#}", config, e);
+            None
+        })
+    }
+
+    /// Fill the values this entry leaves out from another entry of the same voice
+    fn or_else(self, fallback: impl FnOnce() -> Option<Self>) -> Self {
+        let Some(fallback) = fallback() else {
+            return self;
+        };
+        Self {
+            speaker_id: self.speaker_id.or(fallback.speaker_id),
+            display_name: self.display_name.or(fallback.display_name),
+            gender: self.gender.or(fallback.gender),
+            quality: self.quality.or(fallback.quality),
+            sha256: self.sha256.or(fallback.sha256),
+            config_sha256: self.config_sha256.or(fallback.config_sha256),
+            metadata: self.metadata.or(fallback.metadata),
+            ..self
+        }
+    }
+}
+
+fn sorted_subdirs(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    Ok(sorted_entries(dir)?.into_iter().filter(|path| path.is_dir()).collect())
+}
+
+fn sorted_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    Ok(sorted_entries(dir)?.into_iter().filter(|path| path.is_file()).collect())
+}
+
+fn sorted_entries(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// `"engine"` of a map.json entry, if any
+fn engine(entry: &serde_json::Map<String, serde_json::Value>) -> anyhow::Result<Option<EngineKind>> {
+    entry.get("engine").and_then(|x| x.as_str()).map(str::parse).transpose()
+}
+
+/// Pause settings of a language entry, if it has any
+fn insert_pauses(
+    pauses: &mut HashMap<String, PauseConfig>,
+    entry: &serde_json::Map<String, serde_json::Value>,
+    lang: &str,
+) -> anyhow::Result<()> {
+    let config = PauseConfig::from_json(entry).with_context(|| format!("language {}", lang))?;
+    if !config.is_default() {
+        pauses.insert(lang.to_string(), config);
+    }
+    Ok(())
+}
+
+/// Load the lexicon file referenced by a map.json entry's `"lexicon"` key, if any
+fn load_lexicon(
+    lexicons: &mut Lexicons,
+    entry: &serde_json::Map<String, serde_json::Value>,
+    lang: &str,
+    voice: Option<&str>,
+) -> anyhow::Result<()> {
+    if let Some(path) = entry.get("lexicon").and_then(|x| x.as_str()) {
+        let scope = LexiconScope { language: lang.to_string(), voice: voice.map(|v| v.to_string()) };
+        lexicons.set(scope, Lexicon::load(path)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let dir = std::env::temp_dir().join(format!("tts-voice-map-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("voice.onnx.json");
+        fs::write(&config, r#"{"audio": {"sample_rate": 22050}}"#).unwrap();
+        let config = config.display().to_string();
+
+        let text = format!(
+            r#"{{"en_US": {{"default_voice": "amy", "voices": {{"amy": {{"config": "{config}"}}, "ryan": {{"config": "{config}"}}}}}},
+                "de_DE": "{config}"}}"#
+        );
+        // metadata.json is not read without discovery, so a broken one does not matter
+        fs::write(dir.join("metadata.json"), "{").unwrap();
+        let (voices, _) = VoiceMap::parse(&text).unwrap();
+        voices.validate(EngineKind::Piper, &VoiceMap::default()).unwrap();
+        assert!(voices.voices_map["en_US"].1["amy"].metadata.is_none());
+        assert_eq!(voices.voice_ids().into_iter().collect::<Vec<_>>(), vec!["de_DE", "en_US/amy", "en_US/ryan"]);
+        assert_eq!(voices.config_paths().len(), 1);
+        assert!(voices.has_scope(&LexiconScope { language: "en_US".to_string(), voice: Some("ryan".to_string()) }));
+
+        // Unknown default voice and missing config files are rejected
+        let (voices, _) = VoiceMap::parse(&text.replace(r#""default_voice": "amy""#, r#""default_voice": "joe""#)).unwrap();
+        assert!(voices.validate(EngineKind::Piper, &VoiceMap::default()).is_err());
+        let (voices, _) = VoiceMap::parse(r#"{"de_DE": "/nonexistent/voice.onnx.json"}"#).unwrap();
+        assert!(voices.validate(EngineKind::Piper, &VoiceMap::default()).is_err());
+        // ... unless the voice runs on the mock engine
+        voices.validate(EngineKind::Mock, &VoiceMap::default()).unwrap();
+        let (voices, _) = VoiceMap::parse(r#"{"de_DE": {"config": "/nonexistent/voice.onnx.json", "engine": "mock"}}"#).unwrap();
+        voices.validate(EngineKind::Piper, &VoiceMap::default()).unwrap();
+        // Every problem is reported
+        let (voices, _) = VoiceMap::parse(&format!(
+            r#"{{"de_DE": "/nonexistent/voice.onnx.json", "en_US": {{"default_voice": "joe", "voices": {{"amy": {{"config": "{config}"}}}}}}}}"#
+        ))
+        .unwrap();
+        let errors = voices.validate(EngineKind::Piper, &VoiceMap::default()).unwrap_err().errors;
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("default voice 'joe' of en_US"));
+        assert!(errors[1].starts_with("invalid voice config /nonexistent/voice.onnx.json"));
+        assert!(VoiceMap::parse(r#"{"de_DE": {"config": "x.onnx.json", "engine": "espeak"}}"#).is_err());
+        assert!(VoiceMap::parse("[]").is_err());
+
+        // A voice that keeps its broken config is tolerated; the same config on a new voice is not
+        let (current, _) = VoiceMap::parse(r#"{"de_DE": "/nonexistent/voice.onnx.json"}"#).unwrap();
+        let (voices, _) = VoiceMap::parse(&format!(r#"{{"de_DE": "/nonexistent/voice.onnx.json", "fr_FR": "{config}"}}"#)).unwrap();
+        assert_eq!(voices.validate(EngineKind::Piper, &current).unwrap(), vec!["de_DE"]);
+        let (voices, _) = VoiceMap::parse(r#"{"de_DE": "/nonexistent/voice.onnx.json", "fr_FR": "/nonexistent/voice.onnx.json"}"#).unwrap();
+        let errors = voices.validate(EngineKind::Piper, &current).unwrap_err().errors;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("invalid voice config /nonexistent/voice.onnx.json of fr_FR: Failed to read config file"));
+
+        // Pause settings per language and voice
+        let (voices, _) = VoiceMap::parse(
+            r#"{"en_US": {"default_voice": "amy", "pauses": {"sentence_ms": 300, "shape": "linear"},
+                "voices": {"amy": {"config": "a.onnx.json", "pauses": {"comma_ms": 0}}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(voices.pauses["en_US"].sentence_ms, Some(300));
+        assert_eq!(voices.voices_map["en_US"].1["amy"].pauses.comma_ms, Some(0));
+        assert!(VoiceMap::parse(r#"{"de_DE": {"config": "x.onnx.json", "pauses": {"shape": "wavy"}}}"#).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_reload_keeps_broken_voice() {
+        let dir = std::env::temp_dir().join(format!("tts-voice-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let good = dir.join("good.onnx.json");
+        fs::write(&good, r#"{"audio": {"sample_rate": 22050}}"#).unwrap();
+        // A Git LFS pointer instead of the config
+        let broken = dir.join("broken.onnx.json");
+        fs::write(&broken, "version https://git-lfs.github.com/spec/v1\n").unwrap();
+        let map_file = dir.join("map.json");
+        let (good, broken) = (good.display().to_string(), broken.display().to_string());
+        fs::write(&map_file, format!(r#"{{"de_DE": "{good}", "en_US": "{broken}"}}"#)).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let tts = TtsManager::new_from_mapfile(&map_file).unwrap();
+        fs::write(&map_file, format!(r#"{{"de_DE": "{good}", "en_US": "{broken}", "fr_FR": "{good}"}}"#)).unwrap();
+        let reload = runtime.block_on(tts.reload_mapfile(&map_file)).unwrap();
+        assert_eq!(reload.added, vec!["fr_FR"]);
+        assert_eq!(reload.broken, vec!["en_US"]);
+
+        // A new voice with the broken config rejects the map
+        fs::write(&map_file, format!(r#"{{"de_DE": "{good}", "en_US": "{broken}", "fr_FR": "{broken}"}}"#)).unwrap();
+        let error = runtime.block_on(tts.reload_mapfile(&map_file)).unwrap_err();
+        assert_eq!(error.downcast::<InvalidVoiceMap>().unwrap().errors.len(), 1);
+        assert!(tts.list_languages().contains(&"fr_FR".to_string()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_discover_and_overlay() {
+        let dir = std::env::temp_dir().join(format!("tts-voice-discovery-{}", std::process::id()));
+        for (lang, voice, metadata) in [
+            ("en_US", "amy", r#"{"id": "amy", "display_name": "Amy", "gender": "female", "accent": "US", "tags": ["calm"]}"#),
+            ("en_US", "ryan", r#"{"display_name": "Ryan", "dataset": "ryan-2023"}"#),
+            ("fr_FR", "tom", r#"{"language": "fr_FR", "quality": "medium"}"#),
+        ] {
+            let voice_dir = dir.join(lang).join(voice);
+            fs::create_dir_all(&voice_dir).unwrap();
+            fs::write(voice_dir.join("config.onnx.json"), r#"{"audio": {"sample_rate": 22050}}"#).unwrap();
+            fs::write(voice_dir.join("metadata.json"), metadata).unwrap();
+        }
+        // Without metadata.json the folder is not a discovered voice
+        fs::create_dir_all(dir.join("en_US").join("draft")).unwrap();
+        fs::write(dir.join("en_US").join("draft").join("config.onnx.json"), "{}").unwrap();
+        // A broken metadata.json skips its voice only
+        fs::create_dir_all(dir.join("en_US").join("broken")).unwrap();
+        fs::write(dir.join("en_US").join("broken").join("config.onnx.json"), "{}").unwrap();
+        fs::write(dir.join("en_US").join("broken").join("metadata.json"), r#"{"tags": "calm"}"#).unwrap();
+
+        let discovered = VoiceMap::discover(&dir).unwrap();
+        assert_eq!(discovered.voice_ids().into_iter().collect::<Vec<_>>(), vec!["en_US/amy", "en_US/ryan", "fr_FR/tom"]);
+        let (default_voice, voices) = &discovered.voices_map["en_US"];
+        assert_eq!(default_voice, "amy");
+        let amy = &voices["amy"];
+        assert_eq!(amy.display_name.as_deref(), Some("Amy"));
+        assert_eq!(amy.metadata.as_ref().unwrap().tags, vec!["calm"]);
+        assert_eq!(voices["ryan"].metadata.as_ref().unwrap().dataset.as_deref(), Some("ryan-2023"));
+        discovered.validate(EngineKind::Piper, &VoiceMap::default()).unwrap();
+
+        // map.json wins where it has values; a legacy entry replaces the discovered language
+        let ryan = dir.join("en_US").join("ryan").join("config.onnx.json").display().to_string();
+        let (overrides, _) = VoiceMap::parse(&format!(
+            r#"{{"en_US": {{"default_voice": "ryan", "voices": {{"ryan": {{"config": "{ryan}", "display_name": "Ryan (US)", "speaker_id": 0}}}}}},
+                "fr_FR": "{ryan}"}}"#
+        ))
+        .unwrap();
+        let merged = VoiceMap::overlay(discovered, &overrides);
+        let (default_voice, voices) = &merged.voices_map["en_US"];
+        assert_eq!(default_voice, "ryan");
+        assert_eq!(voices["ryan"].display_name.as_deref(), Some("Ryan (US)"));
+        assert_eq!(voices["ryan"].speaker_id, Some(0));
+        assert_eq!(voices["ryan"].metadata.as_ref().unwrap().dataset.as_deref(), Some("ryan-2023"));
+        assert!(voices.contains_key("amy"));
+        assert_eq!(merged.map["en_US"], (ryan.clone(), Some(0)));
+        assert!(!merged.voices_map.contains_key("fr_FR"));
+        assert_eq!(merged.map["fr_FR"], (ryan, None));
+        assert_eq!(merged.models_dir.as_deref(), Some(dir.as_path()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,207 @@
+//! Integrity checks of the voice files.
+//!
+//! Model files in the repository are stored with Git LFS; a checkout without LFS leaves small
+//! pointer files in their place, which only fail once Piper tries to load them. These checks
+//! find such problems up front, without loading any model. Checksums are remembered per file
+//! size and modification time, so repeated checks (`GET /voices/status`) only hash changed files.
+
+use std::{
+    fmt, fs,
+    io::Read,
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{file_hash, model_cache, voice_map::VoiceMap, EngineKind, MockEngine, TtsEngine, TtsManager};
+
+const LFS_POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+
+/// Result of checking one voice
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceStatus {
+    /// `language/voice` (or `language` for legacy entries)
+    pub voice: String,
+    pub engine: EngineKind,
+    pub config: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    pub issues: Vec<VoiceIssue>,
+}
+
+impl VoiceStatus {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A problem that keeps a voice from loading
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum VoiceIssue {
+    Missing { file: String },
+    /// Git LFS pointer instead of the real file (run `git lfs pull`)
+    LfsPointer { file: String },
+    InvalidConfig { error: String },
+    ChecksumMismatch { file: String, expected: String, actual: String },
+}
+
+impl fmt::Display for VoiceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { file } => write!(f, "{file} is missing"),
+            Self::LfsPointer { file } => write!(f, "{file} is a Git LFS pointer (run `git lfs pull`)"),
+            Self::InvalidConfig { error } => write!(f, "invalid config: {error}"),
+            Self::ChecksumMismatch { file, expected, actual } => {
+                write!(f, "{file} has SHA-256 {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
+/// Expected SHA-256 (hex) of a voice's files, if known
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Checksums<'a> {
+    pub(crate) config: Option<&'a str>,
+    pub(crate) model: Option<&'a str>,
+}
+
+/// Check every voice of a map, sorted by voice. Mock voices need no files and always pass.
+pub(crate) fn check_all(voices: &VoiceMap, default_engine: EngineKind) -> Vec<VoiceStatus> {
+    let check = |voice: String, config: &str, checksums: &Checksums| match voices.engine_for(config, default_engine) {
+        EngineKind::Piper => check_voice(voice, config, checksums),
+        EngineKind::Mock => VoiceStatus {
+            voice,
+            engine: EngineKind::Mock,
+            config: config.to_string(),
+            model: model_cache::model_path(Path::new(config)).display().to_string(),
+            sample_rate: Some(MockEngine::for_config(Path::new(config)).sample_rate()),
+            issues: Vec::new(),
+        },
+    };
+    let mut statuses: Vec<VoiceStatus> = voices
+        .voices_map
+        .iter()
+        .flat_map(|(lang, (_, entries))| {
+            entries.iter().map(move |(voice_id, entry)| {
+                let checksums = Checksums { config: entry.config_sha256.as_deref(), model: entry.sha256.as_deref() };
+                check(format!("{lang}/{voice_id}"), &entry.config, &checksums)
+            })
+        })
+        .collect();
+    statuses.extend(
+        voices
+            .map
+            .iter()
+            .filter(|(lang, _)| !voices.voices_map.contains_key(*lang))
+            .map(|(lang, (config, _))| check(lang.clone(), config, &Checksums::default())),
+    );
+    statuses.sort_by(|a, b| a.voice.cmp(&b.voice));
+    statuses
+}
+
+/// Check that a voice's config and model exist, are not LFS pointers, that the config has a
+/// sample rate and that the files match the expected checksums
+pub(crate) fn check_voice(voice: String, config: &str, checksums: &Checksums) -> VoiceStatus {
+    let config_path = Path::new(config);
+    let model_path = model_cache::model_path(config_path);
+    let mut status = VoiceStatus {
+        voice,
+        engine: EngineKind::Piper,
+        config: config.to_string(),
+        model: model_path.display().to_string(),
+        sample_rate: None,
+        issues: Vec::new(),
+    };
+
+    let config_ok = check_file(config_path, checksums.config, &mut status.issues);
+    check_file(&model_path, checksums.model, &mut status.issues);
+    if config_ok {
+        match TtsManager::read_sample_rate(config_path) {
+            Ok(sample_rate) => status.sample_rate = Some(sample_rate),
+            Err(e) => status.issues.push(VoiceIssue::InvalidConfig { error: format!("{e:#}") }),
+        }
+    }
+    status
+}
+
+// Existence, LFS pointer and checksum of one file; false if the file is not usable at all
+fn check_file(path: &Path, expected_sha256: Option<&str>, issues: &mut Vec<VoiceIssue>) -> bool {
+    let file = path.display().to_string();
+    if !path.is_file() {
+        issues.push(VoiceIssue::Missing { file });
+        return false;
+    }
+    if is_lfs_pointer(path) {
+        issues.push(VoiceIssue::LfsPointer { file });
+        return false;
+    }
+    if let Some(expected) = expected_sha256 {
+        match file_hash::sha256_hex(path) {
+            Ok(actual) if actual.eq_ignore_ascii_case(expected.trim()) => {}
+            Ok(actual) => issues.push(VoiceIssue::ChecksumMismatch { file, expected: expected.to_string(), actual }),
+            Err(_) => {
+                issues.push(VoiceIssue::Missing { file });
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn is_lfs_pointer(path: &Path) -> bool {
+    let mut head = [0u8; LFS_POINTER_PREFIX.len()];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut head))
+        .is_ok_and(|_| head == LFS_POINTER_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_broken_voices() {
+        let dir = std::env::temp_dir().join(format!("tts-voice-status-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = dir.join(name);
+            fs::write(&path, contents).unwrap();
+            path.display().to_string()
+        };
+        let good = write("good.onnx.json", r#"{"audio": {"sample_rate": 22050}}"#);
+        write("good.onnx", "weights");
+        let pointer = write(
+            "pointer.onnx.json",
+            "version https://git-lfs.github.com/spec/v1\noid sha256:95a2\nsize 4882\n",
+        );
+        write("pointer.onnx", "weights");
+        let no_rate = write("no_rate.onnx.json", r#"{"audio": {}}"#);
+
+        let status = check_voice("en_US/good".to_string(), &good, &Checksums::default());
+        assert!(status.is_ok());
+        assert_eq!(status.sample_rate, Some(22050));
+
+        let status = check_voice("en_US/pointer".to_string(), &pointer, &Checksums::default());
+        assert_eq!(status.issues, vec![VoiceIssue::LfsPointer { file: pointer.clone() }]);
+
+        let status = check_voice("en_US/no_rate".to_string(), &no_rate, &Checksums::default());
+        assert_eq!(status.issues.len(), 2); // model missing, no sample rate
+        assert!(matches!(status.issues[0], VoiceIssue::Missing { .. }));
+        assert!(matches!(status.issues[1], VoiceIssue::InvalidConfig { .. }));
+
+        // SHA-256 of "weights"
+        let sha256 = "9a129038d9a00aed0cf6a7ea059ca50a813449061ab87848cf1a13eafdf33b2c";
+        let status = check_voice("en_US/good".to_string(), &good, &Checksums { config: None, model: Some(sha256) });
+        assert!(status.is_ok());
+        let status = check_voice("en_US/good".to_string(), &good, &Checksums { config: None, model: Some(&sha256.replace('9', "0")) });
+        assert!(matches!(&status.issues[..], [VoiceIssue::ChecksumMismatch { actual, .. }] if actual == sha256));
+
+        // The remembered hash is not used once the file changes
+        fs::write(dir.join("good.onnx"), "new weights").unwrap();
+        let status = check_voice("en_US/good".to_string(), &good, &Checksums { config: None, model: Some(sha256) });
+        assert!(matches!(status.issues[..], [VoiceIssue::ChecksumMismatch { .. }]));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}